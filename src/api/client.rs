@@ -1,13 +1,113 @@
 //! Mastodon API HTTP client. On 401 clears token and returns `NotAuthenticated`.
 //! r[timeline.home.fetch] r[timeline.pagination] r[toot.post.submit] r[toot.reply] r[toot.boost.toggle] r[toot.favourite.toggle]
-//! r[auth.login.invalid-token]
+//! r[auth.login.invalid-token] r[toot.schedule.compose] r[toot.schedule.list] r[toot.schedule.reschedule]
+//! r[toot.schedule.cancel] r[dm.list] r[dm.mark-read] r[dm.thread] r[dm.reply]
+//! r[filter.manage.list] r[filter.manage.edit] r[filter.manage.keywords] r[mute.rules]
+//! r[list.manage.edit] r[list.manage.members] r[list.add-author] r[follows.list] r[follows.bulk]
+//! r[follow-requests.list] r[follow-requests.decide] r[follow-requests.notify]
+//! r[moderation.mutes] r[moderation.blocks] r[moderation.domains] r[moderation.undo]
+//! r[report.rules] r[report.statuses] r[report.submit] r[explore.trends] r[explore.anonymous]
+//! r[markers.restore] r[markers.save] r[outbox.idempotent] r[outbox.reconcile]
+//! r[ratelimit.track] r[ratelimit.errors] r[ratelimit.retry] r[errors.decode] r[errors.debug]
+//! r[api.endpoint]
 
-use reqwest::Client;
+use std::sync::Mutex;
+use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+
+use serde::de::DeserializeOwned;
+
+use crate::api::endpoint::{next_max_id, Empty, Endpoint, Paging};
 use crate::api::oauth::{app_token_client_credentials, register_app_if_needed};
-use crate::api::types::{List, Status};
+use crate::api::types::{
+    Account, Context, Conversation, Filter, FilterAction, FilterKeyword, List, Markers, NewReport,
+    Notification, Relationship, RepliesPolicy, Rule, ScheduledStatus, Status, Trends, TrendsKind,
+    Visibility,
+};
 use crate::credential::{delete_access_token, get_access_token, instance_host_from_url};
 use crate::error::{MastotuiError, Result};
+use crate::outbox::{OutboxAction, Performed};
+
+/// Times a GET is retried after 429, 502, 503 or 504.
+const MAX_GET_RETRIES: u32 = 3;
+
+/// Longest a GET waits in total across its retries. Requests run on the UI thread, so
+/// beyond this the error is reported at once rather than freezing the screen. The backoff
+/// of all retries (250ms, 500ms, 1s, each with up to 100ms of jitter) fits; a `Retry-After`
+/// that does not is reported as `RateLimited` without waiting.
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(3);
+
+/// Longest wait for a connection to the instance. Requests, including the outbox flush on
+/// every tick, block the UI thread, so an unreachable host must fail fast.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest a whole request may take, response body included.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// HTTP client shared by the logged-in and anonymous clients, with the timeouts above.
+fn http_client() -> Result<Client> {
+    Ok(Client::builder()
+        .user_agent("mastotui/0.1")
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()?)
+}
+
+/// The account's request quota from the last response's `X-RateLimit-*` headers.
+/// r[ratelimit.track]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    /// RFC 3339 time the quota is refilled.
+    pub reset: Option<String>,
+}
+
+impl RateLimit {
+    /// Read the quota headers; None when the server does not send them.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse().ok();
+        Some(Self {
+            limit: number("x-ratelimit-limit")?,
+            remaining: number("x-ratelimit-remaining")?,
+            reset: headers
+                .get("x-ratelimit-reset")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        })
+    }
+
+    /// Less than a tenth of the quota is left, which is kept for the user's own actions.
+    #[must_use]
+    pub const fn is_low(&self) -> bool {
+        self.remaining < self.limit.div_ceil(10)
+    }
+
+    /// When bulk work should wait until: the reset time, if the quota is low and the reset
+    /// is still ahead of `now`. r[ratelimit.bulk]
+    #[must_use]
+    pub fn pause_until(&self, now: DateTime<Utc>) -> Option<&str> {
+        let reset = self.reset.as_deref()?;
+        let at = DateTime::parse_from_rfc3339(reset).ok()?;
+        (self.is_low() && at > now).then_some(reset)
+    }
+}
+
+/// The last request the server answered with an error, for the debug panel.
+/// r[errors.debug]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedRequest {
+    pub status: u16,
+    pub url: String,
+    /// RFC 3339 time of the response.
+    pub at: String,
+    /// Response body exactly as received.
+    pub body: String,
+}
 
 /// API client for a single Mastodon instance with a given access token.
 pub struct MastodonClient {
@@ -15,44 +115,110 @@ pub struct MastodonClient {
     instance_host: String,
     access_token: String,
     client: Client,
+    /// Quota from the most recent response. r[ratelimit.track]
+    rate_limit: Mutex<Option<RateLimit>>,
+    /// Most recent error response. r[errors.debug]
+    last_error: Mutex<Option<FailedRequest>>,
 }
 
 impl MastodonClient {
     pub fn new(base_url: &str, access_token: &str) -> Result<Self> {
         let instance_host = instance_host_from_url(base_url)?;
-        let client = Client::builder().user_agent("mastotui/0.1").build()?;
+        let client = http_client()?;
         Ok(Self {
             base_url: base_url.trim_end_matches('/').to_string(),
             instance_host,
             access_token: access_token.to_string(),
             client,
+            rate_limit: Mutex::new(None),
+            last_error: Mutex::new(None),
         })
     }
 
-    fn api_url(&self, path: &str) -> String {
-        format!("{}/api/v1{}", self.base_url, path)
+    /// The last error response, with its raw body.
+    #[must_use]
+    pub fn last_error(&self) -> Option<FailedRequest> {
+        self.last_error.lock().ok()?.clone()
+    }
+
+    /// Decode a failed response into an error and remember it for the debug panel.
+    /// r[errors.decode] r[errors.debug]
+    async fn api_error(&self, response: reqwest::Response) -> MastotuiError {
+        api_error(response, &self.last_error).await
+    }
+
+    /// The request quota as of the last response, if the server reports one.
+    #[must_use]
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().ok()?.clone()
+    }
+
+    /// Send an endpoint and decode its response. r[api.endpoint]
+    async fn call<T: DeserializeOwned>(&self, endpoint: Endpoint<T>) -> Result<T> {
+        Ok(self.call_page(endpoint).await?.0)
+    }
+
+    /// Send an endpoint and decode its response, with the `max_id` of the next page for
+    /// `Paging::Link` endpoints. An empty body decodes as JSON `null`.
+    async fn call_page<T: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint<T>,
+    ) -> Result<(T, Option<String>)> {
+        let url = endpoint.url(&self.base_url)?;
+        let response = self
+            .send(
+                endpoint.method,
+                url.as_str(),
+                endpoint.body,
+                endpoint.idempotency_key.as_deref(),
+            )
+            .await?;
+        if !response.status().is_success() {
+            return Err(self.api_error(response).await);
+        }
+        let next = match endpoint.paging {
+            Paging::Link => next_max_id(
+                response
+                    .headers()
+                    .get(reqwest::header::LINK)
+                    .and_then(|v| v.to_str().ok()),
+            ),
+            Paging::Single => None,
+        };
+        let bytes = response.bytes().await?;
+        let body: &[u8] = if bytes.is_empty() { b"null" } else { &bytes };
+        Ok((serde_json::from_slice(body)?, next))
     }
 
-    /// On 401, clear stored token and return `NotAuthenticated`.
-    async fn request(
+    /// On 401, clear stored token and return `NotAuthenticated`. With an idempotency key,
+    /// sends the `Idempotency-Key` header so the server ignores a repeat of a write it
+    /// already performed. r[outbox.idempotent]
+    ///
+    /// Rate limits, retries and 429/502–504 errors are handled by `send_recorded`.
+    async fn send(
         &self,
         method: reqwest::Method,
-        path: &str,
+        url: &str,
         body: Option<serde_json::Value>,
+        idempotency_key: Option<&str>,
     ) -> Result<reqwest::Response> {
-        let url = self.api_url(path);
-        let mut req = self
-            .client
-            .request(method, &url)
-            .header("Authorization", format!("Bearer {}", self.access_token))
-            .header("Content-Type", "application/json");
-
-        if let Some(b) = body {
-            req = req.json(&b);
-        }
+        let build = || {
+            let mut req = self
+                .client
+                .request(method.clone(), url)
+                .header("Authorization", format!("Bearer {}", self.access_token))
+                .header("Content-Type", "application/json");
+            if let Some(key) = idempotency_key {
+                req = req.header("Idempotency-Key", key);
+            }
 
-        let response = req.send().await?;
-        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            if let Some(ref b) = body {
+                req = req.json(b);
+            }
+            req
+        };
+        let response = send_recorded(build, &method, &self.rate_limit).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
             let _ = delete_access_token(&self.instance_host);
             return Err(MastotuiError::NotAuthenticated);
         }
@@ -61,29 +227,18 @@ impl MastodonClient {
 
     /// r[timeline.home.fetch]: fetch home timeline
     pub async fn get_timeline_home(&self, max_id: Option<&str>) -> Result<Vec<Status>> {
-        let path = max_id.map_or_else(
-            || "/timelines/home?limit=20".to_string(),
-            |id| format!("/timelines/home?limit=20&max_id={id}"),
-        );
-        self.fetch_timeline_path(&path).await
+        self.call(timeline(&["timelines", "home"], max_id)).await
     }
 
     /// Fetch public timeline (local only) with auth.
     pub async fn get_timeline_local(&self, max_id: Option<&str>) -> Result<Vec<Status>> {
-        let path = max_id.map_or_else(
-            || "/timelines/public?limit=20&local=true".to_string(),
-            |id| format!("/timelines/public?limit=20&local=true&max_id={id}"),
-        );
-        self.fetch_timeline_path(&path).await
+        self.call(timeline(&["timelines", "public"], max_id).query("local", &true))
+            .await
     }
 
     /// Fetch public (federated) timeline with auth.
     pub async fn get_timeline_public(&self, max_id: Option<&str>) -> Result<Vec<Status>> {
-        let path = max_id.map_or_else(
-            || "/timelines/public?limit=20".to_string(),
-            |id| format!("/timelines/public?limit=20&max_id={id}"),
-        );
-        self.fetch_timeline_path(&path).await
+        self.call(timeline(&["timelines", "public"], max_id)).await
     }
 
     /// Fetch a list timeline. Requires read:lists.
@@ -92,159 +247,723 @@ impl MastodonClient {
         list_id: &str,
         max_id: Option<&str>,
     ) -> Result<Vec<Status>> {
-        let path = max_id.map_or_else(
-            || format!("/timelines/list/{list_id}?limit=20"),
-            |id| format!("/timelines/list/{list_id}?limit=20&max_id={id}"),
-        );
-        self.fetch_timeline_path(&path).await
+        self.call(timeline(&["timelines", "list", list_id], max_id))
+            .await
     }
 
     /// Fetch user's lists. Requires read:lists.
     pub async fn get_lists(&self) -> Result<Vec<List>> {
-        let response = self.request(reqwest::Method::GET, "/lists", None).await?;
-        let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
-        }
-        let lists: Vec<List> = response.json().await?;
-        Ok(lists)
+        self.call(Endpoint::get(&["lists"])).await
     }
 
-    async fn fetch_timeline_path(&self, path: &str) -> Result<Vec<Status>> {
-        let response = self.request(reqwest::Method::GET, path, None).await?;
-        let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
-        }
-        let statuses: Vec<Status> = response.json().await?;
-        Ok(statuses)
+    /// r[list.manage.edit]: create (`id` None) or update a list.
+    pub async fn save_list(
+        &self,
+        id: Option<&str>,
+        title: &str,
+        replies_policy: RepliesPolicy,
+        exclusive: bool,
+    ) -> Result<List> {
+        let endpoint = id.map_or_else(
+            || Endpoint::post(&["lists"]),
+            |id| Endpoint::put(&["lists", id]),
+        );
+        self.call(endpoint.body(serde_json::json!({
+            "title": title,
+            "replies_policy": replies_policy,
+            "exclusive": exclusive
+        })))
+        .await
     }
 
-    /// r[toot.post.submit]: post new status
-    pub async fn post_status(&self, status: &str, in_reply_to_id: Option<&str>) -> Result<Status> {
-        let body = serde_json::json!({
-            "status": status,
-            "in_reply_to_id": in_reply_to_id
-        });
-        let response = self
-            .request(reqwest::Method::POST, "/statuses", Some(body))
+    /// r[list.manage.edit]: delete a list.
+    pub async fn delete_list(&self, id: &str) -> Result<()> {
+        self.call(Endpoint::<Empty>::delete(&["lists", id])).await?;
+        Ok(())
+    }
+
+    /// r[list.manage.members]: all accounts in a list (`limit=0` returns every member).
+    pub async fn get_list_accounts(&self, id: &str) -> Result<Vec<Account>> {
+        self.call(Endpoint::get(&["lists", id, "accounts"]).query("limit", &0))
+            .await
+    }
+
+    /// r[list.manage.members]: add (`add` true) or remove accounts. Only followed accounts can
+    /// be added.
+    pub async fn update_list_accounts(
+        &self,
+        id: &str,
+        account_ids: &[String],
+        add: bool,
+    ) -> Result<()> {
+        let method = if add {
+            reqwest::Method::POST
+        } else {
+            reqwest::Method::DELETE
+        };
+        let endpoint = Endpoint::<Empty>::new(method, &["lists", id, "accounts"])
+            .body(serde_json::json!({ "account_ids": account_ids }));
+        self.call(endpoint).await?;
+        Ok(())
+    }
+
+    /// r[list.add-author]: lists of the current user that contain the account.
+    pub async fn get_account_lists(&self, account_id: &str) -> Result<Vec<List>> {
+        self.call(Endpoint::get(&["accounts", account_id, "lists"]))
+            .await
+    }
+
+    /// Resolve `user` or `user@domain` to an account known to the instance.
+    pub async fn lookup_account(&self, acct: &str) -> Result<Account> {
+        let acct = acct.trim().trim_start_matches('@');
+        self.call(Endpoint::get(&["accounts", "lookup"]).query("acct", acct))
+            .await
+    }
+
+    /// r[toot.post.submit]: post new status. `visibility` None uses the account default.
+    /// r[dm.reply]: direct messages are posted with `Visibility::Direct`.
+    pub async fn post_status(
+        &self,
+        status: &str,
+        in_reply_to_id: Option<&str>,
+        visibility: Option<Visibility>,
+    ) -> Result<Status> {
+        self.call(new_status(status, in_reply_to_id, visibility))
+            .await
+    }
+
+    /// r[toot.schedule.compose]: post a status with `scheduled_at`; the server returns a
+    /// `ScheduledStatus` instead of a `Status`.
+    pub async fn schedule_status(
+        &self,
+        status: &str,
+        in_reply_to_id: Option<&str>,
+        visibility: Option<Visibility>,
+        scheduled_at: &str,
+    ) -> Result<ScheduledStatus> {
+        let mut body = status_body(status, in_reply_to_id, visibility);
+        body["scheduled_at"] = serde_json::json!(scheduled_at);
+        self.call(Endpoint::post(&["statuses"]).body(body)).await
+    }
+
+    /// r[toot.schedule.list]: statuses scheduled by the user, soonest first.
+    pub async fn get_scheduled_statuses(&self) -> Result<Vec<ScheduledStatus>> {
+        let mut scheduled: Vec<ScheduledStatus> = self
+            .call(Endpoint::get(&["scheduled_statuses"]).query("limit", &40))
             .await?;
-        let s = response.status();
-        if !s.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: s.as_u16(),
-                message: text,
-            });
-        }
-        Ok(response.json().await?)
+        scheduled.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
+        Ok(scheduled)
+    }
+
+    /// r[toot.schedule.reschedule]: move a scheduled status to a new time.
+    pub async fn reschedule_status(&self, id: &str, scheduled_at: &str) -> Result<ScheduledStatus> {
+        self.call(reschedule(id, scheduled_at)).await
+    }
+
+    /// r[toot.schedule.cancel]: delete a scheduled status so it is never posted.
+    pub async fn cancel_scheduled_status(&self, id: &str) -> Result<()> {
+        self.call(cancel_scheduled(id)).await?;
+        Ok(())
     }
 
     /// r[toot.boost.toggle]: reblog or unreblog
     pub async fn reblog(&self, id: &str, reblog: bool) -> Result<Status> {
-        let path = if reblog {
-            format!("/statuses/{id}/reblog")
-        } else {
-            format!("/statuses/{id}/unreblog")
-        };
-        let response = self.request(reqwest::Method::POST, &path, None).await?;
-        let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
-        }
-        Ok(response.json().await?)
+        self.call(toggle(&["statuses", id], reblog, "reblog", "unreblog"))
+            .await
     }
 
     /// r[toot.favourite.toggle]: favourite or unfavourite
     pub async fn favourite(&self, id: &str, favourite: bool) -> Result<Status> {
-        let path = if favourite {
-            format!("/statuses/{id}/favourite")
-        } else {
-            format!("/statuses/{id}/unfavourite")
+        self.call(toggle(
+            &["statuses", id],
+            favourite,
+            "favourite",
+            "unfavourite",
+        ))
+        .await
+    }
+
+    /// Bookmark or remove a bookmark.
+    pub async fn bookmark(&self, id: &str, bookmark: bool) -> Result<Status> {
+        self.call(toggle(
+            &["statuses", id],
+            bookmark,
+            "bookmark",
+            "unbookmark",
+        ))
+        .await
+    }
+
+    /// Send a queued write with its idempotency key. r[outbox.idempotent]
+    pub async fn perform(&self, action: &OutboxAction, idempotency_key: &str) -> Result<Performed> {
+        let status = |endpoint: Endpoint<Status>| async move {
+            self.call(endpoint.idempotency_key(idempotency_key))
+                .await
+                .map(|s| Performed::Status(Box::new(s)))
         };
-        let response = self.request(reqwest::Method::POST, &path, None).await?;
-        let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
+        match action {
+            OutboxAction::Post {
+                text,
+                in_reply_to_id,
+                visibility,
+            } => {
+                let body = status_body(text, in_reply_to_id.as_deref(), *visibility);
+                status(Endpoint::post(&["statuses"]).body(body)).await
+            }
+            OutboxAction::Boost { status_id, on } => {
+                status(toggle(&["statuses", status_id], *on, "reblog", "unreblog")).await
+            }
+            OutboxAction::Favourite { status_id, on } => {
+                status(toggle(
+                    &["statuses", status_id],
+                    *on,
+                    "favourite",
+                    "unfavourite",
+                ))
+                .await
+            }
+            OutboxAction::Bookmark { status_id, on } => {
+                status(toggle(
+                    &["statuses", status_id],
+                    *on,
+                    "bookmark",
+                    "unbookmark",
+                ))
+                .await
+            }
+            OutboxAction::Follow { account_id, on, .. } => {
+                let endpoint = toggle(&["accounts", account_id], *on, "follow", "unfollow");
+                self.call(endpoint.idempotency_key(idempotency_key))
+                    .await
+                    .map(Performed::Relationship)
+            }
         }
-        Ok(response.json().await?)
+    }
+
+    /// The logged-in user's account (used to tell own messages apart in conversations).
+    pub async fn verify_credentials(&self) -> Result<Account> {
+        self.call(Endpoint::get(&["accounts", "verify_credentials"]))
+            .await
+    }
+
+    /// Relationships with the given accounts (follow state for mute rules). Empty input
+    /// makes no request.
+    pub async fn get_relationships(&self, ids: &[String]) -> Result<Vec<Relationship>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.call(Endpoint::get(&["accounts", "relationships"]).query_each("id[]", ids))
+            .await
+    }
+
+    /// r[follows.list]: one page of an account's followers (`followers` true) or follows, with
+    /// the `max_id` for the next page taken from the Link header.
+    pub async fn get_follows(
+        &self,
+        account_id: &str,
+        followers: bool,
+        max_id: Option<&str>,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        let kind = if followers { "followers" } else { "following" };
+        self.call_page(page(&["accounts", account_id, kind], 40, max_id))
+            .await
+    }
+
+    /// r[follows.bulk]: follow (`follow` true) or unfollow an account.
+    pub async fn follow_account(&self, account_id: &str, follow: bool) -> Result<Relationship> {
+        self.call(toggle(
+            &["accounts", account_id],
+            follow,
+            "follow",
+            "unfollow",
+        ))
+        .await
+    }
+
+    /// r[follows.bulk]: mute (`mute` true) or unmute an account.
+    pub async fn mute_account(&self, account_id: &str, mute: bool) -> Result<Relationship> {
+        self.call(toggle(&["accounts", account_id], mute, "mute", "unmute"))
+            .await
+    }
+
+    /// r[follow-requests.list]: one page of pending follow requests, with the next `max_id`.
+    pub async fn get_follow_requests(
+        &self,
+        max_id: Option<&str>,
+    ) -> Result<(Vec<Account>, Option<String>)> {
+        self.call_page(page(&["follow_requests"], 80, max_id)).await
+    }
+
+    /// r[follow-requests.decide]: authorize (`authorize` true) or reject a follow request.
+    pub async fn decide_follow_request(
+        &self,
+        account_id: &str,
+        authorize: bool,
+    ) -> Result<Relationship> {
+        let path = ["follow_requests", account_id];
+        self.call(toggle(&path, authorize, "authorize", "reject"))
+            .await
+    }
+
+    /// r[moderation.mutes]: one page of muted accounts (with `mute_expires_at`), and the next
+    /// `max_id`.
+    pub async fn get_mutes(&self, max_id: Option<&str>) -> Result<(Vec<Account>, Option<String>)> {
+        self.call_page(page(&["mutes"], 40, max_id)).await
+    }
+
+    /// r[moderation.blocks]: one page of blocked accounts, and the next `max_id`.
+    pub async fn get_blocks(&self, max_id: Option<&str>) -> Result<(Vec<Account>, Option<String>)> {
+        self.call_page(page(&["blocks"], 40, max_id)).await
+    }
+
+    /// r[moderation.undo]: block (`block` true) or unblock an account.
+    pub async fn block_account(&self, account_id: &str, block: bool) -> Result<Relationship> {
+        self.call(toggle(&["accounts", account_id], block, "block", "unblock"))
+            .await
+    }
+
+    /// r[moderation.domains]: one page of blocked domains, and the next `max_id`.
+    pub async fn get_domain_blocks(
+        &self,
+        max_id: Option<&str>,
+    ) -> Result<(Vec<String>, Option<String>)> {
+        self.call_page(page(&["domain_blocks"], 100, max_id)).await
+    }
+
+    /// r[moderation.domains] r[moderation.undo]: block (`block` true) or unblock a domain.
+    pub async fn block_domain(&self, domain: &str, block: bool) -> Result<()> {
+        let method = if block {
+            reqwest::Method::POST
+        } else {
+            reqwest::Method::DELETE
+        };
+        let endpoint = Endpoint::<Empty>::new(method, &["domain_blocks"])
+            .body(serde_json::json!({ "domain": domain }));
+        self.call(endpoint).await?;
+        Ok(())
+    }
+
+    /// r[report.rules]: the instance's rules, for reports in the `violation` category.
+    pub async fn get_instance_rules(&self) -> Result<Vec<Rule>> {
+        self.call(Endpoint::get(&["instance", "rules"])).await
+    }
+
+    /// r[report.statuses]: an account's recent posts (boosts excluded), newest first.
+    pub async fn get_account_statuses(&self, account_id: &str) -> Result<Vec<Status>> {
+        let endpoint = Endpoint::get(&["accounts", account_id, "statuses"])
+            .query("limit", &20)
+            .query("exclude_reblogs", &true);
+        self.call(endpoint).await
+    }
+
+    /// r[report.submit]: file a report with the instance moderators.
+    pub async fn create_report(&self, report: &NewReport) -> Result<()> {
+        let endpoint = Endpoint::<Empty>::post(&["reports"]).body(serde_json::json!(report));
+        self.call(endpoint).await?;
+        Ok(())
+    }
+
+    /// r[explore.trends]: trending tags, posts or links on the instance.
+    pub async fn get_trends(&self, kind: TrendsKind) -> Result<Trends> {
+        Ok(match kind {
+            TrendsKind::Tags => Trends::Tags(self.call(trends(kind)).await?),
+            TrendsKind::Statuses => Trends::Statuses(self.call(trends(kind)).await?),
+            TrendsKind::Links => Trends::Links(self.call(trends(kind)).await?),
+        })
+    }
+
+    /// r[markers.restore]: saved read positions for the home timeline and notifications.
+    pub async fn get_markers(&self) -> Result<Markers> {
+        let endpoint =
+            Endpoint::get(&["markers"]).query_each("timeline[]", ["home", "notifications"]);
+        self.call(endpoint).await
+    }
+
+    /// r[markers.save]: save the newest read status in the home timeline.
+    pub async fn save_home_marker(&self, last_read_id: &str) -> Result<()> {
+        let body = serde_json::json!({ "home": { "last_read_id": last_read_id } });
+        self.call(Endpoint::<Empty>::post(&["markers"]).body(body))
+            .await?;
+        Ok(())
+    }
+
+    /// r[follow-requests.notify]: notifications of the given types, newest first, optionally
+    /// only those newer than `since_id`.
+    pub async fn get_notifications(
+        &self,
+        types: &[&str],
+        since_id: Option<&str>,
+    ) -> Result<Vec<Notification>> {
+        let endpoint = Endpoint::get(&["notifications"])
+            .query("limit", &30)
+            .query_each("types[]", types)
+            .query_opt("since_id", since_id);
+        self.call(endpoint).await
+    }
+
+    /// r[dm.list]: direct-message conversations, most recent first.
+    pub async fn get_conversations(&self) -> Result<Vec<Conversation>> {
+        self.call(Endpoint::get(&["conversations"]).query("limit", &40))
+            .await
+    }
+
+    /// r[dm.mark-read]: mark a conversation as read.
+    pub async fn mark_conversation_read(&self, id: &str) -> Result<Conversation> {
+        self.call(conversation_read(id)).await
+    }
+
+    /// r[dm.thread]: ancestors and descendants of a status.
+    pub async fn get_status_context(&self, id: &str) -> Result<Context> {
+        self.call(Endpoint::get(&["statuses", id, "context"])).await
+    }
+
+    /// r[filter.manage.list]: the user's v2 filters with their keywords.
+    pub async fn get_filters(&self) -> Result<Vec<Filter>> {
+        self.call(Endpoint::get(&["filters"]).v2()).await
+    }
+
+    /// r[filter.manage.edit]: create a filter (`id` None) or update an existing one.
+    pub async fn save_filter(
+        &self,
+        id: Option<&str>,
+        title: &str,
+        context: &[String],
+        action: FilterAction,
+    ) -> Result<Filter> {
+        self.call(filter(id, title, context, action)).await
+    }
+
+    /// r[filter.manage.edit]: delete a filter and its keywords.
+    pub async fn delete_filter(&self, id: &str) -> Result<()> {
+        self.call(Endpoint::<Empty>::delete(&["filters", id]).v2())
+            .await?;
+        Ok(())
+    }
+
+    /// r[filter.manage.keywords]: add a keyword to a filter (`keyword_id` None) or update one.
+    pub async fn save_filter_keyword(
+        &self,
+        filter_id: &str,
+        keyword_id: Option<&str>,
+        keyword: &str,
+        whole_word: bool,
+    ) -> Result<FilterKeyword> {
+        self.call(filter_keyword(filter_id, keyword_id, keyword, whole_word))
+            .await
+    }
+
+    /// r[filter.manage.keywords]: remove a keyword from its filter.
+    pub async fn delete_filter_keyword(&self, keyword_id: &str) -> Result<()> {
+        self.call(Endpoint::<Empty>::delete(&["filters", "keywords", keyword_id]).v2())
+            .await?;
+        Ok(())
     }
 
     /// Get a single status by id (for thread context). r[toot.view-detail]
     pub async fn get_status(&self, id: &str) -> Result<Status> {
-        let path = format!("/statuses/{id}");
-        let response = self.request(reqwest::Method::GET, &path, None).await?;
-        let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
+        self.call(single_status(id)).await
+    }
+}
+
+/// Read-only access to an instance's public endpoints without logging in. Requests go
+/// without a token first; if the instance answers 401, an app token is fetched via
+/// `client_credentials` and kept for later requests. r[browse.instance.public-timeline]
+pub struct PublicClient {
+    instance_url: String,
+    client: Client,
+    app_token: Mutex<Option<String>>,
+    /// Quota from the most recent response. r[ratelimit.track]
+    rate_limit: Mutex<Option<RateLimit>>,
+    /// Most recent error response. r[errors.debug]
+    last_error: Mutex<Option<FailedRequest>>,
+}
+
+impl PublicClient {
+    pub fn new(instance_url: &str) -> Result<Self> {
+        instance_host_from_url(instance_url)?;
+        Ok(Self {
+            instance_url: instance_url.to_string(),
+            client: http_client()?,
+            app_token: Mutex::new(None),
+            rate_limit: Mutex::new(None),
+            last_error: Mutex::new(None),
+        })
+    }
+
+    /// The instance URL as given.
+    #[must_use]
+    pub fn instance_url(&self) -> &str {
+        &self.instance_url
+    }
+
+    /// The request quota as of the last response, if the server reports one.
+    #[must_use]
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().ok()?.clone()
+    }
+
+    /// The last error response, with its raw body.
+    #[must_use]
+    pub fn last_error(&self) -> Option<FailedRequest> {
+        self.last_error.lock().ok()?.clone()
+    }
+
+    /// The instance's public timeline. r[browse.instance.public-timeline]
+    pub async fn get_timeline_public(&self, max_id: Option<&str>) -> Result<Vec<Status>> {
+        self.get(timeline(&["timelines", "public"], max_id)).await
+    }
+
+    /// Trends of one kind. r[explore.anonymous]
+    pub async fn get_trends(&self, kind: TrendsKind) -> Result<Trends> {
+        Ok(match kind {
+            TrendsKind::Tags => Trends::Tags(self.get(trends(kind)).await?),
+            TrendsKind::Statuses => Trends::Statuses(self.get(trends(kind)).await?),
+            TrendsKind::Links => Trends::Links(self.get(trends(kind)).await?),
+        })
+    }
+
+    /// GET an endpoint through `send_recorded`, with the app-token fallback on 401.
+    async fn get<T: DeserializeOwned>(&self, endpoint: Endpoint<T>) -> Result<T> {
+        let url = endpoint.url(self.instance_url.trim_end_matches('/'))?;
+        let mut token = self.app_token.lock().ok().and_then(|t| t.clone());
+        let mut response = self.send(&url, token.as_deref()).await?;
+        if response.status() == StatusCode::UNAUTHORIZED && token.is_none() {
+            let (client_id, client_secret) =
+                register_app_if_needed(&self.instance_url, &self.client).await?;
+            let app_token = app_token_client_credentials(
+                &self.instance_url,
+                &client_id,
+                &client_secret,
+                &self.client,
+            )
+            .await?;
+            if let Ok(mut stored) = self.app_token.lock() {
+                *stored = Some(app_token.clone());
+            }
+            token = Some(app_token);
+            response = self.send(&url, token.as_deref()).await?;
+        }
+
+        if !response.status().is_success() {
+            return Err(api_error(response, &self.last_error).await);
         }
         Ok(response.json().await?)
     }
+
+    async fn send(&self, url: &reqwest::Url, token: Option<&str>) -> Result<reqwest::Response> {
+        let build = || {
+            let req = self.client.get(url.clone());
+            match token {
+                Some(token) => req.header("Authorization", format!("Bearer {token}")),
+                None => req,
+            }
+        };
+        send_recorded(build, &reqwest::Method::GET, &self.rate_limit).await
+    }
 }
 
-/// Fetch public timeline for an instance. Tries without auth first; on 401 (instance requires
-/// auth for public timeline) obtains an app token via `client_credentials` and retries. r[browse.instance.public-timeline]
-pub async fn get_public_timeline(instance_url: &str, max_id: Option<&str>) -> Result<Vec<Status>> {
-    let base = instance_url.trim_end_matches('/');
-    let path = max_id.map_or_else(
-        || "/timelines/public?limit=20".to_string(),
-        |id| format!("/timelines/public?limit=20&max_id={id}"),
-    );
-    let url = format!("{base}/api/v1{path}");
-    let client = Client::builder().user_agent("mastotui/0.1").build()?;
-    let response = client.get(&url).send().await?;
-    let status = response.status();
-
-    if status == reqwest::StatusCode::UNAUTHORIZED {
-        let (client_id, client_secret) = register_app_if_needed(instance_url, &client).await?;
-        let app_token =
-            app_token_client_credentials(instance_url, &client_id, &client_secret, &client).await?;
-        let response = client
-            .get(&url)
-            .header("Authorization", format!("Bearer {app_token}"))
-            .send()
-            .await?;
+/// Decode a failed response into an error and keep it, with its raw body, in `last_error`
+/// for the debug panel. r[errors.decode] r[errors.debug]
+async fn api_error(
+    response: reqwest::Response,
+    last_error: &Mutex<Option<FailedRequest>>,
+) -> MastotuiError {
+    let status = response.status().as_u16();
+    let url = response.url().to_string();
+    let body = response.text().await.unwrap_or_default();
+    let error = MastotuiError::from_response(status, &body);
+    if let Ok(mut last) = last_error.lock() {
+        *last = Some(FailedRequest {
+            status,
+            url,
+            at: Utc::now().to_rfc3339(),
+            body,
+        });
+    }
+    error
+}
+
+/// Send the request `build` makes and record the rate limit headers of every response in
+/// `rate_limit`. 429 and 502–504 become `RateLimited` and `ServerUnavailable`; GETs are
+/// retried a few times first, after `Retry-After` or a jittered backoff, as long as the
+/// waits add up to no more than `MAX_RETRY_WAIT`. Other responses, 401 included, are
+/// returned as they are. r[ratelimit.track] r[ratelimit.errors] r[ratelimit.retry]
+async fn send_recorded(
+    build: impl Fn() -> reqwest::RequestBuilder,
+    method: &reqwest::Method,
+    rate_limit: &Mutex<Option<RateLimit>>,
+) -> Result<reqwest::Response> {
+    let mut attempt = 0;
+    let mut waited = Duration::ZERO;
+    loop {
+        let response = build().send().await?;
+        let quota = RateLimit::from_headers(response.headers());
+        if quota.is_some() {
+            if let Ok(mut state) = rate_limit.lock() {
+                state.clone_from(&quota);
+            }
+        }
         let status = response.status();
-        if !status.is_success() {
-            let text = response.text().await.unwrap_or_default();
-            return Err(MastotuiError::Api {
-                status: status.as_u16(),
-                message: text,
-            });
+        if !is_retryable(status) {
+            return Ok(response);
+        }
+        if *method == reqwest::Method::GET && attempt < MAX_GET_RETRIES {
+            let wait = retry_delay(response.headers(), attempt, Utc::now());
+            if waited + wait <= MAX_RETRY_WAIT {
+                tokio::time::sleep(wait).await;
+                waited += wait;
+                attempt += 1;
+                continue;
+            }
         }
-        let statuses: Vec<Status> = response.json().await?;
-        return Ok(statuses);
+        return Err(if status == StatusCode::TOO_MANY_REQUESTS {
+            MastotuiError::RateLimited {
+                reset: quota.and_then(|r| r.reset),
+            }
+        } else {
+            MastotuiError::ServerUnavailable
+        });
     }
+}
 
-    if !status.is_success() {
-        let text = response.text().await.unwrap_or_default();
-        return Err(MastotuiError::Api {
-            status: status.as_u16(),
-            message: text,
-        });
+/// 20 statuses of a timeline, older than `max_id` when given. r[timeline.pagination]
+fn timeline(path: &[&str], max_id: Option<&str>) -> Endpoint<Vec<Status>> {
+    Endpoint::get(path)
+        .query("limit", &20)
+        .query_opt("max_id", max_id)
+}
+
+/// One page of a Link-paginated list, older than `max_id` when given.
+fn page<T>(path: &[&str], limit: u32, max_id: Option<&str>) -> Endpoint<Vec<T>> {
+    Endpoint::get(path)
+        .query("limit", &limit)
+        .query_opt("max_id", max_id)
+        .paged()
+}
+
+/// POST `<path>/<on>` or `<path>/<off>`, e.g. `statuses/1/reblog` and `statuses/1/unreblog`.
+fn toggle<T>(path: &[&str], on: bool, yes: &str, no: &str) -> Endpoint<T> {
+    let mut segments = path.to_vec();
+    segments.push(if on { yes } else { no });
+    Endpoint::post(&segments)
+}
+
+/// One page of trending tags, posts or links.
+fn trends<T>(kind: TrendsKind) -> Endpoint<T> {
+    Endpoint::get(&["trends", kind.segment()]).query("limit", &20)
+}
+
+/// PUT a scheduled status's new `scheduled_at`.
+fn reschedule(id: &str, scheduled_at: &str) -> Endpoint<ScheduledStatus> {
+    Endpoint::put(&["scheduled_statuses", id])
+        .body(serde_json::json!({ "scheduled_at": scheduled_at }))
+}
+
+/// DELETE a scheduled status.
+fn cancel_scheduled(id: &str) -> Endpoint<Empty> {
+    Endpoint::delete(&["scheduled_statuses", id])
+}
+
+/// POST that marks a conversation read.
+fn conversation_read(id: &str) -> Endpoint<Conversation> {
+    Endpoint::post(&["conversations", id, "read"])
+}
+
+/// POST a new v2 filter (`id` None) or PUT an existing one.
+fn filter(
+    id: Option<&str>,
+    title: &str,
+    context: &[String],
+    action: FilterAction,
+) -> Endpoint<Filter> {
+    let endpoint = id.map_or_else(
+        || Endpoint::post(&["filters"]),
+        |id| Endpoint::put(&["filters", id]),
+    );
+    endpoint.v2().body(serde_json::json!({
+        "title": title,
+        "context": context,
+        "filter_action": action
+    }))
+}
+
+/// POST a keyword to a filter (`keyword_id` None) or PUT an existing keyword.
+fn filter_keyword(
+    filter_id: &str,
+    keyword_id: Option<&str>,
+    keyword: &str,
+    whole_word: bool,
+) -> Endpoint<FilterKeyword> {
+    let endpoint = keyword_id.map_or_else(
+        || Endpoint::post(&["filters", filter_id, "keywords"]),
+        |id| Endpoint::put(&["filters", "keywords", id]),
+    );
+    endpoint
+        .v2()
+        .body(serde_json::json!({ "keyword": keyword, "whole_word": whole_word }))
+}
+
+/// Statuses worth retrying later: rate limited, or the server is down or overloaded.
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// How long to wait before retry number `attempt + 1`: the `Retry-After` header (seconds
+/// or an HTTP date) if present, else 250ms, 500ms, 1s plus up to 100ms of jitter.
+/// r[ratelimit.retry]
+fn retry_delay(headers: &HeaderMap, attempt: u32, now: DateTime<Utc>) -> Duration {
+    let retry_after = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim);
+    if let Some(value) = retry_after {
+        if let Ok(secs) = value.parse::<u64>() {
+            return Duration::from_secs(secs);
+        }
+        if let Ok(at) = DateTime::parse_from_rfc2822(value) {
+            return (at.with_timezone(&Utc) - now)
+                .to_std()
+                .unwrap_or(Duration::ZERO);
+        }
     }
-    let statuses: Vec<Status> = response.json().await?;
-    Ok(statuses)
+    let jitter = u64::from(now.timestamp_subsec_millis() % 100);
+    Duration::from_millis(250 << attempt) + Duration::from_millis(jitter)
+}
+
+/// POST an immediate post or reply.
+fn new_status(
+    status: &str,
+    in_reply_to_id: Option<&str>,
+    visibility: Option<Visibility>,
+) -> Endpoint<Status> {
+    Endpoint::post(&["statuses"]).body(status_body(status, in_reply_to_id, visibility))
+}
+
+/// GET one status by id.
+fn single_status(id: &str) -> Endpoint<Status> {
+    Endpoint::get(&["statuses", id])
+}
+
+/// Body of POST /api/v1/statuses for an immediate post or reply.
+fn status_body(
+    status: &str,
+    in_reply_to_id: Option<&str>,
+    visibility: Option<Visibility>,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "status": status,
+        "in_reply_to_id": in_reply_to_id
+    });
+    if let Some(v) = visibility {
+        body["visibility"] = serde_json::json!(v);
+    }
+    body
 }
 
 /// Build a client from stored config and keyring. r[auth.login.use-stored-token]
@@ -261,6 +980,7 @@ pub fn client_from_stored_credentials(instance_url: &str) -> Result<Option<Masto
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reqwest::Method;
 
     // r[verify auth.login.use-stored-token]
     #[test]
@@ -271,71 +991,284 @@ mod tests {
 
     // r[verify timeline.home.fetch]
     #[test]
-    fn timeline_home_path_without_max_id() {
-        let path: String = "/timelines/home?limit=20".into();
-        assert!(path.contains("timelines/home"));
-        assert!(path.contains("limit=20"));
+    fn home_timeline_fetches_twenty_statuses() {
+        let endpoint = timeline(&["timelines", "home"], None);
+        assert_eq!(endpoint.method, Method::GET);
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/timelines/home?limit=20"
+        );
+        assert!(endpoint.body.is_none());
     }
 
     // r[verify timeline.pagination]
     #[test]
-    fn timeline_home_path_with_max_id() {
-        let path = format!("/timelines/home?limit=20&max_id={}", "123");
-        assert!(path.contains("max_id=123"));
+    fn timeline_pages_older_than_max_id() {
+        let endpoint = timeline(&["timelines", "home"], Some("123"));
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/timelines/home?limit=20&max_id=123"
+        );
     }
 
     // r[verify toot.post.submit]
     #[test]
-    fn post_status_path() {
-        assert_eq!("/statuses", "/statuses");
+    fn new_status_posts_the_text() {
+        let endpoint = new_status("hi", None, None);
+        assert_eq!(endpoint.method, Method::POST);
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/statuses"
+        );
+        let body = endpoint.body.unwrap();
+        assert_eq!(body["status"], "hi");
+        assert!(body["in_reply_to_id"].is_null());
+        assert!(body.get("visibility").is_none(), "account default");
     }
 
-    // r[verify toot.reply]
+    // r[verify toot.reply] r[verify dm.reply]
     #[test]
-    fn reply_includes_in_reply_to_id() {
-        let body = serde_json::json!({ "status": "hi", "in_reply_to_id": "99" });
+    fn reply_carries_in_reply_to_id_and_direct_visibility() {
+        let endpoint = new_status("hi", Some("99"), Some(Visibility::Direct));
+        assert_eq!(endpoint.method, Method::POST);
         assert_eq!(
-            body.get("in_reply_to_id").and_then(|v| v.as_str()),
-            Some("99")
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/statuses"
         );
+        let body = endpoint.body.unwrap();
+        assert_eq!(body["in_reply_to_id"], "99");
+        assert_eq!(body["visibility"], "direct");
     }
 
-    // r[verify toot.boost.toggle]
+    // r[verify toot.boost.toggle] r[verify toot.favourite.toggle]
     #[test]
-    fn reblog_path_format() {
-        let id = "42";
-        assert_eq!(format!("/statuses/{id}/reblog"), "/statuses/42/reblog");
-        assert_eq!(format!("/statuses/{id}/unreblog"), "/statuses/42/unreblog");
+    fn toggles_post_to_the_on_or_off_path() {
+        for (on, yes, no, path) in [
+            (true, "reblog", "unreblog", "statuses/42/reblog"),
+            (false, "reblog", "unreblog", "statuses/42/unreblog"),
+            (true, "favourite", "unfavourite", "statuses/42/favourite"),
+            (false, "favourite", "unfavourite", "statuses/42/unfavourite"),
+        ] {
+            let endpoint: Endpoint<Status> = toggle(&["statuses", "42"], on, yes, no);
+            assert_eq!(endpoint.method, Method::POST);
+            assert_eq!(
+                endpoint.url("https://example.com").unwrap().as_str(),
+                format!("https://example.com/api/v1/{path}")
+            );
+            assert!(endpoint.body.is_none());
+        }
     }
 
-    // r[verify toot.favourite.toggle]
+    // r[verify toot.view-detail]
     #[test]
-    fn favourite_path_format() {
-        let id = "42";
+    fn single_status_gets_it_by_encoded_id() {
+        let endpoint = single_status("99");
+        assert_eq!(endpoint.method, Method::GET);
         assert_eq!(
-            format!("/statuses/{id}/favourite"),
-            "/statuses/42/favourite"
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/statuses/99"
         );
         assert_eq!(
-            format!("/statuses/{id}/unfavourite"),
-            "/statuses/42/unfavourite"
+            single_status("a/b")
+                .url("https://example.com")
+                .unwrap()
+                .path(),
+            "/api/v1/statuses/a%2Fb"
         );
     }
 
-    // r[verify toot.view-detail]
+    // r[verify toot.schedule.reschedule] r[verify toot.schedule.cancel]
+    #[test]
+    fn scheduled_status_endpoints() {
+        let endpoint = reschedule("7", "2025-06-02T09:00:00Z");
+        assert_eq!(endpoint.method, Method::PUT);
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/scheduled_statuses/7"
+        );
+        assert_eq!(
+            endpoint.body.unwrap()["scheduled_at"],
+            "2025-06-02T09:00:00Z"
+        );
+        let cancel = cancel_scheduled("7");
+        assert_eq!(cancel.method, Method::DELETE);
+        assert!(cancel.body.is_none());
+        assert_eq!(
+            cancel.url("https://example.com").unwrap().path(),
+            "/api/v1/scheduled_statuses/7"
+        );
+    }
+
+    // r[verify follows.list]
+    #[test]
+    fn next_page_comes_from_link_header() {
+        let link = "<https://example.com/api/v1/accounts/1/followers?limit=40&max_id=7788>; \
+                    rel=\"next\", <https://example.com/api/v1/accounts/1/followers?since_id=9900>; \
+                    rel=\"prev\"";
+        assert_eq!(next_max_id(Some(link)).as_deref(), Some("7788"));
+        let only_prev = "<https://example.com/api/v1/x?since_id=1>; rel=\"prev\"";
+        assert_eq!(next_max_id(Some(only_prev)), None);
+        assert_eq!(next_max_id(None), None);
+    }
+
+    // r[verify follow-requests.notify]
+    #[test]
+    fn notification_type_field_deserializes() {
+        let n: Notification = serde_json::from_value(serde_json::json!({
+            "id": "5",
+            "type": "follow_request",
+            "account": { "id": "2", "display_name": "Ann", "username": "ann", "acct": "ann" }
+        }))
+        .unwrap();
+        assert_eq!(n.kind, "follow_request");
+        assert_eq!(n.account.map(|a| a.acct), Some("ann".to_string()));
+    }
+
+    // r[verify list.manage.edit]
+    #[test]
+    fn list_json_defaults_and_policy_serialization() {
+        let list: List =
+            serde_json::from_value(serde_json::json!({ "id": "1", "title": "Friends" })).unwrap();
+        assert_eq!(list.replies_policy, RepliesPolicy::List);
+        assert!(!list.exclusive);
+        assert_eq!(
+            serde_json::to_value(RepliesPolicy::Followed).unwrap(),
+            "followed"
+        );
+    }
+
+    // r[verify mute.rules]
+    #[test]
+    fn relationships_query_repeats_id_param() {
+        let ids = vec!["1".to_string(), "22".to_string()];
+        let endpoint = Endpoint::<Vec<Relationship>>::get(&["accounts", "relationships"])
+            .query_each("id[]", &ids);
+        let url = endpoint.url("https://example.com").unwrap();
+        let pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            pairs,
+            vec![("id[]".into(), "1".into()), ("id[]".into(), "22".into())]
+        );
+    }
+
+    // r[verify filter.manage.list]
+    #[test]
+    fn filters_use_api_v2() {
+        let filters = Endpoint::<Vec<Filter>>::get(&["filters"]).v2();
+        assert_eq!(
+            filters.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v2/filters"
+        );
+        assert_eq!(
+            trends::<Vec<Status>>(TrendsKind::Links)
+                .url("https://example.com")
+                .unwrap()
+                .as_str(),
+            "https://example.com/api/v1/trends/links?limit=20"
+        );
+    }
+
+    // r[verify filter.manage.edit] r[verify filter.manage.keywords]
+    #[test]
+    fn filter_body_serializes_action_and_context() {
+        let context = vec!["home".to_string(), "public".to_string()];
+        let created = filter(None, "Spoilers", &context, FilterAction::Hide);
+        assert_eq!(created.method, Method::POST);
+        let body = created.body.unwrap();
+        assert_eq!(body["filter_action"], "hide");
+        assert_eq!(body["context"], serde_json::json!(["home", "public"]));
+        let updated = filter(Some("3"), "Spoilers", &context, FilterAction::Warn);
+        assert_eq!(updated.method, Method::PUT);
+        assert_eq!(
+            updated.url("https://example.com").unwrap().path(),
+            "/api/v2/filters/3"
+        );
+
+        let added = filter_keyword("3", None, "finale", true);
+        assert_eq!(added.method, Method::POST);
+        assert_eq!(
+            added.url("https://example.com").unwrap().path(),
+            "/api/v2/filters/3/keywords"
+        );
+        assert_eq!(added.body.unwrap()["whole_word"], true);
+        let edited = filter_keyword("3", Some("8"), "finale", false);
+        assert_eq!(edited.method, Method::PUT);
+        assert_eq!(
+            edited.url("https://example.com").unwrap().path(),
+            "/api/v2/filters/keywords/8"
+        );
+    }
+
+    // r[verify dm.mark-read]
     #[test]
-    fn get_status_path_format() {
-        let id = "99";
-        assert_eq!(format!("/statuses/{id}"), "/statuses/99");
+    fn conversation_read_posts_to_the_conversation() {
+        let endpoint = conversation_read("5");
+        assert_eq!(endpoint.method, Method::POST);
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/conversations/5/read"
+        );
     }
 
     // r[verify browse.instance.public-timeline]
     #[test]
-    fn public_timeline_path_format() {
-        let path = "/timelines/public?limit=20".to_string();
-        assert!(path.contains("timelines/public"));
-        assert!(path.contains("limit=20"));
-        let with_max = format!("/timelines/public?limit=20&max_id={}", "xyz");
-        assert!(with_max.contains("max_id=xyz"));
+    fn public_timeline_pages_like_home() {
+        let endpoint = timeline(&["timelines", "public"], Some("xyz"));
+        assert_eq!(endpoint.method, Method::GET);
+        assert_eq!(
+            endpoint.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/timelines/public?limit=20&max_id=xyz"
+        );
+    }
+
+    // r[verify ratelimit.track] r[verify ratelimit.bulk]
+    #[test]
+    fn rate_limit_headers_are_parsed_and_low_quota_pauses() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RateLimit::from_headers(&headers), None);
+        headers.insert("X-RateLimit-Limit", "300".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "29".parse().unwrap());
+        headers.insert(
+            "X-RateLimit-Reset",
+            "2025-06-01T12:05:00.000Z".parse().unwrap(),
+        );
+        let limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!((limit.limit, limit.remaining), (300, 29));
+        assert!(limit.is_low());
+        let before: DateTime<Utc> = "2025-06-01T12:00:00Z".parse().unwrap();
+        let after: DateTime<Utc> = "2025-06-01T12:06:00Z".parse().unwrap();
+        assert_eq!(limit.pause_until(before), Some("2025-06-01T12:05:00.000Z"));
+        assert_eq!(limit.pause_until(after), None);
+        let plenty = RateLimit {
+            remaining: 30,
+            ..limit
+        };
+        assert_eq!(plenty.pause_until(before), None);
+    }
+
+    // r[verify ratelimit.retry] r[verify ratelimit.errors]
+    #[test]
+    fn retry_delay_honours_retry_after_then_backs_off() {
+        let now: DateTime<Utc> = "2015-10-21T07:27:50Z".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        let backoff = retry_delay(&headers, 2, now);
+        assert!(backoff >= Duration::from_secs(1) && backoff < Duration::from_millis(1100));
+        let late: DateTime<Utc> = "2015-10-21T07:27:50.999Z".parse().unwrap();
+        let all: Duration = (0..MAX_GET_RETRIES)
+            .map(|attempt| retry_delay(&headers, attempt, late))
+            .sum();
+        assert!(all <= MAX_RETRY_WAIT, "every backoff retry fits in the cap");
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_delay(&headers, 0, now), Duration::from_secs(7));
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_delay(&headers, 0, now), Duration::from_secs(10));
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable(StatusCode::UNPROCESSABLE_ENTITY));
+        let limited = MastotuiError::RateLimited { reset: None };
+        assert_eq!(limited.to_string(), "Rate limited by the server");
     }
 }
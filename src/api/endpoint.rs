@@ -0,0 +1,222 @@
+//! Typed description of one Mastodon API call: method, path, query, body, response type and
+//! how further pages are found. Every `MastodonClient` call is built from one, so path
+//! segments and query values are always percent-encoded. r[api.endpoint] r[api.encode]
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use reqwest::Method;
+use serde::de::IgnoredAny;
+use url::Url;
+
+use crate::error::{MastotuiError, Result};
+
+/// Response type for calls whose body is not used (DELETE and friends return `{}`).
+pub type Empty = IgnoredAny;
+
+/// API version the path lives under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    V1,
+    /// For endpoints only available under /api/v2 (e.g. filters).
+    V2,
+}
+
+/// How the next page of a list is found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paging {
+    /// One response holds everything asked for.
+    Single,
+    /// The `max_id` of the next page is in the `Link: <…>; rel="next"` header.
+    Link,
+}
+
+/// A request to /api/v1 or /api/v2 whose JSON response decodes to `T`. r[api.endpoint]
+pub struct Endpoint<T> {
+    pub method: Method,
+    pub version: Version,
+    /// Path segments after /api/vN, each encoded on its own.
+    pub segments: Vec<String>,
+    /// Query pairs in order; a key may repeat (e.g. `types[]`).
+    pub query: Vec<(String, String)>,
+    pub body: Option<serde_json::Value>,
+    pub paging: Paging,
+    /// Sent as the `Idempotency-Key` header. r[outbox.idempotent]
+    pub idempotency_key: Option<String>,
+    response: PhantomData<fn() -> T>,
+}
+
+impl<T> fmt::Debug for Endpoint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Endpoint")
+            .field("method", &self.method)
+            .field("version", &self.version)
+            .field("segments", &self.segments)
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Endpoint<T> {
+    /// `path` is the list of segments after /api/v1, e.g. `["statuses", id, "context"]`.
+    #[must_use]
+    pub fn new(method: Method, path: &[&str]) -> Self {
+        Self {
+            method,
+            version: Version::V1,
+            segments: path.iter().map(|s| (*s).to_string()).collect(),
+            query: Vec::new(),
+            body: None,
+            paging: Paging::Single,
+            idempotency_key: None,
+            response: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn get(path: &[&str]) -> Self {
+        Self::new(Method::GET, path)
+    }
+
+    #[must_use]
+    pub fn post(path: &[&str]) -> Self {
+        Self::new(Method::POST, path)
+    }
+
+    #[must_use]
+    pub fn put(path: &[&str]) -> Self {
+        Self::new(Method::PUT, path)
+    }
+
+    #[must_use]
+    pub fn delete(path: &[&str]) -> Self {
+        Self::new(Method::DELETE, path)
+    }
+
+    /// Live under /api/v2 instead of /api/v1.
+    #[must_use]
+    pub const fn v2(mut self) -> Self {
+        self.version = Version::V2;
+        self
+    }
+
+    /// The next page's `max_id` comes from the Link header.
+    #[must_use]
+    pub const fn paged(mut self) -> Self {
+        self.paging = Paging::Link;
+        self
+    }
+
+    #[must_use]
+    pub fn query(mut self, key: &str, value: &(impl ToString + ?Sized)) -> Self {
+        self.query.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Add the pair only when there is a value (e.g. `max_id` on the first page).
+    #[must_use]
+    pub fn query_opt(self, key: &str, value: Option<&str>) -> Self {
+        match value {
+            Some(v) => self.query(key, v),
+            None => self,
+        }
+    }
+
+    /// Repeat `key` once per value, as in `id[]=1&id[]=2`.
+    #[must_use]
+    pub fn query_each<V: ToString>(self, key: &str, values: impl IntoIterator<Item = V>) -> Self {
+        values.into_iter().fold(self, |e, v| e.query(key, &v))
+    }
+
+    #[must_use]
+    pub fn body(mut self, body: serde_json::Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    #[must_use]
+    pub fn idempotency_key(mut self, key: &str) -> Self {
+        self.idempotency_key = Some(key.to_string());
+        self
+    }
+
+    /// Full URL on an instance. r[api.encode]
+    pub fn url(&self, base_url: &str) -> Result<Url> {
+        let mut url = Url::parse(base_url).map_err(|e| MastotuiError::Config(e.to_string()))?;
+        {
+            let mut path = url
+                .path_segments_mut()
+                .map_err(|()| MastotuiError::Config("Instance URL cannot have a path".into()))?;
+            let version = match self.version {
+                Version::V1 => "v1",
+                Version::V2 => "v2",
+            };
+            path.pop_if_empty()
+                .extend(["api", version])
+                .extend(&self.segments);
+        }
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+        Ok(url)
+    }
+}
+
+/// `max_id` of the `rel="next"` URL in a Link header, if there is a next page.
+pub fn next_max_id(link: Option<&str>) -> Option<String> {
+    link?
+        .split(',')
+        .find(|part| part.contains("rel=\"next\""))
+        .and_then(|part| {
+            let url = part.split(['<', '>']).nth(1)?;
+            Url::parse(url)
+                .ok()?
+                .query_pairs()
+                .find(|(k, _)| k == "max_id")
+                .map(|(_, v)| v.into_owned())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Status;
+
+    // r[verify api.encode]
+    #[test]
+    fn path_segments_and_query_values_are_encoded() {
+        let e = Endpoint::<Vec<Status>>::get(&["timelines", "tag", "c#/rust?"])
+            .query("limit", &20)
+            .query_opt("max_id", None)
+            .query("any[]", "a&b=c d");
+        assert_eq!(
+            e.url("https://example.com").unwrap().as_str(),
+            "https://example.com/api/v1/timelines/tag/c%23%2Frust%3F?limit=20&any%5B%5D=a%26b%3Dc+d"
+        );
+        let list = Endpoint::<Empty>::delete(&["lists", "../accounts"]).v2();
+        assert_eq!(
+            list.url("https://example.com/sub/").unwrap().path(),
+            "/sub/api/v2/lists/..%2Faccounts"
+        );
+    }
+
+    // r[verify api.endpoint]
+    #[test]
+    fn endpoint_carries_method_body_and_paging() {
+        let e = Endpoint::<Vec<Status>>::post(&["statuses"])
+            .body(serde_json::json!({ "status": "hi" }))
+            .idempotency_key("k1");
+        assert_eq!(e.method, Method::POST);
+        assert_eq!(e.paging, Paging::Single);
+        assert_eq!(e.body.as_ref().unwrap()["status"], "hi");
+        assert_eq!(e.idempotency_key.as_deref(), Some("k1"));
+        let page = Endpoint::<Vec<Status>>::get(&["mutes"])
+            .query_each("id[]", ["1", "22"])
+            .paged();
+        assert_eq!(page.paging, Paging::Link);
+        assert_eq!(
+            page.url("https://example.com").unwrap().query(),
+            Some("id%5B%5D=1&id%5B%5D=22")
+        );
+    }
+}
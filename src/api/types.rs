@@ -1,6 +1,6 @@
 //! Mastodon API response types (subset needed for MVP).
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Application {
@@ -8,7 +8,7 @@ pub struct Application {
     pub client_secret: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub id: String,
     pub display_name: String,
@@ -16,9 +16,27 @@ pub struct Account {
     #[serde(default)]
     pub acct: String,
     pub url: Option<String>,
+    /// Follows must be approved; only locked accounts get follow requests.
+    #[serde(default)]
+    pub locked: bool,
+    /// When a mute ends; only set on accounts from GET /api/v1/mutes (None = indefinite).
+    #[serde(default)]
+    pub mute_expires_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+impl Account {
+    /// Handle for display and mentions: `acct` when present, else `username`.
+    #[must_use]
+    pub fn handle(&self) -> &str {
+        if self.acct.is_empty() {
+            &self.username
+        } else {
+            &self.acct
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Visibility {
     #[default]
@@ -29,12 +47,41 @@ pub enum Visibility {
 }
 
 /// Alt text is in `description`; we only use it for [media: …] display.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaAttachment {
     pub description: Option<String>,
 }
 
+/// A notification; only the fields mastotui uses.
 #[derive(Debug, Clone, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    /// e.g. `mention`, `follow`, `follow_request`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub account: Option<Account>,
+}
+
+/// Relationship between the logged-in account and another account.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(clippy::struct_excessive_bools)] // mirrors the API entity's flags
+pub struct Relationship {
+    pub id: String,
+    #[serde(default)]
+    pub following: bool,
+    #[serde(default)]
+    pub followed_by: bool,
+    /// A follow request from the user is awaiting approval.
+    #[serde(default)]
+    pub requested: bool,
+    #[serde(default)]
+    pub muting: bool,
+    /// The mute also hides the account's notifications.
+    #[serde(default)]
+    pub muting_notifications: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Status {
     pub id: String,
     pub uri: String,
@@ -45,16 +92,252 @@ pub struct Status {
     pub reblog: Option<Box<Self>>,
     pub favourited: Option<bool>,
     pub reblogged: Option<bool>,
+    pub bookmarked: Option<bool>,
+    #[serde(default)]
+    pub replies_count: u64,
+    #[serde(default)]
+    pub reblogs_count: u64,
+    #[serde(default)]
+    pub favourites_count: u64,
     pub in_reply_to_id: Option<String>,
     pub in_reply_to_account_id: Option<String>,
     #[serde(default)]
     pub media_attachments: Vec<MediaAttachment>,
+    /// Server-side filters matching this status in the requested context. r[filter.server.hide]
+    #[serde(default)]
+    pub filtered: Vec<FilterResult>,
+}
+
+impl Status {
+    /// Strongest server filter action for this status (or the boosted status), with the
+    /// matching filter's title. `Hide` wins over `Warn`; `Blur` only affects media.
+    #[must_use]
+    pub fn server_filter(&self) -> Option<(FilterAction, &str)> {
+        let results = self
+            .filtered
+            .iter()
+            .chain(self.reblog.iter().flat_map(|r| r.filtered.iter()));
+        let mut warn = None;
+        for r in results {
+            match r.filter.filter_action {
+                FilterAction::Hide => return Some((FilterAction::Hide, &r.filter.title)),
+                FilterAction::Warn if warn.is_none() => {
+                    warn = Some((FilterAction::Warn, r.filter.title.as_str()));
+                }
+                _ => {}
+            }
+        }
+        warn
+    }
+}
+
+/// What the server does with statuses matching a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterAction {
+    /// Show behind a warning with the filter title.
+    #[default]
+    Warn,
+    /// Do not show at all.
+    Hide,
+    /// Show, but blur media (Mastodon 4.4+).
+    Blur,
+}
+
+/// A v2 filter (GET /api/v2/filters). r[filter.manage.list]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Filter {
+    pub id: String,
+    pub title: String,
+    /// Where the filter applies: home, notifications, public, thread, account.
+    pub context: Vec<String>,
+    pub expires_at: Option<String>,
+    pub filter_action: FilterAction,
+    #[serde(default)]
+    pub keywords: Vec<FilterKeyword>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterKeyword {
+    pub id: String,
+    pub keyword: String,
+    #[serde(default)]
+    pub whole_word: bool,
+}
+
+/// Why a status matched a filter (the `filtered` array on `Status`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterResult {
+    pub filter: Filter,
+    pub keyword_matches: Option<Vec<String>>,
+}
+
+/// Ancestors and descendants of a status (GET /statuses/:id/context).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Context {
+    pub ancestors: Vec<Status>,
+    pub descendants: Vec<Status>,
+}
+
+/// A direct-message conversation. r[dm.list]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Conversation {
+    pub id: String,
+    #[serde(default)]
+    pub unread: bool,
+    /// Participants other than the current user.
+    pub accounts: Vec<Account>,
+    pub last_status: Option<Status>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct List {
     pub id: String,
     pub title: String,
+    /// Which replies appear in the list timeline.
+    #[serde(default)]
+    pub replies_policy: RepliesPolicy,
+    /// Members' posts are removed from the home timeline (Mastodon 4.2+).
+    #[serde(default)]
+    pub exclusive: bool,
+}
+
+/// Replies shown in a list timeline: to any followed user, to list members only, or none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RepliesPolicy {
+    Followed,
+    #[default]
+    List,
+    None,
+}
+
+/// A server rule from GET /api/v1/instance/rules. r[report.rules]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    pub text: String,
+}
+
+/// Why an account is reported. `Legal` needs Mastodon 4.2+.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportCategory {
+    Spam,
+    Legal,
+    Violation,
+    #[default]
+    Other,
+}
+
+impl ReportCategory {
+    /// Every category, in the order offered in the report flow.
+    pub const ALL: [Self; 4] = [Self::Spam, Self::Legal, Self::Violation, Self::Other];
+}
+
+/// Body of POST /api/v1/reports. r[report.submit]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewReport {
+    pub account_id: String,
+    pub status_ids: Vec<String>,
+    pub category: ReportCategory,
+    /// Broken rules; only sent for `Violation`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rule_ids: Vec<String>,
+    pub comment: String,
+    /// Also send the report to the account's own server.
+    pub forward: bool,
+}
+
+/// Usage of a trending tag or link on one day; counts arrive as strings. r[explore.trends]
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrendHistory {
+    /// UNIX timestamp of the day.
+    pub day: String,
+    pub uses: String,
+    pub accounts: String,
+}
+
+/// A trending hashtag from GET /api/v1/trends/tags.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tag {
+    pub name: String,
+    pub url: String,
+    /// Newest day first.
+    #[serde(default)]
+    pub history: Vec<TrendHistory>,
+}
+
+/// A trending link from GET /api/v1/trends/links.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrendLink {
+    pub url: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub provider_name: String,
+    /// Newest day first.
+    #[serde(default)]
+    pub history: Vec<TrendHistory>,
+}
+
+/// Which of the trends endpoints to read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrendsKind {
+    #[default]
+    Tags,
+    Statuses,
+    Links,
+}
+
+impl TrendsKind {
+    /// Path segment under /api/v1/trends.
+    #[must_use]
+    pub const fn segment(self) -> &'static str {
+        match self {
+            Self::Tags => "tags",
+            Self::Statuses => "statuses",
+            Self::Links => "links",
+        }
+    }
+}
+
+/// One page of trends of a single kind.
+#[derive(Debug, Clone)]
+pub enum Trends {
+    Tags(Vec<Tag>),
+    Statuses(Vec<Status>),
+    Links(Vec<TrendLink>),
+}
+
+impl Trends {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Tags(t) => t.len(),
+            Self::Statuses(s) => s.len(),
+            Self::Links(l) => l.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Saved read position in one timeline. r[markers.restore]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Marker {
+    pub last_read_id: String,
+}
+
+/// Response of GET /api/v1/markers; a timeline is absent until some client saves it.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Markers {
+    pub home: Option<Marker>,
+    pub notifications: Option<Marker>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -63,3 +346,20 @@ pub struct Card {
     pub title: Option<String>,
     pub description: Option<String>,
 }
+
+/// A status queued on the server for later publication. r[toot.schedule.list]
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledStatus {
+    pub id: String,
+    /// ISO 8601 UTC timestamp at which the server will publish the status.
+    pub scheduled_at: String,
+    pub params: ScheduledStatusParams,
+}
+
+/// Parameters the scheduled status will be posted with (subset).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScheduledStatusParams {
+    pub text: String,
+    pub in_reply_to_id: Option<String>,
+    pub visibility: Option<Visibility>,
+}
@@ -1,25 +1,69 @@
 //! App state and main event loop.
 //! r[config.first-run] r[timeline.home.fetch] r[timeline.pagination]
 //! r[toot.view-detail] r[toot.post.submit] r[toot.post.validation] r[toot.reply] r[toot.boost.toggle] r[toot.favourite.toggle]
+//! r[toot.schedule.compose] r[toot.schedule.list] r[toot.schedule.reschedule] r[toot.schedule.cancel]
+//! r[toot.thread.preview] r[toot.thread.post] r[toot.thread.resume]
+//! r[dm.list] r[dm.unread] r[dm.mark-read] r[dm.thread] r[dm.reply]
+//! r[filter.server.hide] r[filter.server.warn] r[filter.manage.list] r[filter.manage.edit]
+//! r[filter.manage.keywords] r[mute.rules] r[mute.show-hidden]
+//! r[list.manage.list] r[list.manage.edit] r[list.manage.members] r[list.add-author]
+//! r[follows.list] r[follows.badges] r[follows.search] r[follows.bulk]
+//! r[follow-requests.list] r[follow-requests.decide] r[follow-requests.count]
+//! r[follow-requests.notify] r[moderation.mutes] r[moderation.blocks] r[moderation.domains]
+//! r[moderation.undo] r[moderation.domain-add] r[report.open] r[report.category] r[report.rules]
+//! r[report.statuses] r[report.comment] r[report.submit] r[explore.trends] r[explore.anonymous]
+//! r[markers.restore] r[markers.save] r[markers.divider] r[cache.store] r[cache.stale]
+//! r[cache.offline] r[outbox.queue] r[outbox.retry] r[outbox.reconcile] r[outbox.view]
+//! r[toot.optimistic] r[ratelimit.status] r[ratelimit.bulk] r[errors.debug]
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{HashMap, HashSet};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::Frame;
 use tokio::runtime::Runtime;
 
 use crate::api::{
     authorization_url, client_from_stored_credentials, exchange_code_for_token,
-    get_public_timeline, register_app_if_needed, MastodonClient,
+    register_app_if_needed, Account, FilterAction, MastodonClient, NewReport, PublicClient,
+    Relationship, RepliesPolicy, ReportCategory, Rule, Trends, TrendsKind, Visibility,
 };
+use crate::cache::TimelineCache;
 use crate::config::{load_config, save_config, AppConfig};
 use crate::credential::{delete_access_token, get_client_secret, instance_host_from_url};
 use crate::error::{MastotuiError, Result};
+use crate::keymap::{Action, Key, KeyView, Keymap, Lookup};
+use crate::mute::{MuteContext, MuteRules};
+use crate::outbox::{idempotency_key, Outbox, OutboxAction, Performed};
+use crate::palette::{Command, Palette};
+use crate::schedule::{format_local, parse_schedule, to_api_timestamp};
+use crate::thread::{has_separator, split_thread};
+use crate::tui::mouse::{Hit, HitMap};
+use crate::tui::theme::{set_theme, Theme};
 use crate::tui::{
-    draw_compose, draw_instance_info, draw_instance_picker, draw_login, draw_timeline,
-    draw_timeline_picker, draw_toot_detail,
+    draw_compose, draw_conversation_thread, draw_conversations, draw_debug, draw_explore,
+    draw_filter_keywords, draw_filters, draw_follow_requests, draw_follows, draw_help,
+    draw_instance_info, draw_instance_picker, draw_list_members, draw_list_picker, draw_lists,
+    draw_login, draw_moderation, draw_outbox, draw_palette, draw_report, draw_scheduled_posts,
+    draw_split, draw_thread_preview, draw_timeline, draw_timeline_columns, draw_timeline_picker,
+    draw_toot_detail, TimelinePane,
 };
 
 const CHAR_LIMIT: usize = 500;
 
+/// Least time between saves of the home marker while scrolling. r[markers.save]
+const MARKER_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Least time between checks for new follow requests. r[follow-requests.count]
+const FOLLOW_REQUEST_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Pages of Home loaded at most while looking for the last-read status. r[markers.restore]
+const MARKER_MAX_PAGES: usize = 10;
+
+/// Most time between two clicks on a timeline row that open it. r[mouse.select]
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Which timeline is currently shown (or selected in the picker).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TimelineSelection {
@@ -39,6 +83,527 @@ impl TimelineSelection {
             Self::List { title, .. } => title.clone(),
         }
     }
+
+    /// Name used by mute rules' `timelines` (`home`, `local`, `public`, `list:<title>`).
+    #[must_use]
+    pub fn rule_key(&self) -> String {
+        match self {
+            Self::Home => "home".to_string(),
+            Self::Local => "local".to_string(),
+            Self::Public => "public".to_string(),
+            Self::List { title, .. } => format!("list:{title}"),
+        }
+    }
+
+    /// Timeline for a `columns` entry in config: `home`, `local`, `public` or `list:<title>`,
+    /// the list found by title in `lists`. r[layout.columns]
+    #[must_use]
+    pub fn from_column_name(name: &str, lists: &[crate::api::List]) -> Option<Self> {
+        match name {
+            "home" => Some(Self::Home),
+            "local" => Some(Self::Local),
+            "public" => Some(Self::Public),
+            _ => {
+                let title = name.strip_prefix("list:")?;
+                lists.iter().find(|l| l.title == title).map(|l| Self::List {
+                    id: l.id.clone(),
+                    title: l.title.clone(),
+                })
+            }
+        }
+    }
+}
+
+/// How the timeline shares the screen (`|` cycles). r[layout.split] r[layout.columns]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutMode {
+    /// One view at a time.
+    #[default]
+    Single,
+    /// Timeline on the left, the selected or opened toot on the right.
+    Split,
+    /// Several timelines side by side.
+    Columns,
+}
+
+impl LayoutMode {
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Single => Self::Split,
+            Self::Split => Self::Columns,
+            Self::Columns => Self::Single,
+        }
+    }
+
+    /// Parse `layout` from config.toml (`single`, `split` or `columns`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "single" => Some(Self::Single),
+            "split" => Some(Self::Split),
+            "columns" => Some(Self::Columns),
+            _ => None,
+        }
+    }
+}
+
+/// Shown statuses of a timeline, each with why it is hidden (when hidden ones are shown).
+pub type TimelineRows<'a> = Vec<(&'a crate::api::Status, Option<&'a str>)>;
+
+/// A timeline column with what it has loaded. The focused column's timeline lives in `App`'s
+/// timeline fields and its slot here is only a placeholder; focusing swaps them.
+/// r[layout.columns]
+// Mirrors App's timeline fields one to one, flags included.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub struct TimelineColumn {
+    pub timeline: TimelineSelection,
+    pub statuses: Vec<crate::api::Status>,
+    /// Indices into `statuses` that are shown, as `App::timeline_visible`.
+    pub visible: Vec<usize>,
+    /// Why each status is hidden, as `App::timeline_hidden`.
+    pub hidden: Vec<Option<String>>,
+    pub selected: usize,
+    pub scroll: usize,
+    /// Load error, as `App::timeline_message`.
+    pub message: String,
+    /// Loaded at least once, so an empty timeline is not fetched again on every pass.
+    pub loaded: bool,
+    /// As `App::loading`.
+    pub loading: bool,
+    /// As `App::timeline_cached_at`.
+    pub cached_at: Option<String>,
+    /// As `App::offline`.
+    pub offline: bool,
+    /// As `App::refresh_pending`.
+    pub refresh_pending: bool,
+}
+
+impl TimelineColumn {
+    #[must_use]
+    pub const fn new(timeline: TimelineSelection) -> Self {
+        Self {
+            timeline,
+            statuses: Vec::new(),
+            visible: Vec::new(),
+            hidden: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            message: String::new(),
+            loaded: false,
+            loading: false,
+            cached_at: None,
+            offline: false,
+            refresh_pending: false,
+        }
+    }
+
+    /// Shown statuses with why each is hidden, for drawing.
+    #[must_use]
+    pub fn rows(&self) -> TimelineRows<'_> {
+        self.visible
+            .iter()
+            .filter_map(|&i| {
+                let reason = self.hidden.get(i).and_then(Option::as_deref);
+                self.statuses.get(i).map(|s| (s, reason))
+            })
+            .collect()
+    }
+}
+
+/// Contexts a v2 filter can apply to, in the order shown in the filter form.
+pub const FILTER_CONTEXTS: [&str; 5] = ["home", "notifications", "public", "thread", "account"];
+
+/// Filter being created or edited in the filters view. r[filter.manage.edit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterForm {
+    /// None when creating a new filter.
+    pub id: Option<String>,
+    pub title: String,
+    /// One flag per entry in `FILTER_CONTEXTS`.
+    pub context: [bool; 5],
+    pub action: FilterAction,
+    /// Focused row: 0 title, 1..=5 contexts, 6 action.
+    pub field: usize,
+}
+
+impl FilterForm {
+    /// Row index of the action field (after title and the contexts).
+    pub const ACTION_FIELD: usize = FILTER_CONTEXTS.len() + 1;
+
+    const fn new() -> Self {
+        Self {
+            id: None,
+            title: String::new(),
+            context: [true, false, true, true, false],
+            action: FilterAction::Warn,
+            field: 0,
+        }
+    }
+
+    fn from_filter(filter: &crate::api::Filter) -> Self {
+        Self {
+            id: Some(filter.id.clone()),
+            title: filter.title.clone(),
+            context: FILTER_CONTEXTS.map(|c| filter.context.iter().any(|fc| fc == c)),
+            action: filter.filter_action,
+            field: 0,
+        }
+    }
+
+    fn selected_contexts(&self) -> Vec<String> {
+        FILTER_CONTEXTS
+            .iter()
+            .zip(self.context)
+            .filter(|(_, on)| *on)
+            .map(|(c, _)| (*c).to_string())
+            .collect()
+    }
+}
+
+/// List being created or edited in the lists view. r[list.manage.edit]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListForm {
+    /// None when creating a new list.
+    pub id: Option<String>,
+    pub title: String,
+    pub replies_policy: RepliesPolicy,
+    pub exclusive: bool,
+    /// Focused row: 0 title, 1 replies policy, 2 exclusive.
+    pub field: usize,
+}
+
+impl ListForm {
+    /// Number of rows in the form.
+    pub const FIELDS: usize = 3;
+
+    const fn new() -> Self {
+        Self {
+            id: None,
+            title: String::new(),
+            replies_policy: RepliesPolicy::List,
+            exclusive: false,
+            field: 0,
+        }
+    }
+
+    fn from_list(list: &crate::api::List) -> Self {
+        Self {
+            id: Some(list.id.clone()),
+            title: list.title.clone(),
+            replies_policy: list.replies_policy,
+            exclusive: list.exclusive,
+            field: 0,
+        }
+    }
+}
+
+/// Which side of the follow graph the follows browser shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FollowsTab {
+    #[default]
+    Following,
+    Followers,
+}
+
+/// Operation applied to the marked accounts in the follows browser. r[follows.bulk]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    Unfollow,
+    Mute,
+    AddToList { id: String, title: String },
+}
+
+impl BulkAction {
+    /// Verb for prompts and progress, e.g. "Unfollow" or "Add to Friends".
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Unfollow => "Unfollow".to_string(),
+            Self::Mute => "Mute".to_string(),
+            Self::AddToList { title, .. } => format!("Add to {title}"),
+        }
+    }
+}
+
+/// A running bulk operation. One account is processed per tick so progress can be drawn.
+#[derive(Debug, Clone)]
+pub struct BulkJob {
+    pub action: BulkAction,
+    /// Accounts still to process, last first (popped from the end).
+    pub pending: Vec<Account>,
+    pub total: usize,
+    pub failed: usize,
+}
+
+impl BulkJob {
+    /// Accounts processed so far.
+    #[must_use]
+    pub fn done(&self) -> usize {
+        self.total - self.pending.len()
+    }
+}
+
+/// State of the followers/following browser. r[follows.list]
+#[derive(Debug, Default)]
+pub struct FollowsBrowser {
+    pub tab: FollowsTab,
+    /// Accounts loaded so far, in server order.
+    pub accounts: Vec<Account>,
+    /// `max_id` for the next page; None when everything is loaded.
+    pub next_max_id: Option<String>,
+    /// Relationship to each loaded account, by account id.
+    pub relationships: HashMap<String, Relationship>,
+    /// Selected index into `filtered()`.
+    pub selected: usize,
+    /// Account ids marked for a bulk action.
+    pub marked: HashSet<String>,
+    /// Search text; only accounts whose handle or name contains it are listed.
+    pub query: String,
+    /// Typing into `query` (after /).
+    pub searching: bool,
+    /// Last bulk error per account id.
+    pub errors: HashMap<String, String>,
+    /// Selected list while choosing where to add the marked accounts.
+    pub list_choice: Option<usize>,
+    /// Bulk action waiting for y/n.
+    pub confirm: Option<BulkAction>,
+    pub job: Option<BulkJob>,
+    pub message: String,
+}
+
+impl FollowsBrowser {
+    /// Indices into `accounts` matching `query` (case-insensitive, handle or display name).
+    #[must_use]
+    pub fn filtered(&self) -> Vec<usize> {
+        let query = self.query.to_lowercase();
+        self.accounts
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| {
+                query.is_empty()
+                    || a.handle().to_lowercase().contains(&query)
+                    || a.display_name.to_lowercase().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn selected_account(&self) -> Option<&Account> {
+        self.filtered()
+            .get(self.selected)
+            .and_then(|&i| self.accounts.get(i))
+    }
+
+    /// Marked accounts in list order, or the selected account when nothing is marked.
+    fn targets(&self) -> Vec<Account> {
+        if self.marked.is_empty() {
+            return self.selected_account().cloned().into_iter().collect();
+        }
+        self.accounts
+            .iter()
+            .filter(|a| self.marked.contains(&a.id))
+            .cloned()
+            .collect()
+    }
+}
+
+/// State of the Explore view. r[explore.trends]
+#[derive(Debug, Default)]
+pub struct ExploreBrowser {
+    pub tab: TrendsKind,
+    /// Trends for `tab`; None until loaded.
+    pub trends: Option<Trends>,
+    pub selected: usize,
+    pub message: String,
+}
+
+/// Step of the report flow, in order. r[report.open]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStep {
+    Category,
+    /// Only for the `violation` category.
+    Rules,
+    Statuses,
+    Comment,
+}
+
+/// Report being written about an account. r[report.open]
+#[derive(Debug, Clone)]
+pub struct ReportForm {
+    pub account: Account,
+    pub step: ReportStep,
+    pub category: ReportCategory,
+    /// Instance rules offered for the `violation` category.
+    pub rules: Vec<Rule>,
+    pub rule_ids: HashSet<String>,
+    /// Posts that can be attached: the reported one first, then the author's recent posts.
+    pub statuses: Vec<crate::api::Status>,
+    pub status_ids: HashSet<String>,
+    pub comment: String,
+    pub forward: bool,
+    /// Cursor in the current step's list.
+    pub cursor: usize,
+    /// View to go back to when the report is sent or cancelled.
+    pub return_to: View,
+    pub message: String,
+}
+
+impl ReportForm {
+    /// Longest comment the server accepts.
+    pub const COMMENT_LIMIT: usize = 1000;
+
+    fn new(account: Account, status: Option<crate::api::Status>, return_to: View) -> Self {
+        Self {
+            account,
+            step: ReportStep::Category,
+            category: ReportCategory::default(),
+            rules: Vec::new(),
+            rule_ids: HashSet::new(),
+            status_ids: status.iter().map(|s| s.id.clone()).collect(),
+            statuses: status.into_iter().collect(),
+            comment: String::new(),
+            forward: false,
+            cursor: 0,
+            return_to,
+            message: String::new(),
+        }
+    }
+
+    /// The account lives on another server, so the report can be forwarded there.
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        self.account.acct.contains('@')
+    }
+
+    /// Number of rows the cursor moves over in the current step.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        match self.step {
+            ReportStep::Category => ReportCategory::ALL.len(),
+            ReportStep::Rules => self.rules.len(),
+            ReportStep::Statuses => self.statuses.len(),
+            ReportStep::Comment => 0,
+        }
+    }
+
+    /// Steps shown for the chosen category (Rules only for `violation`).
+    #[must_use]
+    pub fn steps(&self) -> Vec<ReportStep> {
+        let mut steps = vec![ReportStep::Category];
+        if self.category == ReportCategory::Violation {
+            steps.push(ReportStep::Rules);
+        }
+        steps.extend([ReportStep::Statuses, ReportStep::Comment]);
+        steps
+    }
+
+    /// Move one step forward (`forward`) or back; false when there is no such step.
+    fn go(&mut self, forward: bool) -> bool {
+        let steps = self.steps();
+        let Some(i) = steps.iter().position(|s| *s == self.step) else {
+            return false;
+        };
+        let next = if forward {
+            steps.get(i + 1)
+        } else {
+            i.checked_sub(1).and_then(|i| steps.get(i))
+        };
+        let Some(&next) = next else {
+            return false;
+        };
+        self.step = next;
+        self.cursor = if next == ReportStep::Category {
+            ReportCategory::ALL
+                .iter()
+                .position(|c| *c == self.category)
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        self.message.clear();
+        true
+    }
+
+    fn to_report(&self) -> NewReport {
+        let violation = self.category == ReportCategory::Violation;
+        NewReport {
+            account_id: self.account.id.clone(),
+            status_ids: self
+                .statuses
+                .iter()
+                .filter(|s| self.status_ids.contains(&s.id))
+                .map(|s| s.id.clone())
+                .collect(),
+            category: self.category,
+            rule_ids: self
+                .rules
+                .iter()
+                .filter(|r| violation && self.rule_ids.contains(&r.id))
+                .map(|r| r.id.clone())
+                .collect(),
+            comment: self.comment.trim().to_string(),
+            forward: self.forward && self.is_remote(),
+        }
+    }
+}
+
+/// Which moderation list the moderation view shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModerationTab {
+    #[default]
+    Mutes,
+    Blocks,
+    Domains,
+}
+
+impl ModerationTab {
+    /// The tab after this one (Tab key), wrapping around.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Mutes => Self::Blocks,
+            Self::Blocks => Self::Domains,
+            Self::Domains => Self::Mutes,
+        }
+    }
+}
+
+/// State of the mutes/blocks/domain blocks view. r[moderation.mutes] r[moderation.blocks]
+/// r[moderation.domains]
+#[derive(Debug, Default)]
+pub struct ModerationBrowser {
+    pub tab: ModerationTab,
+    /// Muted or blocked accounts loaded so far (Mutes and Blocks tabs).
+    pub accounts: Vec<Account>,
+    /// Blocked domains loaded so far (Domains tab).
+    pub domains: Vec<String>,
+    /// `max_id` for the next page; None when everything is loaded.
+    pub next_max_id: Option<String>,
+    /// Ids of muted accounts whose notifications are muted too.
+    pub muting_notifications: HashSet<String>,
+    /// Selected index into `accounts` or `domains`.
+    pub selected: usize,
+    /// Hostname being typed for a new domain block (Some while the prompt is open).
+    pub domain_input: Option<String>,
+    pub message: String,
+}
+
+impl ModerationBrowser {
+    /// Number of rows on the current tab.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self.tab {
+            ModerationTab::Mutes | ModerationTab::Blocks => self.accounts.len(),
+            ModerationTab::Domains => self.domains.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,13 +618,127 @@ pub enum View {
     InstanceInfo,
     /// Timeline picker: choose Home / Local / Public / List (press t).
     TimelinePicker,
+    /// r[toot.schedule.list]: posts scheduled for later (press s).
+    ScheduledPosts,
+    /// r[toot.thread.preview]: Compose buffer split into a numbered chain, before posting.
+    ThreadPreview,
+    /// r[dm.list]: direct-message conversations (press d).
+    Conversations,
+    /// r[dm.thread]: chat-like view of one conversation.
+    ConversationThread,
+    /// r[filter.manage.list]: server-side filters (press F).
+    Filters,
+    /// r[filter.manage.keywords]: keywords of the selected filter.
+    FilterKeywords,
+    /// r[list.manage.list]: the user's lists (press L).
+    Lists,
+    /// r[list.manage.members]: accounts in the selected list.
+    ListMembers,
+    /// r[list.add-author]: add or remove a toot's author from lists (l in toot view).
+    ListPicker,
+    /// r[follows.list]: accounts the user follows or is followed by (press w).
+    Follows,
+    /// r[follow-requests.list]: pending follow requests to approve (press R).
+    FollowRequests,
+    /// r[moderation.mutes]: muted and blocked accounts and blocked domains (press M).
+    Moderation,
+    /// r[report.open]: report an account and its posts to the moderators (! in toot view).
+    Report,
+    /// r[explore.trends]: trending tags, posts and links (press e; also when browsing).
+    Explore,
+    /// r[outbox.view]: queued writes waiting to be sent or rejected (press o).
+    Outbox,
+    /// r[errors.debug]: the last error response with its raw body (F12 anywhere).
+    Debug,
 }
 
+impl View {
+    /// Section of the keymap that drives this view, if its keys are configurable.
+    const fn key_view(self) -> Option<KeyView> {
+        match self {
+            Self::Timeline => Some(KeyView::Timeline),
+            Self::TootDetail => Some(KeyView::TootDetail),
+            Self::InstanceInfo => Some(KeyView::InstanceInfo),
+            Self::ScheduledPosts => Some(KeyView::ScheduledPosts),
+            Self::ThreadPreview => Some(KeyView::ThreadPreview),
+            Self::Conversations => Some(KeyView::Conversations),
+            Self::ConversationThread => Some(KeyView::Conversation),
+            Self::Filters => Some(KeyView::Filters),
+            Self::FilterKeywords => Some(KeyView::FilterKeywords),
+            Self::Lists => Some(KeyView::Lists),
+            Self::ListMembers => Some(KeyView::ListMembers),
+            Self::Follows => Some(KeyView::Follows),
+            Self::FollowRequests => Some(KeyView::FollowRequests),
+            Self::Moderation => Some(KeyView::Moderation),
+            Self::Report => Some(KeyView::Report),
+            Self::Explore => Some(KeyView::Explore),
+            Self::Outbox => Some(KeyView::Outbox),
+            Self::Login
+            | Self::Compose
+            | Self::InstancePicker
+            | Self::TimelinePicker
+            | Self::ListPicker
+            | Self::Debug => None,
+        }
+    }
+}
+
+/// A state of the shown toot the user can switch on and off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Toggle {
+    Boost,
+    Favourite,
+    Bookmark,
+}
+
+/// Result of trying a queued write straight away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SendOutcome {
+    Sent,
+    /// Not sent yet (e.g. no connection); it stays queued and is retried. r[outbox.retry]
+    Queued(String),
+    /// The server refused it.
+    Rejected(String),
+}
+
+// One flat state bag per view; each view's flags are independent.
+#[allow(clippy::struct_excessive_bools)]
 pub struct App {
     pub view: View,
     pub config: Option<AppConfig>,
     pub client: Option<MastodonClient>,
     pub statuses: Vec<crate::api::Status>,
+    /// Indices into `statuses` that are shown (server `hide` filters and mute rules removed
+    /// unless `show_hidden`). `selected` and `scroll` index into this list. r[filter.server.hide]
+    pub timeline_visible: Vec<usize>,
+    /// Why each entry of `statuses` is hidden (None = shown). r[mute.show-hidden]
+    pub timeline_hidden: Vec<Option<String>>,
+    /// Show hidden statuses with their reason instead of dropping them (h).
+    pub show_hidden: bool,
+    /// Compiled client-side mute rules from config. r[mute.rules]
+    pub mute_rules: MuteRules,
+    /// Active key bindings: defaults with `[keys.*]` from config. r[keys.config]
+    pub keymap: Keymap,
+    /// Keys typed so far of a multi-key sequence (e.g. the first `g` of `g g`).
+    key_pending: Vec<Key>,
+    /// Help overlay listing the current view's bindings is shown (?). r[keys.help]
+    pub help_open: bool,
+    /// `:` command palette, while open. r[palette.open]
+    pub palette: Option<Palette>,
+    /// Mouse capture is on (`mouse` in config, default true). r[mouse.capture]
+    pub mouse: bool,
+    /// Clickable areas of the last frame drawn. r[mouse.select]
+    pub hits: HitMap,
+    /// Time and row of the last click on a timeline row, to spot a double click.
+    last_click: Option<(Instant, usize)>,
+    /// Single, split or columns. r[layout.split] r[layout.columns]
+    pub layout: LayoutMode,
+    /// Timeline columns in the columns layout (empty otherwise). r[layout.columns]
+    pub columns: Vec<TimelineColumn>,
+    /// Index into `columns` of the column keys go to.
+    pub focused_column: usize,
+    /// Whether the user follows each reply-target account seen so far.
+    pub following: HashMap<String, bool>,
     pub selected: usize,
     pub scroll: usize,
     pub loading: bool,
@@ -72,10 +751,24 @@ pub struct App {
 
     pub detail_status: Option<crate::api::Status>,
     pub detail_message: String,
+    /// Show a status collapsed by a `warn` filter anyway (v). r[filter.server.warn]
+    pub detail_revealed: bool,
+    /// View Esc returns to from the toot view (Timeline or Explore).
+    pub detail_return_to: View,
+    /// View to return to when the debug panel is closed.
+    pub debug_return_to: View,
 
     pub compose_buffer: String,
     pub compose_reply_to_id: Option<String>,
     pub compose_error: String,
+    /// Optional time to schedule the post for (e.g. "in 2h"); empty posts immediately.
+    pub compose_schedule: String,
+    /// True while typing into the schedule field instead of the toot body (Tab toggles).
+    pub compose_schedule_focus: bool,
+    /// Visibility to post with; None uses the account default. Direct for DM replies.
+    pub compose_visibility: Option<Visibility>,
+    /// View to return to when Compose is cancelled or the post is sent.
+    pub compose_return_to: View,
 
     /// Shown on Timeline when a load failed (so we don't retry every tick).
     pub timeline_message: String,
@@ -84,7 +777,7 @@ pub struct App {
     pub timeline_visible_rows: usize,
 
     /// When set, we are viewing this instance's public timeline without login (read-only).
-    pub anonymous_instance_url: Option<String>,
+    pub anonymous_instance: Option<PublicClient>,
 
     /// View to restore when `InstancePicker` is cancelled. r[browse.instance.cancel]
     pub return_to_view: View,
@@ -101,8 +794,80 @@ pub struct App {
     /// Which timeline is displayed (Home, Local, Public, or a list).
     pub current_timeline: TimelineSelection,
 
-    /// User's lists (fetched when opening timeline picker; requires read:lists).
+    /// User's lists (fetched when opening timeline picker or lists view; requires read:lists).
     pub lists: Vec<crate::api::List>,
+    /// Selected index in `lists` (lists view).
+    pub lists_selected: usize,
+    /// Error or status line for the list views.
+    pub lists_message: String,
+    /// List being created or edited (Some while the form is open).
+    pub list_form: Option<ListForm>,
+    /// Waiting for y/n before deleting the selected list.
+    pub lists_confirm_delete: bool,
+    /// Members of the selected list.
+    pub list_members: Vec<Account>,
+    /// Selected index in `list_members`.
+    pub list_members_selected: usize,
+    /// Handle being typed to add a member (Some while the prompt is open).
+    pub list_member_input: Option<String>,
+    /// Author offered to the list picker.
+    pub list_picker_account: Option<Account>,
+    /// Ids of lists that already contain `list_picker_account`.
+    pub list_picker_member_of: Vec<String>,
+    /// Selected index in `lists` (list picker).
+    pub list_picker_selected: usize,
+    /// Followers/following browser. r[follows.list]
+    pub follows: FollowsBrowser,
+
+    /// Pending follow requests. r[follow-requests.list]
+    pub follow_requests: Vec<Account>,
+    /// `max_id` for the next page of follow requests.
+    pub follow_requests_next: Option<String>,
+    /// Selected index in `follow_requests`.
+    pub follow_requests_selected: usize,
+    /// Error or status line for the follow request view.
+    pub follow_requests_message: String,
+    /// Pending request count for the timeline header, and whether there are more pages.
+    /// None until first checked. r[follow-requests.count]
+    pub follow_request_count: Option<(usize, bool)>,
+    /// Newest `follow_request` notification id already seen.
+    pub follow_request_seen: Option<String>,
+    /// Handle from a new `follow_request` notification, until the view is opened.
+    /// r[follow-requests.notify]
+    pub follow_request_notice: Option<String>,
+    /// When follow requests were last checked.
+    pub follow_requests_checked_at: Option<Instant>,
+    /// Mutes, blocks and domain blocks. r[moderation.mutes]
+    pub moderation: ModerationBrowser,
+    /// Report being written (Some while the report flow is open). r[report.open]
+    pub report: Option<ReportForm>,
+    /// Trends. r[explore.trends]
+    pub explore: ExploreBrowser,
+    /// Newest Home status read, here or in another client (GET/POST /api/v1/markers).
+    /// r[markers.restore]
+    pub home_marker: Option<String>,
+    /// `home_marker` moved since it was last saved.
+    pub home_marker_dirty: bool,
+    pub home_marker_saved_at: Option<Instant>,
+    /// Markers are fetched, and the position restored, once per session.
+    pub markers_restored: bool,
+    /// Cached timelines for the current source, with its cache key. r[cache.store]
+    pub cache: Option<(String, TimelineCache)>,
+    /// Some while the shown statuses come from the cache: when they were fetched.
+    /// r[cache.stale]
+    pub timeline_cached_at: Option<String>,
+    /// The last refresh failed, so cached statuses are shown instead. r[cache.offline]
+    pub offline: bool,
+    /// The cache was offered for the first timeline load of the session.
+    pub cache_shown_at_start: bool,
+    /// Cached statuses are on screen at startup and the real fetch is still due.
+    pub refresh_pending: bool,
+    /// Queued writes of the logged-in account, with its file key. r[outbox.queue]
+    pub outbox: Option<(String, Outbox)>,
+    /// Selected index in the outbox view.
+    pub outbox_selected: usize,
+    /// Error or status line for the outbox view.
+    pub outbox_message: String,
 
     /// Timeline picker: options to choose from (built when opening picker).
     pub timeline_picker_options: Vec<TimelineSelection>,
@@ -111,6 +876,57 @@ pub struct App {
     /// When `get_lists()` fails (e.g. missing `read:lists`), show hint to re-login.
     pub timeline_picker_lists_message: String,
 
+    /// Scheduled posts (fetched when opening the scheduled view). r[toot.schedule.list]
+    pub scheduled_statuses: Vec<crate::api::ScheduledStatus>,
+    /// Selected index in `scheduled_statuses`.
+    pub scheduled_selected: usize,
+    /// Status or error line shown in the scheduled view.
+    pub scheduled_message: String,
+    /// New time being typed for the selected post (Some while rescheduling).
+    pub scheduled_reschedule_input: Option<String>,
+    /// Waiting for y/n before cancelling the selected post.
+    pub scheduled_confirm_cancel: bool,
+
+    /// Thread preview: the Compose buffer split into posts. r[toot.thread.preview]
+    pub thread_parts: Vec<String>,
+    /// Ids of parts already posted, in order; posting resumes after the last one.
+    pub thread_posted_ids: Vec<String>,
+    /// Idempotency key of each part, made at the split and kept across resumes so a part
+    /// whose response was lost is not posted twice.
+    pub thread_keys: Vec<String>,
+    /// First part shown in the preview (j/k scroll).
+    pub thread_scroll: usize,
+    /// Progress or failure message shown in the thread preview.
+    pub thread_message: String,
+
+    /// Logged-in user's account, fetched on first use (tells own DMs apart).
+    pub me: Option<crate::api::Account>,
+    /// Direct-message conversations. r[dm.list]
+    pub conversations: Vec<crate::api::Conversation>,
+    /// Selected index in `conversations`.
+    pub conversations_selected: usize,
+    /// Error or status line for the conversations and conversation thread views.
+    pub conversations_message: String,
+    /// Messages of the open conversation, oldest first. r[dm.thread]
+    pub conversation_statuses: Vec<crate::api::Status>,
+    /// Lines scrolled up from the newest message in the conversation thread.
+    pub conversation_scroll: usize,
+
+    /// Server-side filters (v2). r[filter.manage.list]
+    pub filters: Vec<crate::api::Filter>,
+    /// Selected index in `filters`.
+    pub filters_selected: usize,
+    /// Error or status line for the filter views.
+    pub filters_message: String,
+    /// Filter being created or edited (Some while the form is open).
+    pub filter_form: Option<FilterForm>,
+    /// Waiting for y/n before deleting the selected filter.
+    pub filters_confirm_delete: bool,
+    /// Selected keyword of the selected filter.
+    pub filter_keywords_selected: usize,
+    /// Keyword being added (id None) or edited, with its text.
+    pub filter_keyword_input: Option<(Option<String>, String)>,
+
     runtime: Runtime,
 }
 
@@ -118,6 +934,17 @@ impl App {
     pub fn new() -> Result<Self> {
         let runtime = Runtime::new().map_err(|e| MastotuiError::Config(e.to_string()))?;
         let config = load_config()?;
+        let mute_rules =
+            MuteRules::compile(config.as_ref().map_or(&[][..], |c| c.mute_rules.as_slice()));
+        let keymap = config
+            .as_ref()
+            .map_or_else(|| Ok(Keymap::default()), |c| Keymap::from_config(&c.keys))?;
+        set_theme(Theme::load(
+            config.as_ref().and_then(|c| c.theme.as_deref()),
+        )?);
+        let layout = config
+            .as_ref()
+            .map_or(Ok(LayoutMode::Single), layout_from_config)?;
 
         let (view, client) = config.as_ref().map_or(Ok((View::Login, None)), |cfg| {
             client_from_stored_credentials(&cfg.instance_url)
@@ -129,6 +956,21 @@ impl App {
             config: config.clone(),
             client,
             statuses: Vec::new(),
+            timeline_visible: Vec::new(),
+            timeline_hidden: Vec::new(),
+            show_hidden: false,
+            mute_rules,
+            keymap,
+            key_pending: Vec::new(),
+            help_open: false,
+            palette: None,
+            mouse: config.as_ref().and_then(|c| c.mouse).unwrap_or(true),
+            hits: HitMap::default(),
+            last_click: None,
+            layout: LayoutMode::Single,
+            columns: Vec::new(),
+            focused_column: 0,
+            following: HashMap::new(),
             selected: 0,
             scroll: 0,
             loading: false,
@@ -142,12 +984,19 @@ impl App {
             login_message: String::new(),
             detail_status: None,
             detail_message: String::new(),
+            detail_revealed: false,
+            detail_return_to: View::Timeline,
+            debug_return_to: View::Timeline,
             compose_buffer: String::new(),
             compose_reply_to_id: None,
             compose_error: String::new(),
+            compose_schedule: String::new(),
+            compose_schedule_focus: false,
+            compose_visibility: None,
+            compose_return_to: View::Timeline,
             timeline_message: String::new(),
             timeline_visible_rows: 20,
-            anonymous_instance_url: None,
+            anonymous_instance: None,
             return_to_view: View::Login,
             instance_picker_input: String::new(),
             instance_picker_known: config
@@ -162,15 +1011,73 @@ impl App {
                 TimelineSelection::Public
             },
             lists: Vec::new(),
+            lists_selected: 0,
+            lists_message: String::new(),
+            list_form: None,
+            lists_confirm_delete: false,
+            list_members: Vec::new(),
+            list_members_selected: 0,
+            list_member_input: None,
+            list_picker_account: None,
+            list_picker_member_of: Vec::new(),
+            list_picker_selected: 0,
+            follows: FollowsBrowser::default(),
+            follow_requests: Vec::new(),
+            follow_requests_next: None,
+            follow_requests_selected: 0,
+            follow_requests_message: String::new(),
+            follow_request_count: None,
+            follow_request_seen: None,
+            follow_request_notice: None,
+            follow_requests_checked_at: None,
+            moderation: ModerationBrowser::default(),
+            report: None,
+            explore: ExploreBrowser::default(),
+            home_marker: None,
+            home_marker_dirty: false,
+            home_marker_saved_at: None,
+            markers_restored: false,
+            cache: None,
+            timeline_cached_at: None,
+            offline: false,
+            cache_shown_at_start: false,
+            refresh_pending: false,
+            outbox: None,
+            outbox_selected: 0,
+            outbox_message: String::new(),
             timeline_picker_options: Vec::new(),
             timeline_picker_selected: 0,
             timeline_picker_lists_message: String::new(),
+            scheduled_statuses: Vec::new(),
+            scheduled_selected: 0,
+            scheduled_message: String::new(),
+            scheduled_reschedule_input: None,
+            scheduled_confirm_cancel: false,
+            thread_parts: Vec::new(),
+            thread_posted_ids: Vec::new(),
+            thread_keys: Vec::new(),
+            thread_scroll: 0,
+            thread_message: String::new(),
+            me: None,
+            conversations: Vec::new(),
+            conversations_selected: 0,
+            conversations_message: String::new(),
+            conversation_statuses: Vec::new(),
+            conversation_scroll: 0,
+            filters: Vec::new(),
+            filters_selected: 0,
+            filters_message: String::new(),
+            filter_form: None,
+            filters_confirm_delete: false,
+            filter_keywords_selected: 0,
+            filter_keyword_input: None,
             runtime,
         };
 
         if app.view == View::Login && app.config.is_some() && !app.instance_url.is_empty() {
             let _ = app.start_login_flow();
         }
+        app.set_layout(layout);
 
         Ok(app)
     }
@@ -191,9 +1098,13 @@ impl App {
             .as_ref()
             .map(|c| vec![c.instance_url.clone()])
             .unwrap_or_default();
-        if let Some(ref u) = self.anonymous_instance_url {
-            if !known.contains(u) {
-                known.push(u.clone());
+        if let Some(u) = self
+            .anonymous_instance
+            .as_ref()
+            .map(PublicClient::instance_url)
+        {
+            if !known.iter().any(|k| k == u) {
+                known.push(u.to_string());
             }
         }
         self.instance_picker_known = known;
@@ -237,6 +1148,215 @@ impl App {
         self.view = View::TimelinePicker;
     }
 
+    /// Open Compose for a new toot or a reply, clearing any previous draft.
+    /// Esc or a successful post returns to the view Compose was opened from.
+    fn open_compose(&mut self, reply_to_id: Option<String>) {
+        self.compose_buffer.clear();
+        self.compose_reply_to_id = reply_to_id;
+        self.compose_error.clear();
+        self.compose_schedule.clear();
+        self.compose_schedule_focus = false;
+        self.compose_visibility = None;
+        self.compose_return_to = self.view;
+        self.view = View::Compose;
+    }
+
+    const fn compose_title(&self) -> &'static str {
+        match (self.compose_visibility, self.compose_reply_to_id.is_some()) {
+            (Some(Visibility::Direct), _) => " Direct message ",
+            (_, true) => " Reply ",
+            (_, false) => " New toot ",
+        }
+    }
+
+    /// Clear the draft after a successful post and go back where Compose was opened from.
+    fn finish_compose(&mut self) {
+        self.compose_buffer.clear();
+        self.compose_reply_to_id = None;
+        self.compose_schedule.clear();
+        self.compose_visibility = None;
+        self.compose_error.clear();
+        self.view = self.compose_return_to;
+        if self.view == View::ConversationThread {
+            self.reload_conversation();
+        } else {
+            self.load_timeline(false);
+        }
+    }
+
+    /// Open the conversations view (press d) and fetch conversations. r[dm.list]
+    fn open_conversations(&mut self) {
+        self.view = View::Conversations;
+        self.load_conversations();
+    }
+
+    fn load_conversations(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_conversations()) {
+            Ok(list) => {
+                self.conversations = list;
+                self.conversations_selected = self
+                    .conversations_selected
+                    .min(self.conversations.len().saturating_sub(1));
+                self.conversations_message.clear();
+            }
+            Err(e) => self.conversations_message = format!("Failed to load conversations: {e}"),
+        }
+    }
+
+    /// r[dm.mark-read]: mark the selected conversation read on the server and locally.
+    fn mark_selected_conversation_read(&mut self) {
+        let Some(conv) = self.conversations.get(self.conversations_selected) else {
+            return;
+        };
+        if !conv.unread {
+            return;
+        }
+        let id = conv.id.clone();
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.mark_conversation_read(&id)) {
+            Ok(_) => {
+                if let Some(c) = self.conversations.iter_mut().find(|c| c.id == id) {
+                    c.unread = false;
+                }
+            }
+            Err(e) => self.conversations_message = format!("Failed to mark read: {e}"),
+        }
+    }
+
+    /// r[dm.thread]: open the selected conversation as a chat, marking it read.
+    fn open_conversation_thread(&mut self) {
+        if self
+            .conversations
+            .get(self.conversations_selected)
+            .is_none()
+        {
+            return;
+        }
+        self.mark_selected_conversation_read();
+        self.ensure_me();
+        self.conversation_scroll = 0;
+        self.view = View::ConversationThread;
+        self.reload_conversation();
+    }
+
+    /// Fetch the full chain around the conversation's last status, oldest first.
+    fn reload_conversation(&mut self) {
+        let Some(last) = self
+            .conversations
+            .get(self.conversations_selected)
+            .and_then(|c| c.last_status.clone())
+        else {
+            self.conversation_statuses.clear();
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_status_context(&last.id)) {
+            Ok(ctx) => {
+                let mut chain = ctx.ancestors;
+                chain.push(last);
+                chain.extend(ctx.descendants);
+                if let Some(newest) = chain.last().cloned() {
+                    if let Some(c) = self.conversations.get_mut(self.conversations_selected) {
+                        c.last_status = Some(newest);
+                    }
+                }
+                self.conversation_statuses = chain;
+                self.conversations_message.clear();
+            }
+            Err(e) => {
+                self.conversation_statuses = vec![last];
+                self.conversations_message = format!("Failed to load conversation: {e}");
+            }
+        }
+    }
+
+    /// r[dm.reply]: reply to the newest message with direct visibility, mentioning everyone.
+    fn reply_in_conversation(&mut self) {
+        let Some(conv) = self.conversations.get(self.conversations_selected) else {
+            return;
+        };
+        let reply_to = self
+            .conversation_statuses
+            .last()
+            .or(conv.last_status.as_ref())
+            .map(|s| s.id.clone());
+        let mut mentions = String::new();
+        for a in &conv.accounts {
+            mentions.push('@');
+            mentions.push_str(a.handle());
+            mentions.push(' ');
+        }
+        self.open_compose(reply_to);
+        self.compose_visibility = Some(Visibility::Direct);
+        self.compose_buffer = mentions;
+    }
+
+    /// Actions of the conversation list. r[dm.list]
+    fn conversations_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Timeline;
+                self.conversations_message.clear();
+            }
+            Action::Up => {
+                self.conversations_selected = self.conversations_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.conversations_selected = (self.conversations_selected + 1)
+                    .min(self.conversations.len().saturating_sub(1));
+            }
+            Action::Open => self.open_conversation_thread(),
+            Action::MarkRead => self.mark_selected_conversation_read(),
+            Action::Refresh => self.load_conversations(),
+            _ => {}
+        }
+    }
+
+    /// Actions of an open conversation; up scrolls back to older messages. r[dm.thread]
+    fn conversation_thread_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Conversations;
+                self.conversations_message.clear();
+            }
+            Action::Up => self.conversation_scroll += 1,
+            Action::Down => self.conversation_scroll = self.conversation_scroll.saturating_sub(1),
+            Action::Reply if self.client.is_some() => self.reply_in_conversation(),
+            _ => {}
+        }
+    }
+
+    /// Open the scheduled posts view (press s) and fetch the list. r[toot.schedule.list]
+    fn open_scheduled_posts(&mut self) {
+        self.view = View::ScheduledPosts;
+        self.scheduled_reschedule_input = None;
+        self.scheduled_confirm_cancel = false;
+        self.load_scheduled_posts();
+    }
+
+    fn load_scheduled_posts(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_scheduled_statuses()) {
+            Ok(list) => {
+                self.scheduled_statuses = list;
+                self.scheduled_selected = self
+                    .scheduled_selected
+                    .min(self.scheduled_statuses.len().saturating_sub(1));
+                self.scheduled_message.clear();
+            }
+            Err(e) => self.scheduled_message = format!("Failed to load scheduled posts: {e}"),
+        }
+    }
+
     fn start_login_flow(&mut self) -> Result<()> {
         let url = self.instance_url.trim().to_string();
         if url.is_empty() {
@@ -259,11 +1379,12 @@ impl App {
     }
 
     pub fn draw(&mut self, frame: &mut Frame) {
+        self.hits = HitMap::default();
         if self.view == View::Timeline {
             let content_height = frame.area().height as usize;
             let content_height = content_height.saturating_sub(2).max(1);
             self.timeline_visible_rows = (content_height / 2).max(1);
-            if !self.statuses.is_empty() {
+            if !self.timeline_visible.is_empty() {
                 if self.selected >= self.scroll + self.timeline_visible_rows {
                     self.scroll = self.selected - self.timeline_visible_rows + 1;
                 } else if self.selected < self.scroll {
@@ -279,24 +1400,32 @@ impl App {
                 &self.login_code,
                 &self.login_message,
             ),
-            View::Timeline => draw_timeline(
-                frame,
-                &self.current_timeline.label(),
-                &self.statuses,
-                self.selected,
-                self.scroll,
-                self.loading,
-                &self.timeline_message,
-            ),
+            View::Timeline => self.hits = self.draw_timelines(frame, None),
             View::TootDetail => {
                 if let Some(ref s) = self.detail_status {
-                    draw_toot_detail(frame, s, None, &self.detail_message);
+                    // r[layout.split]: a toot opened from the timeline stays beside it.
+                    self.hits = if self.layout == LayoutMode::Split
+                        && self.detail_return_to == View::Timeline
+                    {
+                        let detail = (s, self.detail_revealed, self.detail_message.as_str());
+                        self.draw_timelines(frame, Some(detail))
+                    } else {
+                        draw_toot_detail(
+                            frame,
+                            &self.keymap,
+                            s,
+                            self.detail_revealed,
+                            &self.detail_message,
+                        )
+                    };
                 }
             }
             View::Compose => draw_compose(
                 frame,
                 &self.compose_buffer,
-                self.compose_reply_to_id.as_deref(),
+                self.compose_title(),
+                &self.compose_schedule,
+                self.compose_schedule_focus,
                 &self.compose_error,
                 CHAR_LIMIT,
             ),
@@ -309,9 +1438,12 @@ impl App {
             ),
             View::InstanceInfo => draw_instance_info(
                 frame,
+                &self.keymap,
                 &self.instance_url,
                 self.client.is_some(),
-                self.anonymous_instance_url.as_deref(),
+                self.anonymous_instance
+                    .as_ref()
+                    .map(PublicClient::instance_url),
             ),
             View::TimelinePicker => draw_timeline_picker(
                 frame,
@@ -319,14 +1451,167 @@ impl App {
                 self.timeline_picker_selected,
                 &self.timeline_picker_lists_message,
             ),
-        }
-    }
-
-    pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
-        let mut quit = false;
-        let code = key.code;
-        let mods = key.modifiers;
-        match self.view {
+            View::Conversations => draw_conversations(
+                frame,
+                &self.keymap,
+                &self.conversations,
+                self.conversations_selected,
+                &self.conversations_message,
+            ),
+            View::ConversationThread => draw_conversation_thread(
+                frame,
+                &self.keymap,
+                self.conversations.get(self.conversations_selected),
+                &self.conversation_statuses,
+                self.me.as_ref().map(|a| a.id.as_str()),
+                self.conversation_scroll,
+                &self.conversations_message,
+            ),
+            View::Filters => draw_filters(
+                frame,
+                &self.keymap,
+                &self.filters,
+                self.filters_selected,
+                self.filter_form.as_ref(),
+                self.filters_confirm_delete,
+                &self.filters_message,
+            ),
+            View::FilterKeywords => draw_filter_keywords(
+                frame,
+                &self.keymap,
+                self.filters.get(self.filters_selected),
+                self.filter_keywords_selected,
+                self.filter_keyword_input.as_ref().map(|(_, k)| k.as_str()),
+                &self.filters_message,
+            ),
+            View::Lists => draw_lists(
+                frame,
+                &self.keymap,
+                &self.lists,
+                self.lists_selected,
+                self.list_form.as_ref(),
+                self.lists_confirm_delete,
+                &self.lists_message,
+            ),
+            View::ListMembers => draw_list_members(
+                frame,
+                &self.keymap,
+                self.lists.get(self.lists_selected),
+                &self.list_members,
+                self.list_members_selected,
+                self.list_member_input.as_deref(),
+                &self.lists_message,
+            ),
+            View::ListPicker => draw_list_picker(
+                frame,
+                self.list_picker_account.as_ref(),
+                &self.lists,
+                &self.list_picker_member_of,
+                self.list_picker_selected,
+                &self.lists_message,
+            ),
+            View::Follows => draw_follows(frame, &self.keymap, &self.follows, &self.lists),
+            View::FollowRequests => draw_follow_requests(
+                frame,
+                &self.keymap,
+                &self.follow_requests,
+                self.follow_requests_selected,
+                self.follow_requests_next.is_some(),
+                &self.follow_requests_message,
+            ),
+            View::Moderation => draw_moderation(frame, &self.keymap, &self.moderation),
+            View::Report => {
+                if let Some(ref form) = self.report {
+                    draw_report(frame, &self.keymap, form);
+                }
+            }
+            View::Explore => draw_explore(frame, &self.keymap, &self.explore),
+            View::Debug => draw_debug(
+                frame,
+                self.client
+                    .as_ref()
+                    .map_or_else(
+                        || {
+                            self.anonymous_instance
+                                .as_ref()
+                                .and_then(PublicClient::last_error)
+                        },
+                        MastodonClient::last_error,
+                    )
+                    .as_ref(),
+            ),
+            View::Outbox => draw_outbox(
+                frame,
+                &self.keymap,
+                self.outbox
+                    .as_ref()
+                    .map_or(&[][..], |(_, o)| o.items.as_slice()),
+                self.outbox_selected,
+                &self.outbox_message,
+            ),
+            View::ThreadPreview => draw_thread_preview(
+                frame,
+                &self.keymap,
+                &self.thread_parts,
+                self.thread_posted_ids.len(),
+                self.thread_scroll,
+                &self.thread_message,
+            ),
+            View::ScheduledPosts => draw_scheduled_posts(
+                frame,
+                &self.keymap,
+                &self.scheduled_statuses,
+                self.scheduled_selected,
+                self.scheduled_reschedule_input.as_deref(),
+                self.scheduled_confirm_cancel,
+                &self.scheduled_message,
+            ),
+        }
+        if self.help_open {
+            let (title, rows) = self.help_rows();
+            draw_help(frame, title, &rows);
+        }
+        if let Some(ref palette) = self.palette {
+            let matches: Vec<(String, &str)> = palette
+                .matches()
+                .into_iter()
+                .map(|(command, keys)| (command.label(), keys.as_str()))
+                .collect();
+            draw_palette(frame, &palette.input, &matches, palette.selected);
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let mut quit = false;
+        let code = key.code;
+        let mods = key.modifiers;
+        // r[keys.help]: any key closes the help overlay
+        if self.help_open {
+            self.help_open = false;
+            return Ok(false);
+        }
+        if self.palette.is_some() {
+            return Ok(self.handle_palette_key(code));
+        }
+        // r[keys.help]: F1 anywhere; ? where it is not typed (configurable views bind it)
+        if code == KeyCode::F(1)
+            || (code == KeyCode::Char('?') && self.view.key_view().is_none() && !self.typing())
+        {
+            self.help_open = true;
+            return Ok(false);
+        }
+        // r[palette.open]: views with a keymap bind it; the others take it unless typing
+        if code == KeyCode::Char(':') && self.view.key_view().is_none() && !self.typing() {
+            self.open_palette();
+            return Ok(false);
+        }
+        // r[errors.debug]
+        if code == KeyCode::F(12) && self.view != View::Debug {
+            self.debug_return_to = self.view;
+            self.view = View::Debug;
+            return Ok(false);
+        }
+        match self.view {
             View::Login => match code {
                 KeyCode::Char('q') => {
                     if self.auth_url.is_empty() {
@@ -389,6 +1674,7 @@ impl App {
                                 Ok(token) => {
                                     self.client = Some(MastodonClient::new(&url, &token)?);
                                     save_config(self.config.as_ref().unwrap())?;
+                                    self.current_timeline = TimelineSelection::Home;
                                     self.view = View::Timeline;
                                     self.login_message.clear();
                                     self.load_timeline(false);
@@ -406,176 +1692,35 @@ impl App {
                 }
                 _ => {}
             },
-            View::Timeline => match code {
-                KeyCode::Char('q') => quit = true,
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if self.selected > 0 {
-                        self.selected -= 1;
-                        if self.selected < self.scroll {
-                            self.scroll = self.selected;
-                        }
-                    }
-                }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if self.selected + 1 < self.statuses.len() {
-                        self.selected += 1;
-                        if self.selected >= self.scroll + self.timeline_visible_rows {
-                            self.scroll =
-                                (self.selected + 1).saturating_sub(self.timeline_visible_rows);
-                        }
-                    }
-                }
-                KeyCode::Enter => {
-                    if let Some(s) = self.statuses.get(self.selected).cloned() {
-                        self.detail_status = Some(s);
-                        self.detail_message.clear();
-                        self.view = View::TootDetail;
-                    }
-                }
-                KeyCode::Char('p') => {
-                    if self.client.is_some() {
-                        self.compose_buffer.clear();
-                        self.compose_reply_to_id = None;
-                        self.compose_error.clear();
-                        self.view = View::Compose;
-                    }
-                }
-                KeyCode::Char('r') => {
-                    self.load_timeline(false);
-                }
-                KeyCode::Char('m') => {
-                    self.load_timeline(true);
-                }
-                KeyCode::Char('i') => self.open_instance_info(View::Timeline),
-                KeyCode::Char('t') => self.open_timeline_picker(),
-                _ => {}
-            },
-            View::TootDetail => match code {
-                KeyCode::Esc => {
-                    self.view = View::Timeline;
-                    self.detail_message.clear();
-                }
-                KeyCode::Char('r') => {
-                    if self.client.is_some() {
-                        if let Some(ref s) = self.detail_status {
-                            self.compose_buffer.clear();
-                            self.compose_reply_to_id = Some(s.id.clone());
-                            self.compose_error.clear();
-                            self.view = View::Compose;
-                        }
-                    }
-                }
-                KeyCode::Char('b') => {
-                    if let Some(ref client) = self.client {
-                        if let Some(ref s) = self.detail_status {
-                            let id = s.id.clone();
-                            let reblog = !s.reblogged.unwrap_or(false);
-                            match self.runtime.block_on(client.reblog(&id, reblog)) {
-                                Ok(updated) => {
-                                    self.detail_status = Some(updated);
-                                    self.detail_message =
-                                        if reblog { "Boosted." } else { "Unboosted." }.to_string();
-                                }
-                                Err(e) => self.detail_message = format!("Error: {e}"),
-                            }
-                        }
-                    }
-                }
-                KeyCode::Char('f') => {
-                    if let Some(ref client) = self.client {
-                        if let Some(ref s) = self.detail_status {
-                            let id = s.id.clone();
-                            let fav = !s.favourited.unwrap_or(false);
-                            match self.runtime.block_on(client.favourite(&id, fav)) {
-                                Ok(updated) => {
-                                    self.detail_status = Some(updated);
-                                    self.detail_message =
-                                        if fav { "Favourited." } else { "Unfavourited." }
-                                            .to_string();
-                                }
-                                Err(e) => self.detail_message = format!("Error: {e}"),
-                            }
-                        }
-                    }
-                }
-                KeyCode::Char('i') => self.open_instance_info(View::TootDetail),
-                _ => {}
-            },
             View::Compose => match code {
                 KeyCode::Esc => {
-                    self.view = if self.compose_reply_to_id.is_some() {
-                        View::TootDetail
-                    } else {
-                        View::Timeline
-                    };
+                    self.view = self.compose_return_to;
                     self.compose_error.clear();
                 }
                 KeyCode::Char('i') if mods.contains(KeyModifiers::CONTROL) => {
                     self.open_instance_info(View::Compose);
                 }
-                KeyCode::Enter => {
-                    let text = self.compose_buffer.trim().to_string();
-                    if text.is_empty() {
-                        self.compose_error = "Cannot post empty toot.".to_string();
-                    } else if text.chars().count() > CHAR_LIMIT {
-                        self.compose_error = format!("Over {CHAR_LIMIT} character limit.");
-                    } else if let Some(ref client) = self.client {
-                        let reply_to = self.compose_reply_to_id.clone();
-                        match self
-                            .runtime
-                            .block_on(client.post_status(&text, reply_to.as_deref()))
-                        {
-                            Ok(_) => {
-                                self.compose_buffer.clear();
-                                self.compose_reply_to_id = None;
-                                self.compose_error.clear();
-                                self.view = if reply_to.is_some() {
-                                    View::TootDetail
-                                } else {
-                                    View::Timeline
-                                };
-                                self.load_timeline(false);
-                            }
-                            Err(e) => self.compose_error = format!("Post failed: {e}"),
-                        }
-                    }
+                KeyCode::Char('t') if mods.contains(KeyModifiers::CONTROL) => {
+                    self.open_thread_preview();
+                }
+                KeyCode::Enter
+                    if mods.contains(KeyModifiers::ALT) && !self.compose_schedule_focus =>
+                {
+                    self.compose_buffer.push('\n');
                 }
+                KeyCode::Enter => self.submit_compose(),
+                KeyCode::Tab => self.compose_schedule_focus = !self.compose_schedule_focus,
+                KeyCode::Char(c) if self.compose_schedule_focus => self.compose_schedule.push(c),
                 KeyCode::Char(c) => self.compose_buffer.push(c),
+                KeyCode::Backspace if self.compose_schedule_focus => {
+                    self.compose_schedule.pop();
+                }
                 KeyCode::Backspace => {
                     self.compose_buffer.pop();
                 }
                 _ => {}
             },
-            View::InstanceInfo => match code {
-                KeyCode::Esc => self.view = self.return_to_view,
-                KeyCode::Char('l') => {
-                    if self.client.is_some() {
-                        // r[instance.info.logout]
-                        if let Ok(host) = instance_host_from_url(&self.instance_url) {
-                            let _ = delete_access_token(&host);
-                        }
-                        self.client = None;
-                        self.statuses.clear();
-                        self.selected = 0;
-                        self.scroll = 0;
-                        self.timeline_message.clear();
-                        self.login_message = "Logged out.".to_string();
-                        self.view = View::Login;
-                        if self.config.is_some() && !self.instance_url.is_empty() {
-                            let _ = self.start_login_flow();
-                        }
-                    } else {
-                        // r[instance.info.login]
-                        self.view = View::Login;
-                        self.login_message.clear();
-                    }
-                }
-                KeyCode::Char('b') => {
-                    // r[instance.info.browse]
-                    self.open_instance_picker(View::InstanceInfo);
-                }
-                _ => {}
-            },
+            #[allow(clippy::collapsible_match)]
             View::InstancePicker => match code {
                 KeyCode::Esc => {
                     self.view = self.return_to_view;
@@ -593,20 +1738,23 @@ impl App {
                     if url.is_empty() {
                         self.instance_picker_message =
                             "Enter a URL or pick an instance.".to_string();
-                    } else if let Err(e) = instance_host_from_url(url) {
-                        self.instance_picker_message = format!("Invalid URL: {e}");
                     } else {
-                        let url = url.to_string();
-                        self.anonymous_instance_url = Some(url);
-                        self.client = None;
-                        self.current_timeline = TimelineSelection::Public;
-                        self.statuses.clear();
-                        self.selected = 0;
-                        self.scroll = 0;
-                        self.timeline_message.clear();
-                        self.view = View::Timeline;
-                        self.instance_picker_message.clear();
-                        self.load_timeline(false);
+                        match PublicClient::new(url) {
+                            Err(e) => self.instance_picker_message = format!("Invalid URL: {e}"),
+                            Ok(public) => {
+                                self.anonymous_instance = Some(public);
+                                self.client = None;
+                                self.current_timeline = TimelineSelection::Public;
+                                self.statuses.clear();
+                                self.timeline_visible.clear();
+                                self.selected = 0;
+                                self.scroll = 0;
+                                self.timeline_message.clear();
+                                self.view = View::Timeline;
+                                self.instance_picker_message.clear();
+                                self.load_timeline(false);
+                            }
+                        }
                     }
                 }
                 KeyCode::Backspace => {
@@ -629,19 +1777,15 @@ impl App {
                 }
                 _ => {}
             },
+            #[allow(clippy::collapsible_match)]
             View::TimelinePicker => match code {
                 // r[timeline.select.submit]: Esc cancels; Enter switches and loads
                 KeyCode::Esc => self.view = View::Timeline,
                 KeyCode::Enter => {
                     if self.timeline_picker_selected < self.timeline_picker_options.len() {
-                        self.current_timeline =
-                            self.timeline_picker_options[self.timeline_picker_selected].clone();
-                        self.statuses.clear();
-                        self.selected = 0;
-                        self.scroll = 0;
-                        self.timeline_message.clear();
-                        self.view = View::Timeline;
-                        self.load_timeline(false);
+                        self.switch_timeline(
+                            self.timeline_picker_options[self.timeline_picker_selected].clone(),
+                        );
                     }
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
@@ -656,313 +1800,4858 @@ impl App {
                 }
                 _ => {}
             },
+            View::ListPicker => self.handle_list_picker_key(code),
+            View::Debug => {
+                if matches!(code, KeyCode::Esc | KeyCode::F(12)) {
+                    self.view = self.debug_return_to;
+                }
+            }
+            _ => {
+                if !self.handle_prompt_key(code) {
+                    if let Some(action) = self.key_action(key) {
+                        quit = self.run_action(action);
+                    }
+                }
+            }
         }
         Ok(quit)
     }
 
-    /// append: false = refresh from top (replace); true = load next page (append).
-    fn load_timeline(&mut self, append: bool) {
-        if let Some(ref client) = self.client {
-            self.loading = true;
-            self.timeline_message.clear();
-            let max_id_str = if append && !self.statuses.is_empty() {
-                self.statuses.last().map(|s| s.id.clone())
-            } else {
-                None
-            };
-            let max_id = max_id_str.as_deref();
-            let result = match &self.current_timeline {
-                TimelineSelection::Home => self.runtime.block_on(client.get_timeline_home(max_id)),
-                TimelineSelection::Local => {
-                    self.runtime.block_on(client.get_timeline_local(max_id))
+    /// Feed a key press to the current view's bindings. Returns the action once a whole
+    /// sequence is typed; a key that ends no sequence starts over on its own. r[keys.sequence]
+    fn key_action(&mut self, event: KeyEvent) -> Option<Action> {
+        let view = self.view.key_view()?;
+        let key = Key::from_event(event);
+        loop {
+            self.key_pending.push(key);
+            match self.keymap.lookup(view, &self.key_pending) {
+                Lookup::Pending => return None,
+                Lookup::Action(action) => {
+                    self.key_pending.clear();
+                    return Some(action);
                 }
-                TimelineSelection::Public => {
-                    self.runtime.block_on(client.get_timeline_public(max_id))
-                }
-                TimelineSelection::List { id, .. } => {
-                    self.runtime.block_on(client.get_timeline_list(id, max_id))
-                }
-            };
-            match result {
-                Ok(mut new_statuses) => {
-                    if max_id.is_some() {
-                        self.statuses.append(&mut new_statuses);
-                    } else {
-                        self.statuses = new_statuses;
+                Lookup::None => {
+                    let retry = self.key_pending.len() > 1;
+                    self.key_pending.clear();
+                    if !retry {
+                        return None;
                     }
                 }
-                Err(MastotuiError::NotAuthenticated) => {
-                    self.client = None;
-                    self.view = View::Login;
-                    self.login_message = "Session expired. Please log in again.".to_string();
-                    if self.config.is_some() {
-                        let _ = self.start_login_flow();
-                    }
+            }
+        }
+    }
+
+    /// Keys for a form, prompt or confirmation open over a configurable view, which take
+    /// keys as they are. Returns false when none is open, leaving the key to the keymap.
+    fn handle_prompt_key(&mut self, code: KeyCode) -> bool {
+        match self.view {
+            View::ScheduledPosts => self.handle_scheduled_key(code),
+            View::Filters => self.handle_filters_key(code),
+            View::FilterKeywords => self.handle_filter_keywords_key(code),
+            View::Lists => self.handle_lists_key(code),
+            View::ListMembers => self.handle_list_members_key(code),
+            View::Follows => self.handle_follows_key(code),
+            View::Moderation => self.handle_moderation_key(code),
+            View::Report => self.handle_report_key(code),
+            _ => false,
+        }
+    }
+
+    /// Do what a bound key asks for in the current view. Returns true to quit.
+    fn run_action(&mut self, action: Action) -> bool {
+        match (self.view, action) {
+            // r[keys.help]
+            (_, Action::Help) => self.help_open = true,
+            (_, Action::Palette) => self.open_palette(),
+            (View::ScheduledPosts, _) => self.scheduled_action(action),
+            (View::ThreadPreview, _) => self.thread_preview_action(action),
+            (View::Conversations, _) => self.conversations_action(action),
+            (View::ConversationThread, _) => self.conversation_thread_action(action),
+            (View::Filters, _) => self.filters_action(action),
+            (View::FilterKeywords, _) => self.filter_keywords_action(action),
+            (View::Lists, _) => self.lists_action(action),
+            (View::ListMembers, _) => self.list_members_action(action),
+            (View::Follows, _) => self.follows_action(action),
+            (View::FollowRequests, _) => self.follow_requests_action(action),
+            (View::Moderation, _) => self.moderation_action(action),
+            (View::Report, _) => self.report_action(action),
+            (View::Explore, _) => self.explore_action(action),
+            (View::Outbox, _) => self.outbox_action(action),
+            _ => return self.timeline_action(action),
+        }
+        false
+    }
+
+    /// Actions of the timeline, toot and instance views. Returns true to quit.
+    fn timeline_action(&mut self, action: Action) -> bool {
+        let logged_in = self.client.is_some();
+        match action {
+            Action::Quit => return true,
+            Action::CycleLayout => self.set_layout(self.layout.next()),
+            Action::NextPane => return self.cycle_pane(true),
+            Action::PreviousPane => return self.cycle_pane(false),
+            Action::Back if self.view == View::TootDetail => {
+                self.view = self.detail_return_to;
+                self.detail_message.clear();
+            }
+            Action::Back => self.view = self.return_to_view,
+            Action::Up if self.selected > 0 => {
+                self.selected -= 1;
+                if self.selected < self.scroll {
+                    self.scroll = self.selected;
                 }
-                Err(e) => {
-                    self.timeline_message = format!("Failed to load timeline: {e}");
+                self.note_read_position();
+            }
+            Action::Down if self.selected + 1 < self.timeline_visible.len() => {
+                self.selected += 1;
+                if self.selected >= self.scroll + self.timeline_visible_rows {
+                    self.scroll = (self.selected + 1).saturating_sub(self.timeline_visible_rows);
                 }
+                self.note_read_position();
             }
-            self.loading = false;
-        } else if let Some(ref url) = self.anonymous_instance_url {
-            self.loading = true;
-            self.timeline_message.clear();
-            let max_id = if append && !self.statuses.is_empty() {
-                self.statuses.last().map(|s| s.id.as_str())
-            } else {
-                None
-            };
-            match self.runtime.block_on(get_public_timeline(url, max_id)) {
-                Ok(mut new_statuses) => {
-                    if max_id.is_some() {
-                        self.statuses.append(&mut new_statuses);
-                    } else {
-                        self.statuses = new_statuses;
-                    }
+            Action::Top => {
+                self.selected = 0;
+                self.scroll = 0;
+                self.note_read_position();
+            }
+            Action::Bottom if !self.timeline_visible.is_empty() => {
+                self.selected = self.timeline_visible.len() - 1;
+                self.scroll = (self.selected + 1).saturating_sub(self.timeline_visible_rows);
+                self.note_read_position();
+            }
+            Action::Open => {
+                if let Some(s) = self.selected_status().cloned() {
+                    self.detail_status = Some(s);
+                    self.detail_message.clear();
+                    self.detail_revealed = false;
+                    self.detail_return_to = View::Timeline;
+                    self.view = View::TootDetail;
                 }
-                Err(e) => {
-                    self.timeline_message = format!("Failed to load timeline: {e}");
+            }
+            Action::Explore if logged_in || self.anonymous_instance.is_some() => {
+                self.open_explore(TrendsKind::Tags);
+            }
+            Action::Compose if logged_in => self.open_compose(None),
+            Action::Outbox if logged_in => self.open_outbox(),
+            Action::Scheduled if logged_in => self.open_scheduled_posts(),
+            Action::Conversations if logged_in => self.open_conversations(),
+            Action::Filters if logged_in => self.open_filters(),
+            Action::Lists if logged_in => self.open_lists(),
+            Action::Follows if logged_in => self.open_follows(FollowsTab::Following),
+            Action::FollowRequests if logged_in => self.open_follow_requests(),
+            Action::Moderation if logged_in => self.open_moderation(ModerationTab::Mutes),
+            Action::ToggleHidden => {
+                let anchor = self.selected_status().map(|s| s.id.clone());
+                self.show_hidden = !self.show_hidden;
+                self.rebuild_visible();
+                self.reselect(anchor.as_deref());
+            }
+            Action::Refresh => self.load_timeline(false),
+            Action::LoadMore => self.load_timeline(true),
+            Action::InstanceInfo => self.open_instance_info(self.view),
+            Action::PickTimeline => self.open_timeline_picker(),
+            Action::Reply if logged_in => {
+                if let Some(id) = self.detail_status.as_ref().map(|s| s.id.clone()) {
+                    self.open_compose(Some(id));
                 }
             }
-            self.loading = false;
+            Action::Boost if logged_in => self.toggle_detail(Toggle::Boost),
+            Action::Favourite if logged_in => self.toggle_detail(Toggle::Favourite),
+            Action::Bookmark if logged_in => self.toggle_detail(Toggle::Bookmark),
+            Action::Reveal => self.detail_revealed = !self.detail_revealed,
+            Action::AddToList if logged_in => self.open_list_picker(),
+            Action::Report if logged_in => {
+                if let Some(s) = self.detail_status.as_ref() {
+                    let shown = s.reblog.as_deref().unwrap_or(s).clone();
+                    self.open_report(shown.account.clone(), Some(shown));
+                }
+            }
+            Action::LogInOut if logged_in => self.log_out(),
+            Action::LogInOut => {
+                // r[instance.info.login]
+                self.view = View::Login;
+                self.login_message.clear();
+            }
+            // r[instance.info.browse]
+            Action::BrowseInstance => self.open_instance_picker(View::InstanceInfo),
+            _ => {}
         }
+        false
     }
 
-    /// Called each tick; fetches timeline when on home view with client or anonymous instance, not loading, empty statuses, no prior error.
-    pub fn ensure_timeline_loaded(&mut self) -> Result<()> {
-        let has_source = self.client.is_some() || self.anonymous_instance_url.is_some();
-        if self.view == View::Timeline
-            && has_source
-            && !self.loading
-            && self.statuses.is_empty()
-            && self.timeline_message.is_empty()
-        {
-            self.load_timeline(false);
-        }
-        Ok(())
+    /// Open the `:` palette with what the current view offers plus the commands that work
+    /// from any view. r[palette.open] r[palette.commands]
+    fn open_palette(&mut self) {
+        let mut commands: Vec<(Command, String)> = self
+            .view
+            .key_view()
+            .map(|view| {
+                self.keymap
+                    .actions(view)
+                    .filter(|a| !matches!(a, Action::Up | Action::Down | Action::Palette))
+                    .map(|a| (Command::Action(a), self.keymap.keys(view, a)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let offers_compose = commands
+            .iter()
+            .any(|(c, _)| *c == Command::Action(Action::Compose));
+        commands.extend(
+            self.global_commands()
+                .into_iter()
+                .filter(|c| !(offers_compose && *c == Command::Compose))
+                .map(|c| (c, String::new())),
+        );
+        self.palette = Some(Palette::new(commands));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use crossterm::event::KeyModifiers;
-
-    use super::*;
 
-    fn k(code: KeyCode) -> KeyEvent {
-        KeyEvent::new(code, KeyModifiers::empty())
+    /// Palette commands that do not depend on the view: the timelines and lists, searching
+    /// follows, a new toot and the account commands. Empty when not logged in.
+    fn global_commands(&mut self) -> Vec<Command> {
+        let Some(ref client) = self.client else {
+            return Vec::new();
+        };
+        if self.lists.is_empty() {
+            if let Ok(lists) = self.runtime.block_on(client.get_lists()) {
+                self.lists = lists;
+            }
+        }
+        [
+            TimelineSelection::Home,
+            TimelineSelection::Local,
+            TimelineSelection::Public,
+        ]
+        .into_iter()
+        .chain(self.lists.iter().map(|l| TimelineSelection::List {
+            id: l.id.clone(),
+            title: l.title.clone(),
+        }))
+        .map(Command::Timeline)
+        .chain([
+            Command::SearchFollows,
+            Command::Compose,
+            Command::LogOut,
+            Command::SwitchAccount,
+        ])
+        .collect()
     }
 
-    fn ctrl_i() -> KeyEvent {
-        KeyEvent::new(KeyCode::Char('i'), KeyModifiers::CONTROL)
+    /// Keys while the palette is open: type to narrow, ↑/↓ to pick, Enter runs, Esc closes.
+    /// Returns true to quit.
+    fn handle_palette_key(&mut self, code: KeyCode) -> bool {
+        let Some(palette) = self.palette.as_mut() else {
+            return false;
+        };
+        match code {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Enter => {
+                let command = palette.selected_command();
+                self.palette = None;
+                if let Some(command) = command {
+                    return self.run_command(command);
+                }
+            }
+            KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Down => {
+                palette.selected =
+                    (palette.selected + 1).min(palette.matches().len().saturating_sub(1));
+            }
+            KeyCode::Backspace => {
+                palette.input.pop();
+                palette.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                palette.input.push(c);
+                palette.selected = 0;
+            }
+            _ => {}
+        }
+        false
     }
 
-    // r[verify toot.post.validation]
-    #[test]
-    fn compose_rejects_over_char_limit() {
-        const LIMIT: usize = 500;
-        let over = "x".repeat(LIMIT + 1);
-        assert!(over.chars().count() > LIMIT);
+    /// Run a palette command. Returns true to quit. r[palette.commands]
+    fn run_command(&mut self, command: Command) -> bool {
+        match command {
+            Command::Action(action) => return self.run_action(action),
+            Command::Timeline(timeline) => self.switch_timeline(timeline),
+            Command::SearchFollows => {
+                self.open_follows(FollowsTab::Following);
+                self.follows.searching = true;
+            }
+            Command::Compose => {
+                if self.client.is_some() {
+                    self.open_compose(None);
+                }
+            }
+            Command::LogOut => self.log_out(),
+            Command::SwitchAccount => {
+                self.end_session("Enter the instance of the account to switch to.");
+                self.auth_url.clear();
+                self.pkce_verifier.clear();
+                self.login_code.clear();
+            }
+        }
+        false
     }
 
-    /// Condition under which `ensure_timeline_loaded` triggers a fetch. Must match `ensure_timeline_loaded()`.
-    #[allow(clippy::fn_params_excessive_bools)]
-    fn should_auto_fetch_timeline(
-        view: View,
-        client_is_some: bool,
-        loading: bool,
-        statuses_empty: bool,
-        timeline_message_empty: bool,
-    ) -> bool {
-        view == View::Timeline
-            && client_is_some
-            && !loading
-            && statuses_empty
-            && timeline_message_empty
+    /// Show `timeline` from the top. r[timeline.select.submit]
+    fn switch_timeline(&mut self, timeline: TimelineSelection) {
+        self.current_timeline = timeline;
+        self.statuses.clear();
+        self.timeline_visible.clear();
+        self.selected = 0;
+        self.scroll = 0;
+        self.timeline_message.clear();
+        self.view = View::Timeline;
+        self.load_timeline(false);
     }
 
-    #[test]
-    fn auto_fetch_timeline_requires_not_loading() {
-        assert!(!should_auto_fetch_timeline(
-            View::Timeline,
-            true,
-            true,
-            true,
-            true
-        ));
+    /// Forget the stored token and start a new login on the same instance.
+    /// r[instance.info.logout]
+    fn log_out(&mut self) {
+        if let Ok(host) = instance_host_from_url(&self.instance_url) {
+            let _ = delete_access_token(&host);
+        }
+        self.end_session("Logged out.");
+        if self.config.is_some() && !self.instance_url.is_empty() {
+            let _ = self.start_login_flow();
+        }
     }
 
-    #[test]
-    fn auto_fetch_timeline_requires_empty_statuses() {
-        assert!(!should_auto_fetch_timeline(
-            View::Timeline,
-            true,
-            false,
-            false,
-            true
-        ));
+    /// Drop the client and its timeline and show the login screen with `message`.
+    fn end_session(&mut self, message: &str) {
+        self.client = None;
+        self.statuses.clear();
+        self.timeline_visible.clear();
+        self.selected = 0;
+        self.scroll = 0;
+        self.timeline_message.clear();
+        self.login_message = message.to_string();
+        self.view = View::Login;
     }
 
+    /// Mouse capture is wanted: on unless `mouse = false`, and off on the login screen so the
+    /// authorization URL can be selected and copied. r[mouse.capture]
+    #[must_use]
+    pub fn wants_mouse(&self) -> bool {
+        self.mouse && self.view != View::Login
+    }
+
+    /// Clicks and the scroll wheel. Returns true to quit.
+    /// r[mouse.select] r[mouse.scroll] r[mouse.hints] r[mouse.links]
+    pub fn handle_mouse(&mut self, event: MouseEvent) -> Result<bool> {
+        match event.kind {
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                if self.help_open || self.palette.is_some() {
+                    return Ok(false);
+                }
+                let up = event.kind == MouseEventKind::ScrollUp;
+                self.last_click = None;
+                if self.typing() {
+                    return Ok(false);
+                }
+                let code = if up { KeyCode::Up } else { KeyCode::Down };
+                if let Some(view) = self.view.key_view() {
+                    let action = if up { Action::Up } else { Action::Down };
+                    // A confirmation over the view takes the wheel like an arrow key.
+                    if !self.handle_prompt_key(code)
+                        && self.keymap.actions(view).any(|a| a == action)
+                    {
+                        return Ok(self.run_action(action));
+                    }
+                } else {
+                    return self.handle_key(KeyEvent::from(code));
+                }
+                Ok(false)
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Like a key press, a click closes an overlay and does nothing else.
+                if self.help_open || self.palette.is_some() {
+                    self.help_open = false;
+                    self.palette = None;
+                    return Ok(false);
+                }
+                Ok(self
+                    .hits
+                    .at(event.column, event.row)
+                    .cloned()
+                    .is_some_and(|hit| self.click(hit)))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// A click on `hit`: select a row (open it on a second click), run a hint's action or open
+    /// a link. Returns true to quit.
+    fn click(&mut self, hit: Hit) -> bool {
+        match hit {
+            Hit::Row(row) => {
+                // The timeline beside an open toot (split layout) takes the focus back.
+                if self.layout == LayoutMode::Split && self.view == View::TootDetail {
+                    self.view = View::Timeline;
+                }
+                let now = Instant::now();
+                let double = self.last_click.is_some_and(|(at, last)| {
+                    last == row && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                });
+                self.selected = row;
+                if double {
+                    self.last_click = None;
+                    return self.run_action(Action::Open);
+                }
+                self.last_click = Some((now, row));
+            }
+            Hit::Action(action) => return self.run_action(action),
+            Hit::Link(url) => self.open_link(&url),
+            Hit::Column(i) => self.focus_column(i),
+        }
+        false
+    }
+
+    /// Open a link from a toot in the system browser. r[mouse.links]
+    fn open_link(&mut self, url: &str) {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else if cfg!(windows) {
+            "explorer"
+        } else {
+            "xdg-open"
+        };
+        let spawned = std::process::Command::new(opener)
+            .arg(url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        self.detail_message = match spawned {
+            Ok(mut child) => {
+                // Reap the opener when it exits; browsers usually detach from it at once.
+                std::thread::spawn(move || child.wait());
+                format!("Opened {url}")
+            }
+            Err(e) => format!("Could not open {url}: {e}"),
+        };
+    }
+
+    /// Switch layout. Entering columns puts the current timeline in its column (or in front
+    /// of the others); leaving keeps the focused column's timeline. r[layout.split]
+    /// r[layout.columns]
+    fn set_layout(&mut self, layout: LayoutMode) {
+        if self.layout == LayoutMode::Columns {
+            self.columns.clear();
+            self.focused_column = 0;
+        }
+        self.layout = layout;
+        if layout == LayoutMode::Columns {
+            let mut timelines = self.column_timelines();
+            self.focused_column = timelines
+                .iter()
+                .position(|t| *t == self.current_timeline)
+                .unwrap_or_else(|| {
+                    timelines.insert(0, self.current_timeline.clone());
+                    0
+                });
+            self.columns = timelines.into_iter().map(TimelineColumn::new).collect();
+        }
+    }
+
+    /// Timelines for the columns: `columns` from config, else Home, Local and Public; only
+    /// the current one when browsing anonymously. Lists are looked up by title.
+    fn column_timelines(&mut self) -> Vec<TimelineSelection> {
+        let Some(ref client) = self.client else {
+            return vec![self.current_timeline.clone()];
+        };
+        let names = self
+            .config
+            .as_ref()
+            .map(|c| c.columns.clone())
+            .unwrap_or_default();
+        if names.is_empty() {
+            return vec![
+                TimelineSelection::Home,
+                TimelineSelection::Local,
+                TimelineSelection::Public,
+            ];
+        }
+        if self.lists.is_empty() && names.iter().any(|n| n.starts_with("list:")) {
+            if let Ok(lists) = self.runtime.block_on(client.get_lists()) {
+                self.lists = lists;
+            }
+        }
+        names
+            .iter()
+            .filter_map(|name| TimelineSelection::from_column_name(name, &self.lists))
+            .collect()
+    }
+
+    /// Exchange the timeline fields with column `i`'s slot. r[layout.columns]
+    fn swap_column(&mut self, i: usize) {
+        let Some(column) = self.columns.get_mut(i) else {
+            return;
+        };
+        std::mem::swap(&mut self.current_timeline, &mut column.timeline);
+        std::mem::swap(&mut self.statuses, &mut column.statuses);
+        std::mem::swap(&mut self.timeline_visible, &mut column.visible);
+        std::mem::swap(&mut self.timeline_hidden, &mut column.hidden);
+        std::mem::swap(&mut self.selected, &mut column.selected);
+        std::mem::swap(&mut self.scroll, &mut column.scroll);
+        std::mem::swap(&mut self.timeline_message, &mut column.message);
+        std::mem::swap(&mut self.loading, &mut column.loading);
+        std::mem::swap(&mut self.timeline_cached_at, &mut column.cached_at);
+        std::mem::swap(&mut self.offline, &mut column.offline);
+        std::mem::swap(&mut self.refresh_pending, &mut column.refresh_pending);
+    }
+
+    /// Focus column `i`: keys, the footer and loading then apply to its timeline.
+    /// r[layout.columns]
+    fn focus_column(&mut self, i: usize) {
+        let from = self.focused_column;
+        if self.layout != LayoutMode::Columns || i >= self.columns.len() || i == from {
+            return;
+        }
+        self.swap_column(from);
+        self.columns[from].loaded = true;
+        self.swap_column(i);
+        self.focused_column = i;
+        self.view = View::Timeline;
+    }
+
+    /// Load column `i` while another one keeps the focus. r[layout.columns]
+    fn load_column(&mut self, i: usize) {
+        let focused = self.focused_column;
+        self.swap_column(focused);
+        self.swap_column(i);
+        self.load_timeline(false);
+        self.swap_column(i);
+        self.swap_column(focused);
+        self.columns[i].loaded = true;
+    }
+
+    /// Tab and Shift+Tab: the next or previous column, or in the split layout the other
+    /// pane (opening the selected toot on the right). Returns true to quit.
+    /// r[layout.split] r[layout.columns]
+    fn cycle_pane(&mut self, forward: bool) -> bool {
+        match self.layout {
+            LayoutMode::Columns if !self.columns.is_empty() => {
+                let count = self.columns.len();
+                let step = if forward { 1 } else { count - 1 };
+                self.focus_column((self.focused_column + step) % count);
+            }
+            LayoutMode::Split if self.view == View::Timeline => {
+                return self.run_action(Action::Open);
+            }
+            LayoutMode::Split if self.view == View::TootDetail => self.view = View::Timeline,
+            _ => {}
+        }
+        false
+    }
+
+    /// Shown statuses of the current timeline with why each is hidden, for drawing.
+    fn timeline_rows(&self) -> TimelineRows<'_> {
+        self.timeline_visible
+            .iter()
+            .filter_map(|&i| {
+                let reason = self.timeline_hidden.get(i).and_then(Option::as_deref);
+                self.statuses.get(i).map(|s| (s, reason))
+            })
+            .collect()
+    }
+
+    /// Draw the timeline view in the current layout; `detail` is the toot open beside it in
+    /// the split layout. r[layout.split] r[layout.columns]
+    fn draw_timelines(
+        &self,
+        frame: &mut Frame,
+        detail: Option<(&crate::api::Status, bool, &str)>,
+    ) -> HitMap {
+        let label = self.timeline_label();
+        let rows = self.timeline_rows();
+        let rate_limit = self.client.as_ref().map_or_else(
+            || {
+                self.anonymous_instance
+                    .as_ref()
+                    .and_then(PublicClient::rate_limit)
+            },
+            MastodonClient::rate_limit,
+        );
+        let pane = TimelinePane {
+            label: &label,
+            rows: &rows,
+            selected: self.selected,
+            scroll: self.scroll,
+            unread: self.unread_count(),
+            loading: self.loading,
+            message: &self.timeline_message,
+        };
+        match self.layout {
+            LayoutMode::Single => draw_timeline(
+                frame,
+                &self.keymap,
+                &label,
+                &rows,
+                self.selected,
+                self.scroll,
+                pane.unread,
+                rate_limit.as_ref(),
+                self.loading,
+                &self.timeline_message,
+            ),
+            LayoutMode::Split => {
+                let preview = rows.get(self.selected).map(|&(s, _)| (s, false, ""));
+                let focused = detail.is_some();
+                draw_split(
+                    frame,
+                    &self.keymap,
+                    &pane,
+                    detail.or(preview),
+                    focused,
+                    rate_limit.as_ref(),
+                )
+            }
+            LayoutMode::Columns => {
+                let others: Vec<(String, TimelineRows)> = self
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        let label =
+                            cache_state_label(c.timeline.label(), c.cached_at.as_ref(), c.offline);
+                        (label, c.rows())
+                    })
+                    .collect();
+                let panes: Vec<TimelinePane> = self
+                    .columns
+                    .iter()
+                    .zip(&others)
+                    .enumerate()
+                    .map(|(i, (column, (label, rows)))| {
+                        if i == self.focused_column {
+                            pane
+                        } else {
+                            TimelinePane {
+                                label,
+                                rows,
+                                selected: column.selected,
+                                scroll: column.scroll,
+                                unread: 0,
+                                loading: column.loading,
+                                message: &column.message,
+                            }
+                        }
+                    })
+                    .collect();
+                draw_timeline_columns(
+                    frame,
+                    &self.keymap,
+                    &panes,
+                    self.focused_column,
+                    rate_limit.as_ref(),
+                )
+            }
+        }
+    }
+
+    /// The current view takes typed text, so `?` is a character rather than help.
+    fn typing(&self) -> bool {
+        match self.view {
+            View::Login | View::Compose | View::InstancePicker => true,
+            View::Filters => self.filter_form.is_some(),
+            View::FilterKeywords => self.filter_keyword_input.is_some(),
+            View::Lists => self.list_form.is_some(),
+            View::ListMembers => self.list_member_input.is_some(),
+            View::Follows => self.follows.searching,
+            View::Moderation => self.moderation.domain_input.is_some(),
+            View::ScheduledPosts => self.scheduled_reschedule_input.is_some(),
+            View::Report => self
+                .report
+                .as_ref()
+                .is_some_and(|r| r.step == ReportStep::Comment),
+            _ => false,
+        }
+    }
+
+    /// Title and rows of the help overlay for the current view: its keymap when the keys are
+    /// configurable, else the fixed keys. r[keys.help]
+    fn help_rows(&self) -> (&'static str, Vec<(String, &'static str)>) {
+        let (title, mut rows) = self.view.key_view().map_or_else(
+            || {
+                let (title, fixed) = fixed_keys(self.view);
+                let rows = fixed.iter().map(|(k, d)| ((*k).to_string(), *d)).collect();
+                (title, rows)
+            },
+            |view| (view.title(), self.keymap.help(view)),
+        );
+        rows.extend(
+            [
+                ("f1", "Show these keys"),
+                ("f12", "Last API error"),
+                ("ctrl-q", "Quit from any screen"),
+            ]
+            .map(|(k, d)| (k.to_string(), d)),
+        );
+        (title, rows)
+    }
+
+    /// Validate and send the compose buffer, immediately or at `compose_schedule`.
+    /// r[toot.post.validation] r[toot.schedule.validation]
+    fn submit_compose(&mut self) {
+        let text = self.compose_buffer.trim().to_string();
+        if text.is_empty() {
+            self.compose_error = "Cannot post empty toot.".to_string();
+            return;
+        }
+        if has_separator(&text) {
+            self.open_thread_preview();
+            return;
+        }
+        if text.chars().count() > CHAR_LIMIT {
+            self.compose_error =
+                format!("Over {CHAR_LIMIT} character limit. [Ctrl+t] splits it into a thread.");
+            return;
+        }
+        let scheduled_at = if self.compose_schedule.trim().is_empty() {
+            None
+        } else {
+            match parse_schedule(&self.compose_schedule, chrono::Local::now()) {
+                Ok(when) => Some(to_api_timestamp(when)),
+                Err(e) => {
+                    self.compose_error = e.to_string();
+                    return;
+                }
+            }
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let reply_to = self.compose_reply_to_id.clone();
+        let visibility = self.compose_visibility;
+        if let Some(at) = scheduled_at {
+            // r[toot.schedule.compose]
+            match self.runtime.block_on(client.schedule_status(
+                &text,
+                reply_to.as_deref(),
+                visibility,
+                &at,
+            )) {
+                Ok(scheduled) => {
+                    self.compose_buffer.clear();
+                    self.compose_reply_to_id = None;
+                    self.compose_schedule.clear();
+                    self.compose_visibility = None;
+                    self.compose_error.clear();
+                    self.open_scheduled_posts();
+                    self.scheduled_selected = self
+                        .scheduled_statuses
+                        .iter()
+                        .position(|s| s.id == scheduled.id)
+                        .unwrap_or(0);
+                    self.scheduled_message =
+                        format!("Scheduled for {}.", format_local(&scheduled.scheduled_at));
+                }
+                Err(e) => self.compose_error = format!("Schedule failed: {e}"),
+            }
+            return;
+        }
+        // r[outbox.queue]
+        let action = OutboxAction::Post {
+            text,
+            in_reply_to_id: reply_to,
+            visibility,
+        };
+        match self.enqueue(action) {
+            SendOutcome::Sent => self.finish_compose(),
+            SendOutcome::Queued(e) => {
+                self.finish_compose();
+                let message = format!("Post queued ({e}); it is sent once the server is back. [o]");
+                if self.view == View::ConversationThread {
+                    self.conversations_message = message;
+                } else {
+                    self.timeline_message = message;
+                }
+            }
+            SendOutcome::Rejected(e) => self.compose_error = format!("Post failed: {e}"),
+        }
+    }
+
+    /// Outbox file name: the logged-in instance's host.
+    fn outbox_key(&self) -> Option<String> {
+        self.client.as_ref()?;
+        let url = self.config.as_ref().map(|c| c.instance_url.as_str())?;
+        instance_host_from_url(url)
+            .ok()
+            .map(|host| TimelineCache::account_key(&host, false))
+    }
+
+    /// The logged-in account's outbox, loaded from disk when the account changed.
+    fn outbox(&mut self) -> Option<&mut Outbox> {
+        let key = self.outbox_key()?;
+        if self.outbox.as_ref().map_or(true, |(k, _)| *k != key) {
+            let outbox = Outbox::load(&key).unwrap_or_else(|e| {
+                self.timeline_message = e.to_string();
+                Outbox::default()
+            });
+            self.outbox = Some((key, outbox));
+        }
+        self.outbox.as_mut().map(|(_, outbox)| outbox)
+    }
+
+    /// Write the outbox to disk. Errors are ignored; the queue then only lasts the session.
+    fn save_outbox(&self) {
+        if let Some((ref key, ref outbox)) = self.outbox {
+            let _ = outbox.save(key);
+        }
+    }
+
+    /// Queue a write and send it now, after any earlier queued writes. A write the server
+    /// refuses straight away is dropped again so the caller can report it. r[outbox.queue]
+    fn enqueue(&mut self, action: OutboxAction) -> SendOutcome {
+        let Some(outbox) = self.outbox() else {
+            return SendOutcome::Rejected("Not logged in".to_string());
+        };
+        let key = outbox.push(action);
+        self.save_outbox();
+        let outcome = self.send_pending_through(&key);
+        if matches!(outcome, SendOutcome::Rejected(_)) {
+            if let Some(outbox) = self.outbox() {
+                outbox.remove(&key);
+            }
+            self.save_outbox();
+        }
+        outcome
+    }
+
+    /// Queue a write without sending it; `flush_outbox` sends it from the main loop once the
+    /// frame showing its optimistic effect has been drawn. False when not logged in.
+    /// r[outbox.queue] r[toot.optimistic]
+    fn queue(&mut self, action: OutboxAction) -> bool {
+        let Some(outbox) = self.outbox() else {
+            return false;
+        };
+        outbox.push(action);
+        self.save_outbox();
+        true
+    }
+
+    /// Send queued writes in order until `last` has been tried. Stops early when an earlier
+    /// one cannot be sent, since later ones (e.g. an unboost after a boost) must wait for it.
+    fn send_pending_through(&mut self, last: &str) -> SendOutcome {
+        loop {
+            let Some(key) = self
+                .outbox
+                .as_ref()
+                .and_then(|(_, o)| o.items.iter().find(|i| !i.failed))
+                .map(|i| i.key.clone())
+            else {
+                return SendOutcome::Queued("not in the outbox".to_string());
+            };
+            let outcome = self.send_outbox_item(&key);
+            if key == last || matches!(outcome, SendOutcome::Queued(_)) {
+                return outcome;
+            }
+        }
+    }
+
+    /// Send one queued write with its idempotency key. Success drops it and applies the
+    /// server's copy; failure is recorded for a retry, or undoes the optimistic change if
+    /// the server refused it. r[outbox.retry] r[outbox.reconcile]
+    fn send_outbox_item(&mut self, key: &str) -> SendOutcome {
+        let Some(ref client) = self.client else {
+            return SendOutcome::Queued("Not logged in".to_string());
+        };
+        let Some(item) = self.outbox.as_ref().and_then(|(_, o)| o.get(key)).cloned() else {
+            return SendOutcome::Queued("not in the outbox".to_string());
+        };
+        let result = self
+            .runtime
+            .block_on(client.perform(&item.action, &item.key));
+        let outcome = match result {
+            Ok(performed) => {
+                if let Some((_, ref mut outbox)) = self.outbox {
+                    outbox.remove(key);
+                }
+                self.reconcile(performed);
+                SendOutcome::Sent
+            }
+            Err(e) => {
+                let refused = self.outbox.as_mut().is_some_and(|(_, outbox)| {
+                    outbox.record_failure(key, &e, chrono::Utc::now());
+                    outbox.get(key).is_some_and(|i| i.failed)
+                });
+                if refused {
+                    self.apply_optimistic(&item.action, false);
+                    SendOutcome::Rejected(e.to_string())
+                } else {
+                    SendOutcome::Queued(e.to_string())
+                }
+            }
+        };
+        self.save_outbox();
+        outcome
+    }
+
+    /// Send the oldest queued write once its retry time has come; called from the main
+    /// loop. r[outbox.retry]
+    pub fn flush_outbox(&mut self) {
+        let Some(key) = self
+            .outbox()
+            .and_then(|o| o.next_due(chrono::Utc::now()))
+            .map(|i| i.key.clone())
+        else {
+            return;
+        };
+        let described = self
+            .outbox
+            .as_ref()
+            .and_then(|(_, o)| o.get(&key))
+            .map(|i| i.action.describe())
+            .unwrap_or_default();
+        match self.send_outbox_item(&key) {
+            SendOutcome::Sent => self.outbox_message = format!("Sent: {described}"),
+            SendOutcome::Rejected(e) => {
+                self.outbox_notice(format!("{described} was refused: {e} [o]"));
+            }
+            SendOutcome::Queued(e) => {
+                let first_failure = self
+                    .outbox
+                    .as_ref()
+                    .and_then(|(_, o)| o.get(&key))
+                    .is_some_and(|i| i.attempts == 1);
+                if first_failure {
+                    self.outbox_notice(format!("{described} queued ({e}); retrying."));
+                }
+            }
+        }
+    }
+
+    /// Show what happened to a queued write on the message line of the view the user is in.
+    fn outbox_notice(&mut self, message: String) {
+        match self.view {
+            View::TootDetail => self.detail_message = message,
+            View::Follows => self.follows.message = message,
+            View::ConversationThread => self.conversations_message = message,
+            _ => self.timeline_message = message,
+        }
+    }
+
+    /// Every loaded copy of a status, in the timeline, the other columns and any open view.
+    /// Boosts are returned whole; the boosted status is in `reblog`.
+    fn status_copies_mut(&mut self) -> impl Iterator<Item = &mut crate::api::Status> {
+        let trending = match self.explore.trends {
+            Some(Trends::Statuses(ref mut statuses)) => Some(statuses),
+            _ => None,
+        };
+        self.statuses
+            .iter_mut()
+            .chain(self.detail_status.as_mut())
+            .chain(self.conversation_statuses.iter_mut())
+            .chain(trending.into_iter().flatten())
+            .chain(self.report.iter_mut().flat_map(|r| r.statuses.iter_mut()))
+            .chain(self.columns.iter_mut().flat_map(|c| c.statuses.iter_mut()))
+    }
+
+    /// Show a write's effect before the server confirms it, or take it back after the
+    /// server refused it (`applied` false). Toggles change every copy of the status and
+    /// its count. r[outbox.reconcile] r[toot.optimistic]
+    fn apply_optimistic(&mut self, action: &OutboxAction, applied: bool) {
+        match action {
+            OutboxAction::Boost { status_id, on }
+            | OutboxAction::Favourite { status_id, on }
+            | OutboxAction::Bookmark { status_id, on } => {
+                let state = *on == applied;
+                let set = |s: &mut crate::api::Status| {
+                    if s.id != *status_id {
+                        return;
+                    }
+                    let (flag, count) = match action {
+                        OutboxAction::Boost { .. } => {
+                            (&mut s.reblogged, Some(&mut s.reblogs_count))
+                        }
+                        OutboxAction::Favourite { .. } => {
+                            (&mut s.favourited, Some(&mut s.favourites_count))
+                        }
+                        _ => (&mut s.bookmarked, None),
+                    };
+                    if flag.unwrap_or(false) == state {
+                        return;
+                    }
+                    *flag = Some(state);
+                    if let Some(count) = count {
+                        *count = if state {
+                            *count + 1
+                        } else {
+                            count.saturating_sub(1)
+                        };
+                    }
+                };
+                for copy in self.status_copies_mut() {
+                    set(copy);
+                    if let Some(reblog) = copy.reblog.as_mut() {
+                        set(reblog);
+                    }
+                }
+            }
+            OutboxAction::Follow { account_id, on, .. } => {
+                if let Some(rel) = self.follows.relationships.get_mut(account_id) {
+                    rel.following = *on == applied;
+                    rel.requested = false;
+                }
+            }
+            OutboxAction::Post { .. } => {}
+        }
+    }
+
+    /// Replace local copies with what the server returned for a sent write.
+    /// r[outbox.reconcile]
+    fn reconcile(&mut self, performed: Performed) {
+        match performed {
+            Performed::Status(status) => {
+                // Boosting returns the new boost, wrapping the updated original.
+                let mut status = *status;
+                if let Some(original) = status.reblog.take() {
+                    status = *original;
+                }
+                for s in self.status_copies_mut() {
+                    if s.id == status.id {
+                        *s = status.clone();
+                    } else if s.reblog.as_ref().is_some_and(|r| r.id == status.id) {
+                        s.reblog = Some(Box::new(status.clone()));
+                    }
+                }
+            }
+            Performed::Relationship(rel) => {
+                self.follows.relationships.insert(rel.id.clone(), rel);
+            }
+        }
+    }
+
+    /// Boost, favourite or bookmark the shown toot, or undo it, through the outbox: the
+    /// change shows at once and is sent after the next frame. r[toot.boost.toggle] r[toot.favourite.toggle] r[outbox.queue]
+    fn toggle_detail(&mut self, toggle: Toggle) {
+        let Some(shown) = self
+            .detail_status
+            .as_ref()
+            .map(|s| s.reblog.as_deref().unwrap_or(s))
+        else {
+            return;
+        };
+        let status_id = shown.id.clone();
+        let (action, done) = match toggle {
+            Toggle::Boost => {
+                let on = !shown.reblogged.unwrap_or(false);
+                let done = if on { "Boosted." } else { "Unboosted." };
+                (OutboxAction::Boost { status_id, on }, done)
+            }
+            Toggle::Favourite => {
+                let on = !shown.favourited.unwrap_or(false);
+                let done = if on { "Favourited." } else { "Unfavourited." };
+                (OutboxAction::Favourite { status_id, on }, done)
+            }
+            Toggle::Bookmark => {
+                let on = !shown.bookmarked.unwrap_or(false);
+                let done = if on {
+                    "Bookmarked."
+                } else {
+                    "Bookmark removed."
+                };
+                (OutboxAction::Bookmark { status_id, on }, done)
+            }
+        };
+        self.apply_optimistic(&action, true);
+        if self.queue(action.clone()) {
+            self.detail_message = done.to_string();
+        } else {
+            self.apply_optimistic(&action, false);
+            self.detail_message = "Error: Not logged in".to_string();
+        }
+    }
+
+    /// Follow or unfollow the selected account in the follows browser through the outbox.
+    /// r[outbox.queue]
+    fn toggle_follow(&mut self) {
+        let Some(account) = self.follows.selected_account().cloned() else {
+            return;
+        };
+        let following = self
+            .follows
+            .relationships
+            .get(&account.id)
+            .map_or(self.follows.tab == FollowsTab::Following, |r| {
+                r.following || r.requested
+            });
+        let action = OutboxAction::Follow {
+            account_id: account.id.clone(),
+            handle: account.handle().to_string(),
+            on: !following,
+        };
+        self.apply_optimistic(&action, true);
+        let described = action.describe();
+        if self.queue(action.clone()) {
+            self.follows.message = format!("{described}: done.");
+        } else {
+            self.apply_optimistic(&action, false);
+            self.follows.message = format!("{described} failed: Not logged in");
+        }
+    }
+
+    /// Open the outbox view (press o). r[outbox.view]
+    fn open_outbox(&mut self) {
+        self.outbox();
+        self.outbox_selected = 0;
+        self.outbox_message.clear();
+        self.view = View::Outbox;
+    }
+
+    /// Actions of the outbox view: send sends the selected write now (also after a refusal),
+    /// discard drops it. r[outbox.view]
+    fn outbox_action(&mut self, action: Action) {
+        let len = self.outbox.as_ref().map_or(0, |(_, o)| o.items.len());
+        let selected = self
+            .outbox
+            .as_ref()
+            .and_then(|(_, o)| o.items.get(self.outbox_selected))
+            .map(|i| (i.key.clone(), i.action.clone()));
+        match action {
+            Action::Back => self.view = View::Timeline,
+            Action::Up => {
+                self.outbox_selected = self.outbox_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.outbox_selected = (self.outbox_selected + 1).min(len.saturating_sub(1));
+            }
+            Action::Send => {
+                let Some((key, action)) = selected else {
+                    return;
+                };
+                if let Some(outbox) = self.outbox() {
+                    outbox.retry_now(&key);
+                }
+                self.apply_optimistic(&action, true);
+                self.outbox_message = match self.send_pending_through(&key) {
+                    SendOutcome::Sent => format!("Sent: {}", action.describe()),
+                    SendOutcome::Queued(e) => format!("Still queued: {e}"),
+                    SendOutcome::Rejected(e) => format!("Refused: {e}"),
+                };
+            }
+            Action::Discard => {
+                let Some((key, action)) = selected else {
+                    return;
+                };
+                if let Some(outbox) = self.outbox() {
+                    outbox.remove(&key);
+                }
+                self.apply_optimistic(&action, false);
+                self.save_outbox();
+                self.outbox_message = format!("Discarded: {}", action.describe());
+            }
+            _ => return,
+        }
+        let len = self.outbox.as_ref().map_or(0, |(_, o)| o.items.len());
+        self.outbox_selected = self.outbox_selected.min(len.saturating_sub(1));
+    }
+
+    /// Split the Compose buffer and show the preview (Ctrl+t, or Enter with `---` lines).
+    /// r[toot.thread.split] r[toot.thread.preview]
+    fn open_thread_preview(&mut self) {
+        if !self.compose_schedule.trim().is_empty() {
+            self.compose_error = "Threads cannot be scheduled; clear the schedule field.".into();
+            return;
+        }
+        let parts = split_thread(&self.compose_buffer, CHAR_LIMIT);
+        if parts.len() < 2 {
+            self.compose_error = "Fits in a single post; press [Enter] to post.".to_string();
+            return;
+        }
+        self.compose_error.clear();
+        self.thread_keys = parts.iter().map(|_| idempotency_key()).collect();
+        self.thread_parts = parts;
+        self.thread_posted_ids.clear();
+        self.thread_scroll = 0;
+        self.thread_message.clear();
+        self.view = View::ThreadPreview;
+    }
+
+    /// Actions of the thread preview. r[toot.thread.preview]
+    fn thread_preview_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.thread_message.clear();
+                if self.thread_posted_ids.is_empty() {
+                    self.view = View::Compose;
+                } else {
+                    // Part of the thread is already live; abandon the rest.
+                    self.finish_thread();
+                }
+            }
+            Action::Send => self.post_thread(),
+            Action::Up => self.thread_scroll = self.thread_scroll.saturating_sub(1),
+            Action::Down => {
+                self.thread_scroll =
+                    (self.thread_scroll + 1).min(self.thread_parts.len().saturating_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    /// Post the remaining thread parts in order, each replying to the previous one.
+    /// On failure stop and keep progress so Enter resumes at the failed part.
+    /// r[toot.thread.post] r[toot.thread.resume]
+    fn post_thread(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let total = self.thread_parts.len();
+        for i in self.thread_posted_ids.len()..total {
+            let reply_to = self
+                .thread_posted_ids
+                .last()
+                .or(self.compose_reply_to_id.as_ref())
+                .cloned();
+            let action = OutboxAction::Post {
+                text: self.thread_parts[i].clone(),
+                in_reply_to_id: reply_to,
+                visibility: self.compose_visibility,
+            };
+            match self
+                .runtime
+                .block_on(client.perform(&action, &self.thread_keys[i]))
+            {
+                Ok(Performed::Status(posted)) => self.thread_posted_ids.push(posted.id),
+                Ok(Performed::Relationship(_)) => unreachable!("posting returns a status"),
+                Err(e) => {
+                    self.thread_scroll = i;
+                    self.thread_message = format!(
+                        "Posted {i}/{total}; part {} failed: {e}. [Enter] resume",
+                        i + 1
+                    );
+                    return;
+                }
+            }
+        }
+        self.finish_thread();
+    }
+
+    /// Leave the thread preview and clear the draft (after posting, or abandoning a partial thread).
+    fn finish_thread(&mut self) {
+        self.thread_parts.clear();
+        self.thread_posted_ids.clear();
+        self.thread_keys.clear();
+        self.thread_message.clear();
+        self.finish_compose();
+    }
+
+    /// Open the filters view (press F) and fetch filters. r[filter.manage.list]
+    fn open_filters(&mut self) {
+        self.view = View::Filters;
+        self.filter_form = None;
+        self.filters_confirm_delete = false;
+        self.load_filters();
+    }
+
+    fn load_filters(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_filters()) {
+            Ok(filters) => {
+                self.filters = filters;
+                self.filters_selected = self
+                    .filters_selected
+                    .min(self.filters.len().saturating_sub(1));
+                self.filters_message.clear();
+            }
+            Err(e) => self.filters_message = format!("Failed to load filters: {e}"),
+        }
+    }
+
+    /// Keys for the create/edit form and the delete confirmation. Returns false when neither
+    /// is open. r[filter.manage.edit]
+    fn handle_filters_key(&mut self, code: KeyCode) -> bool {
+        if let Some(ref mut form) = self.filter_form {
+            match code {
+                KeyCode::Esc => {
+                    self.filter_form = None;
+                    self.filters_message.clear();
+                }
+                KeyCode::Enter => self.save_filter_form(),
+                KeyCode::Tab | KeyCode::Down => {
+                    form.field = (form.field + 1) % (FilterForm::ACTION_FIELD + 1);
+                }
+                KeyCode::BackTab | KeyCode::Up => {
+                    form.field = form
+                        .field
+                        .checked_sub(1)
+                        .unwrap_or(FilterForm::ACTION_FIELD);
+                }
+                KeyCode::Backspace if form.field == 0 => {
+                    form.title.pop();
+                }
+                KeyCode::Char(c) if form.field == 0 => form.title.push(c),
+                KeyCode::Char(' ') if form.field == FilterForm::ACTION_FIELD => {
+                    form.action = match form.action {
+                        FilterAction::Warn => FilterAction::Hide,
+                        FilterAction::Hide | FilterAction::Blur => FilterAction::Warn,
+                    };
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(on) = form.context.get_mut(form.field - 1) {
+                        *on = !*on;
+                    }
+                }
+                _ => {}
+            }
+            return true;
+        }
+        if self.filters_confirm_delete {
+            self.filters_confirm_delete = false;
+            self.filters_message.clear();
+            if code == KeyCode::Char('y') {
+                self.delete_selected_filter();
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Actions of the filters list. r[filter.manage.list]
+    fn filters_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Timeline;
+                self.filters_message.clear();
+            }
+            Action::Up => {
+                self.filters_selected = self.filters_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.filters_selected =
+                    (self.filters_selected + 1).min(self.filters.len().saturating_sub(1));
+            }
+            Action::New => self.filter_form = Some(FilterForm::new()),
+            Action::Edit => {
+                self.filter_form = self
+                    .filters
+                    .get(self.filters_selected)
+                    .map(FilterForm::from_filter);
+            }
+            Action::Delete if !self.filters.is_empty() => self.filters_confirm_delete = true,
+            Action::Open if !self.filters.is_empty() => {
+                self.filter_keywords_selected = 0;
+                self.filter_keyword_input = None;
+                self.filters_message.clear();
+                self.view = View::FilterKeywords;
+            }
+            Action::Refresh => self.load_filters(),
+            _ => {}
+        }
+    }
+
+    /// Create or update the filter in the form; new filters open their keyword list.
+    fn save_filter_form(&mut self) {
+        let Some(form) = self.filter_form.clone() else {
+            return;
+        };
+        let title = form.title.trim();
+        if title.is_empty() {
+            self.filters_message = "Filter title cannot be empty.".to_string();
+            return;
+        }
+        let context = form.selected_contexts();
+        if context.is_empty() {
+            self.filters_message = "Select at least one context.".to_string();
+            return;
+        }
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.save_filter(
+            form.id.as_deref(),
+            title,
+            &context,
+            form.action,
+        )) {
+            Ok(saved) => {
+                let id = saved.id.clone();
+                self.filter_form = None;
+                self.load_filters();
+                self.filters_selected = self.filters.iter().position(|f| f.id == id).unwrap_or(0);
+                if form.id.is_none() {
+                    // New filters have no keywords yet; go straight to adding one.
+                    self.view = View::FilterKeywords;
+                    self.filter_keywords_selected = 0;
+                    self.filter_keyword_input = Some((None, String::new()));
+                }
+                self.filters_message = format!("Saved filter \"{}\".", saved.title);
+            }
+            Err(e) => self.filters_message = format!("Save failed: {e}"),
+        }
+    }
+
+    fn delete_selected_filter(&mut self) {
+        let Some(id) = self
+            .filters
+            .get(self.filters_selected)
+            .map(|f| f.id.clone())
+        else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.delete_filter(&id)) {
+            Ok(()) => {
+                self.filters.retain(|f| f.id != id);
+                self.filters_selected = self
+                    .filters_selected
+                    .min(self.filters.len().saturating_sub(1));
+                self.filters_message = "Filter deleted.".to_string();
+            }
+            Err(e) => self.filters_message = format!("Delete failed: {e}"),
+        }
+    }
+
+    /// Keys for the keyword prompt. Returns false when it is not open.
+    /// r[filter.manage.keywords]
+    fn handle_filter_keywords_key(&mut self, code: KeyCode) -> bool {
+        let Some((_, ref mut text)) = self.filter_keyword_input else {
+            return false;
+        };
+        match code {
+            KeyCode::Esc => self.filter_keyword_input = None,
+            KeyCode::Enter => self.save_filter_keyword(),
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// Actions of the keyword list: add, edit, toggle whole word, delete.
+    /// r[filter.manage.keywords]
+    fn filter_keywords_action(&mut self, action: Action) {
+        let keyword = self
+            .filters
+            .get(self.filters_selected)
+            .and_then(|f| f.keywords.get(self.filter_keywords_selected))
+            .cloned();
+        match action {
+            Action::Back => {
+                self.view = View::Filters;
+                self.filters_message.clear();
+            }
+            Action::Up => {
+                self.filter_keywords_selected = self.filter_keywords_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                let len = self
+                    .filters
+                    .get(self.filters_selected)
+                    .map_or(0, |f| f.keywords.len());
+                self.filter_keywords_selected =
+                    (self.filter_keywords_selected + 1).min(len.saturating_sub(1));
+            }
+            Action::New => self.filter_keyword_input = Some((None, String::new())),
+            Action::Edit => {
+                self.filter_keyword_input = keyword.map(|k| (Some(k.id), k.keyword));
+            }
+            Action::WholeWord => {
+                if let Some(k) = keyword {
+                    self.update_filter_keyword(Some(&k.id), &k.keyword, !k.whole_word);
+                }
+            }
+            Action::Delete => {
+                if let Some(k) = keyword {
+                    self.delete_filter_keyword(&k.id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn save_filter_keyword(&mut self) {
+        let Some((id, text)) = self.filter_keyword_input.clone() else {
+            return;
+        };
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            self.filters_message = "Keyword cannot be empty.".to_string();
+            return;
+        }
+        let whole_word = id
+            .as_ref()
+            .and_then(|id| {
+                self.filters
+                    .get(self.filters_selected)
+                    .and_then(|f| f.keywords.iter().find(|k| &k.id == id))
+            })
+            .map_or(true, |k| k.whole_word);
+        if self.update_filter_keyword(id.as_deref(), &text, whole_word) {
+            self.filter_keyword_input = None;
+        }
+    }
+
+    /// Add (`keyword_id` None) or update a keyword, then reload. Returns true on success.
+    fn update_filter_keyword(
+        &mut self,
+        keyword_id: Option<&str>,
+        keyword: &str,
+        whole_word: bool,
+    ) -> bool {
+        let Some(filter_id) = self
+            .filters
+            .get(self.filters_selected)
+            .map(|f| f.id.clone())
+        else {
+            return false;
+        };
+        let Some(ref client) = self.client else {
+            return false;
+        };
+        match self
+            .runtime
+            .block_on(client.save_filter_keyword(&filter_id, keyword_id, keyword, whole_word))
+        {
+            Ok(_) => {
+                self.load_filters();
+                true
+            }
+            Err(e) => {
+                self.filters_message = format!("Keyword save failed: {e}");
+                false
+            }
+        }
+    }
+
+    fn delete_filter_keyword(&mut self, keyword_id: &str) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self
+            .runtime
+            .block_on(client.delete_filter_keyword(keyword_id))
+        {
+            Ok(()) => {
+                self.load_filters();
+                self.filters_message = "Keyword removed.".to_string();
+            }
+            Err(e) => self.filters_message = format!("Keyword delete failed: {e}"),
+        }
+    }
+
+    /// Open the lists view (press L) and fetch lists. r[list.manage.list]
+    fn open_lists(&mut self) {
+        self.view = View::Lists;
+        self.list_form = None;
+        self.lists_confirm_delete = false;
+        self.lists_message.clear();
+        self.load_lists();
+    }
+
+    fn load_lists(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_lists()) {
+            Ok(lists) => {
+                self.lists = lists;
+                self.lists_selected = self.lists_selected.min(self.lists.len().saturating_sub(1));
+            }
+            Err(e) => self.lists_message = format!("Failed to load lists: {e}"),
+        }
+    }
+
+    /// Keys for the create/rename form and the delete confirmation. Returns false when
+    /// neither is open. r[list.manage.edit]
+    fn handle_lists_key(&mut self, code: KeyCode) -> bool {
+        if let Some(ref mut form) = self.list_form {
+            match code {
+                KeyCode::Esc => {
+                    self.list_form = None;
+                    self.lists_message.clear();
+                }
+                KeyCode::Enter => self.save_list_form(),
+                KeyCode::Tab | KeyCode::Down => form.field = (form.field + 1) % ListForm::FIELDS,
+                KeyCode::BackTab | KeyCode::Up => {
+                    form.field = form.field.checked_sub(1).unwrap_or(ListForm::FIELDS - 1);
+                }
+                KeyCode::Backspace if form.field == 0 => {
+                    form.title.pop();
+                }
+                KeyCode::Char(c) if form.field == 0 => form.title.push(c),
+                KeyCode::Char(' ') if form.field == 1 => {
+                    form.replies_policy = match form.replies_policy {
+                        RepliesPolicy::Followed => RepliesPolicy::List,
+                        RepliesPolicy::List => RepliesPolicy::None,
+                        RepliesPolicy::None => RepliesPolicy::Followed,
+                    };
+                }
+                KeyCode::Char(' ') => form.exclusive = !form.exclusive,
+                _ => {}
+            }
+            return true;
+        }
+        if self.lists_confirm_delete {
+            self.lists_confirm_delete = false;
+            self.lists_message.clear();
+            if code == KeyCode::Char('y') {
+                self.delete_selected_list();
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Actions of the lists view. r[list.manage.list]
+    fn lists_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Timeline;
+                self.lists_message.clear();
+            }
+            Action::Up => {
+                self.lists_selected = self.lists_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.lists_selected =
+                    (self.lists_selected + 1).min(self.lists.len().saturating_sub(1));
+            }
+            Action::New => self.list_form = Some(ListForm::new()),
+            Action::Edit => {
+                self.list_form = self.lists.get(self.lists_selected).map(ListForm::from_list);
+            }
+            Action::Delete if !self.lists.is_empty() => self.lists_confirm_delete = true,
+            Action::Open if !self.lists.is_empty() => self.open_list_members(),
+            Action::Refresh => self.load_lists(),
+            _ => {}
+        }
+    }
+
+    /// Create or update the list in the form, keeping the timeline label in step.
+    fn save_list_form(&mut self) {
+        let Some(form) = self.list_form.clone() else {
+            return;
+        };
+        let title = form.title.trim();
+        if title.is_empty() {
+            self.lists_message = "List title cannot be empty.".to_string();
+            return;
+        }
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.save_list(
+            form.id.as_deref(),
+            title,
+            form.replies_policy,
+            form.exclusive,
+        )) {
+            Ok(saved) => {
+                if let TimelineSelection::List { id, title } = &mut self.current_timeline {
+                    if *id == saved.id {
+                        title.clone_from(&saved.title);
+                    }
+                }
+                self.list_form = None;
+                self.load_lists();
+                self.lists_selected = self
+                    .lists
+                    .iter()
+                    .position(|l| l.id == saved.id)
+                    .unwrap_or(0);
+                self.lists_message = format!("Saved list \"{}\".", saved.title);
+            }
+            Err(e) => self.lists_message = format!("Save failed: {e}"),
+        }
+    }
+
+    fn delete_selected_list(&mut self) {
+        let Some(id) = self.lists.get(self.lists_selected).map(|l| l.id.clone()) else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.delete_list(&id)) {
+            Ok(()) => {
+                self.lists.retain(|l| l.id != id);
+                self.lists_selected = self.lists_selected.min(self.lists.len().saturating_sub(1));
+                self.lists_message = "List deleted.".to_string();
+                let showing_deleted = matches!(
+                    &self.current_timeline,
+                    TimelineSelection::List { id: current, .. } if *current == id
+                );
+                if showing_deleted {
+                    // The list timeline is gone; fall back to Home on return.
+                    self.current_timeline = TimelineSelection::Home;
+                    self.statuses.clear();
+                    self.timeline_visible.clear();
+                    self.selected = 0;
+                    self.scroll = 0;
+                }
+            }
+            Err(e) => self.lists_message = format!("Delete failed: {e}"),
+        }
+    }
+
+    /// Show members of the selected list. r[list.manage.members]
+    fn open_list_members(&mut self) {
+        self.view = View::ListMembers;
+        self.list_members_selected = 0;
+        self.list_member_input = None;
+        self.lists_message.clear();
+        self.load_list_members();
+    }
+
+    fn load_list_members(&mut self) {
+        let Some(id) = self.lists.get(self.lists_selected).map(|l| l.id.clone()) else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.get_list_accounts(&id)) {
+            Ok(members) => {
+                self.list_members = members;
+                self.list_members_selected = self
+                    .list_members_selected
+                    .min(self.list_members.len().saturating_sub(1));
+            }
+            Err(e) => self.lists_message = format!("Failed to load members: {e}"),
+        }
+    }
+
+    /// Keys for the add-by-handle prompt. Returns false when it is not open.
+    /// r[list.manage.members]
+    fn handle_list_members_key(&mut self, code: KeyCode) -> bool {
+        let Some(ref mut input) = self.list_member_input else {
+            return false;
+        };
+        match code {
+            KeyCode::Esc => self.list_member_input = None,
+            KeyCode::Enter => self.add_list_member_by_handle(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// Actions of the list members view: add by handle, remove. r[list.manage.members]
+    fn list_members_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Lists;
+                self.lists_message.clear();
+            }
+            Action::Up => {
+                self.list_members_selected = self.list_members_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.list_members_selected =
+                    (self.list_members_selected + 1).min(self.list_members.len().saturating_sub(1));
+            }
+            Action::New => self.list_member_input = Some(String::new()),
+            Action::Delete => self.remove_selected_list_member(),
+            Action::Refresh => self.load_list_members(),
+            _ => {}
+        }
+    }
+
+    fn remove_selected_list_member(&mut self) {
+        let Some(account) = self.list_members.get(self.list_members_selected).cloned() else {
+            return;
+        };
+        let Some(list_id) = self.lists.get(self.lists_selected).map(|l| l.id.clone()) else {
+            return;
+        };
+        if self.set_list_membership(&list_id, &account, false) {
+            self.load_list_members();
+        }
+    }
+
+    fn add_list_member_by_handle(&mut self) {
+        let Some(handle) = self.list_member_input.clone() else {
+            return;
+        };
+        if handle.trim().is_empty() {
+            self.lists_message = "Enter an account handle.".to_string();
+            return;
+        }
+        let Some(list_id) = self.lists.get(self.lists_selected).map(|l| l.id.clone()) else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.lookup_account(&handle)) {
+            Ok(account) => {
+                if self.set_list_membership(&list_id, &account, true) {
+                    self.list_member_input = None;
+                    self.load_list_members();
+                }
+            }
+            Err(e) => self.lists_message = format!("Account not found: {e}"),
+        }
+    }
+
+    /// Add or remove one account from a list, reporting the outcome. Returns true on success.
+    fn set_list_membership(&mut self, list_id: &str, account: &Account, add: bool) -> bool {
+        let Some(ref client) = self.client else {
+            return false;
+        };
+        let ids = [account.id.clone()];
+        let list_title = self
+            .lists
+            .iter()
+            .find(|l| l.id == list_id)
+            .map_or("list", |l| l.title.as_str());
+        match self
+            .runtime
+            .block_on(client.update_list_accounts(list_id, &ids, add))
+        {
+            Ok(()) => {
+                self.lists_message = if add {
+                    format!("Added @{} to {list_title}.", account.handle())
+                } else {
+                    format!("Removed @{} from {list_title}.", account.handle())
+                };
+                true
+            }
+            Err(e) if add => {
+                self.lists_message = format!(
+                    "Could not add @{}: {e} (you must follow an account to list it)",
+                    account.handle()
+                );
+                false
+            }
+            Err(e) => {
+                self.lists_message = format!("Could not remove @{}: {e}", account.handle());
+                false
+            }
+        }
+    }
+
+    /// "Add this author to list…" from the toot view. r[list.add-author]
+    fn open_list_picker(&mut self) {
+        let Some(account) = self
+            .detail_status
+            .as_ref()
+            .map(|s| s.reblog.as_deref().unwrap_or(s).account.clone())
+        else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        self.lists_message.clear();
+        match self.runtime.block_on(client.get_lists()) {
+            Ok(lists) => self.lists = lists,
+            Err(e) => self.lists_message = format!("Failed to load lists: {e}"),
+        }
+        self.list_picker_member_of = self
+            .runtime
+            .block_on(client.get_account_lists(&account.id))
+            .map(|lists| lists.into_iter().map(|l| l.id).collect())
+            .unwrap_or_default();
+        self.list_picker_account = Some(account);
+        self.list_picker_selected = 0;
+        self.view = View::ListPicker;
+    }
+
+    /// Space/Enter toggles the author's membership of the selected list.
+    fn handle_list_picker_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc => {
+                self.view = View::TootDetail;
+                self.lists_message.clear();
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list_picker_selected = self.list_picker_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list_picker_selected =
+                    (self.list_picker_selected + 1).min(self.lists.len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => self.toggle_picker_membership(),
+            _ => {}
+        }
+    }
+
+    fn toggle_picker_membership(&mut self) {
+        let Some(list_id) = self
+            .lists
+            .get(self.list_picker_selected)
+            .map(|l| l.id.clone())
+        else {
+            return;
+        };
+        let Some(account) = self.list_picker_account.clone() else {
+            return;
+        };
+        let member = self.list_picker_member_of.contains(&list_id);
+        if self.set_list_membership(&list_id, &account, !member) {
+            if member {
+                self.list_picker_member_of.retain(|id| *id != list_id);
+            } else {
+                self.list_picker_member_of.push(list_id);
+            }
+        }
+    }
+
+    /// Fetch the logged-in account once (own messages, follow graph, mute rules).
+    fn ensure_me(&mut self) {
+        if self.me.is_none() {
+            if let Some(ref client) = self.client {
+                self.me = self.runtime.block_on(client.verify_credentials()).ok();
+            }
+        }
+    }
+
+    /// Open the follows browser on a tab and load its first page. r[follows.list]
+    fn open_follows(&mut self, tab: FollowsTab) {
+        let job = self.follows.job.take();
+        self.follows = FollowsBrowser {
+            tab,
+            job,
+            ..FollowsBrowser::default()
+        };
+        self.view = View::Follows;
+        self.load_follows(false);
+    }
+
+    /// Load the first page (or the next one when `append`) and relationships for it.
+    fn load_follows(&mut self, append: bool) {
+        self.ensure_me();
+        let Some(me) = self.me.as_ref().map(|a| a.id.clone()) else {
+            self.follows.message = "Could not load your account.".to_string();
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let max_id = if append {
+            match self.follows.next_max_id.clone() {
+                Some(id) => Some(id),
+                None => return,
+            }
+        } else {
+            None
+        };
+        let followers = self.follows.tab == FollowsTab::Followers;
+        match self
+            .runtime
+            .block_on(client.get_follows(&me, followers, max_id.as_deref()))
+        {
+            Ok((accounts, next)) => {
+                let ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+                if append {
+                    self.follows.accounts.extend(accounts);
+                } else {
+                    self.follows.accounts = accounts;
+                    self.follows.selected = 0;
+                }
+                self.follows.next_max_id = next;
+                self.follows.message.clear();
+                // The relationships endpoint accepts a limited number of ids per call.
+                for chunk in ids.chunks(40) {
+                    if let Ok(rels) = self.runtime.block_on(client.get_relationships(chunk)) {
+                        self.follows
+                            .relationships
+                            .extend(rels.into_iter().map(|r| (r.id.clone(), r)));
+                    }
+                }
+            }
+            Err(e) => self.follows.message = format!("Failed to load accounts: {e}"),
+        }
+    }
+
+    /// Keys for the search field, the list choice and the bulk confirmation of the follows
+    /// browser. Returns false when none is open. r[follows.search]
+    fn handle_follows_key(&mut self, code: KeyCode) -> bool {
+        let f = &mut self.follows;
+        if f.searching {
+            match code {
+                KeyCode::Esc => {
+                    f.searching = false;
+                    f.query.clear();
+                }
+                KeyCode::Enter => f.searching = false,
+                KeyCode::Backspace => {
+                    f.query.pop();
+                }
+                KeyCode::Char(c) => f.query.push(c),
+                _ => {}
+            }
+            f.selected = f.selected.min(f.filtered().len().saturating_sub(1));
+            return true;
+        }
+        if let Some(choice) = f.list_choice {
+            match code {
+                KeyCode::Esc => f.list_choice = None,
+                KeyCode::Up | KeyCode::Char('k') => f.list_choice = Some(choice.saturating_sub(1)),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    f.list_choice = Some((choice + 1).min(self.lists.len().saturating_sub(1)));
+                }
+                KeyCode::Enter => {
+                    f.list_choice = None;
+                    if let Some(list) = self.lists.get(choice) {
+                        let action = BulkAction::AddToList {
+                            id: list.id.clone(),
+                            title: list.title.clone(),
+                        };
+                        self.start_bulk(action);
+                    }
+                }
+                _ => {}
+            }
+            return true;
+        }
+        if let Some(action) = f.confirm.take() {
+            if code == KeyCode::Char('y') {
+                self.start_bulk(action);
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Actions of the follows browser: marking and bulk actions. r[follows.list] r[follows.bulk]
+    fn follows_action(&mut self, action: Action) {
+        let f = &mut self.follows;
+        let busy = f.job.is_some();
+        match action {
+            Action::Back => self.view = View::Timeline,
+            Action::Up => f.selected = f.selected.saturating_sub(1),
+            Action::Down => {
+                f.selected = (f.selected + 1).min(f.filtered().len().saturating_sub(1));
+            }
+            Action::NextTab => {
+                let tab = match f.tab {
+                    FollowsTab::Following => FollowsTab::Followers,
+                    FollowsTab::Followers => FollowsTab::Following,
+                };
+                self.open_follows(tab);
+            }
+            Action::Mark => {
+                if let Some(id) = f.selected_account().map(|a| a.id.clone()) {
+                    if !f.marked.remove(&id) {
+                        f.marked.insert(id);
+                    }
+                }
+            }
+            Action::ClearMarks => f.marked.clear(),
+            Action::Search => f.searching = true,
+            Action::ToggleFollow if !busy => self.toggle_follow(),
+            Action::LoadMore => self.load_follows(true),
+            Action::Refresh => self.load_follows(false),
+            Action::Unfollow | Action::Mute | Action::AddToList if busy => {
+                f.message = "Wait for the current operation to finish.".to_string();
+            }
+            Action::Unfollow => f.confirm = Some(BulkAction::Unfollow),
+            Action::Report => {
+                if let Some(account) = f.selected_account().cloned() {
+                    self.open_report(account, None);
+                }
+            }
+            Action::Mute => f.confirm = Some(BulkAction::Mute),
+            Action::AddToList => {
+                if let Some(ref client) = self.client {
+                    match self.runtime.block_on(client.get_lists()) {
+                        Ok(lists) if lists.is_empty() => {
+                            f.message = "No lists yet. Create one with [L].".to_string();
+                        }
+                        Ok(lists) => {
+                            self.lists = lists;
+                            f.list_choice = Some(0);
+                        }
+                        Err(e) => f.message = format!("Failed to load lists: {e}"),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Queue a bulk action over the marked (or selected) accounts. r[follows.bulk]
+    fn start_bulk(&mut self, action: BulkAction) {
+        let mut pending = self.follows.targets();
+        if pending.is_empty() {
+            return;
+        }
+        pending.reverse();
+        self.follows.message = format!("{}: starting…", action.label());
+        self.follows.job = Some(BulkJob {
+            action,
+            total: pending.len(),
+            pending,
+            failed: 0,
+        });
+    }
+
+    /// Run one step of the current bulk job; called from the main loop so progress is drawn
+    /// between accounts. Failures are recorded per account and the job carries on.
+    /// r[follows.bulk]
+    pub fn step_bulk(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let Some(ref mut job) = self.follows.job else {
+            return;
+        };
+        // r[ratelimit.bulk]: leave the rest of the quota to the user until it is refilled.
+        if let Some(reset) = client
+            .rate_limit()
+            .and_then(|r| r.pause_until(chrono::Utc::now()).map(str::to_string))
+        {
+            self.follows.message = format!(
+                "{}: {}/{}, paused near the rate limit until {}…",
+                job.action.label(),
+                job.done(),
+                job.total,
+                format_local(&reset)
+            );
+            return;
+        }
+        let Some(account) = job.pending.pop() else {
+            return;
+        };
+        let result = match &job.action {
+            BulkAction::Unfollow => self
+                .runtime
+                .block_on(client.follow_account(&account.id, false))
+                .map(Some),
+            BulkAction::Mute => self
+                .runtime
+                .block_on(client.mute_account(&account.id, true))
+                .map(Some),
+            BulkAction::AddToList { id, .. } => self
+                .runtime
+                .block_on(client.update_list_accounts(id, std::slice::from_ref(&account.id), true))
+                .map(|()| None),
+        };
+        match result {
+            Ok(relationship) => {
+                self.follows.errors.remove(&account.id);
+                self.follows.marked.remove(&account.id);
+                if let Some(r) = relationship {
+                    self.follows.relationships.insert(r.id.clone(), r);
+                }
+            }
+            Err(e) => {
+                job.failed += 1;
+                self.follows.errors.insert(account.id, e.to_string());
+            }
+        }
+        let label = job.action.label();
+        if !job.pending.is_empty() {
+            self.follows.message = format!("{label}: {}/{}…", job.done(), job.total);
+            return;
+        }
+        let (total, done) = (job.total, job.total - job.failed);
+        self.follows.job = None;
+        self.follows.message = if done == total {
+            format!("{label}: {done} of {total} done.")
+        } else {
+            format!("{label}: {done} of {total} done; failures are marked ✗ and stay marked.")
+        };
+    }
+
+    /// Refresh the pending follow request count and look for new `follow_request`
+    /// notifications, at most every `FOLLOW_REQUEST_CHECK_INTERVAL` and only for a locked
+    /// account. Called from the main loop. r[follow-requests.count] r[follow-requests.notify]
+    pub fn check_follow_requests(&mut self) {
+        if self.client.is_none()
+            || self
+                .follow_requests_checked_at
+                .is_some_and(|t| t.elapsed() < FOLLOW_REQUEST_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.follow_requests_checked_at = Some(Instant::now());
+        self.ensure_me();
+        let (Some(client), Some(true)) = (self.client.as_ref(), self.me.as_ref().map(|m| m.locked))
+        else {
+            return;
+        };
+        let Ok((pending, next)) = self.runtime.block_on(client.get_follow_requests(None)) else {
+            return;
+        };
+        self.follow_request_count = Some((pending.len(), next.is_some()));
+        let Ok(notifications) = self.runtime.block_on(
+            client.get_notifications(&["follow_request"], self.follow_request_seen.as_deref()),
+        ) else {
+            return;
+        };
+        let first_check = self.follow_request_seen.is_none();
+        if let Some(newest) = notifications.first() {
+            self.follow_request_seen = Some(newest.id.clone());
+        }
+        // On the first check only mention a request that is still pending.
+        self.follow_request_notice = notifications
+            .iter()
+            .filter_map(|n| n.account.as_ref())
+            .find(|a| !first_check || pending.iter().any(|p| p.id == a.id))
+            .map(|a| a.handle().to_string())
+            .or_else(|| self.follow_request_notice.take());
+        self.follow_requests = pending;
+        self.follow_requests_next = next;
+    }
+
+    /// Open the follow request approval view (R). r[follow-requests.list]
+    fn open_follow_requests(&mut self) {
+        self.view = View::FollowRequests;
+        self.follow_request_notice = None;
+        self.follow_requests_selected = 0;
+        self.follow_requests_message.clear();
+        self.load_follow_requests(false);
+    }
+
+    fn load_follow_requests(&mut self, append: bool) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let max_id = if append {
+            match self.follow_requests_next.clone() {
+                Some(id) => Some(id),
+                None => return,
+            }
+        } else {
+            None
+        };
+        match self
+            .runtime
+            .block_on(client.get_follow_requests(max_id.as_deref()))
+        {
+            Ok((accounts, next)) => {
+                if append {
+                    self.follow_requests.extend(accounts);
+                } else {
+                    self.follow_requests = accounts;
+                }
+                self.follow_requests_next = next;
+                self.follow_request_count = Some((
+                    self.follow_requests.len(),
+                    self.follow_requests_next.is_some(),
+                ));
+                self.follow_requests_selected = self
+                    .follow_requests_selected
+                    .min(self.follow_requests.len().saturating_sub(1));
+            }
+            Err(e) => self.follow_requests_message = format!("Failed to load requests: {e}"),
+        }
+    }
+
+    /// Actions of the follow request view: authorize, reject. r[follow-requests.decide]
+    fn follow_requests_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Timeline;
+                self.follow_requests_message.clear();
+            }
+            Action::Up => {
+                self.follow_requests_selected = self.follow_requests_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.follow_requests_selected = (self.follow_requests_selected + 1)
+                    .min(self.follow_requests.len().saturating_sub(1));
+            }
+            Action::Authorize => self.decide_follow_request(true),
+            Action::Reject => self.decide_follow_request(false),
+            Action::LoadMore => self.load_follow_requests(true),
+            Action::Refresh => self.load_follow_requests(false),
+            _ => {}
+        }
+    }
+
+    fn decide_follow_request(&mut self, authorize: bool) {
+        let Some(account) = self
+            .follow_requests
+            .get(self.follow_requests_selected)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self
+            .runtime
+            .block_on(client.decide_follow_request(&account.id, authorize))
+        {
+            Ok(_) => {
+                self.follow_requests.retain(|a| a.id != account.id);
+                self.follow_requests_selected = self
+                    .follow_requests_selected
+                    .min(self.follow_requests.len().saturating_sub(1));
+                if let Some((count, _)) = self.follow_request_count.as_mut() {
+                    *count = count.saturating_sub(1);
+                }
+                self.follow_requests_message = if authorize {
+                    format!("@{} can now follow you.", account.handle())
+                } else {
+                    format!("Rejected @{}.", account.handle())
+                };
+            }
+            Err(e) => self.follow_requests_message = format!("Failed: {e}"),
+        }
+    }
+
+    /// Open the moderation view on a tab and load its first page. r[moderation.mutes]
+    fn open_moderation(&mut self, tab: ModerationTab) {
+        self.moderation = ModerationBrowser {
+            tab,
+            ..ModerationBrowser::default()
+        };
+        self.view = View::Moderation;
+        self.load_moderation(false);
+    }
+
+    /// Load the first page (or the next one when `append`) of the current tab. For mutes,
+    /// also fetch relationships to learn which mutes cover notifications.
+    fn load_moderation(&mut self, append: bool) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let m = &mut self.moderation;
+        let max_id = if append {
+            match m.next_max_id.clone() {
+                Some(id) => Some(id),
+                None => return,
+            }
+        } else {
+            None
+        };
+        let next = match m.tab {
+            ModerationTab::Mutes | ModerationTab::Blocks => {
+                let page = if m.tab == ModerationTab::Mutes {
+                    self.runtime.block_on(client.get_mutes(max_id.as_deref()))
+                } else {
+                    self.runtime.block_on(client.get_blocks(max_id.as_deref()))
+                };
+                page.map(|(accounts, next)| {
+                    if m.tab == ModerationTab::Mutes {
+                        let ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+                        for chunk in ids.chunks(40) {
+                            if let Ok(rels) = self.runtime.block_on(client.get_relationships(chunk))
+                            {
+                                m.muting_notifications.extend(
+                                    rels.into_iter()
+                                        .filter(|r| r.muting_notifications)
+                                        .map(|r| r.id),
+                                );
+                            }
+                        }
+                    }
+                    if append {
+                        m.accounts.extend(accounts);
+                    } else {
+                        m.accounts = accounts;
+                    }
+                    next
+                })
+            }
+            ModerationTab::Domains => self
+                .runtime
+                .block_on(client.get_domain_blocks(max_id.as_deref()))
+                .map(|(domains, next)| {
+                    if append {
+                        m.domains.extend(domains);
+                    } else {
+                        m.domains = domains;
+                    }
+                    next
+                }),
+        };
+        match next {
+            Ok(next) => {
+                m.next_max_id = next;
+                m.selected = m.selected.min(m.len().saturating_sub(1));
+            }
+            Err(e) => m.message = format!("Failed to load: {e}"),
+        }
+    }
+
+    /// Keys for the domain prompt of the moderation view. Returns false when it is not open.
+    /// r[moderation.domain-add]
+    fn handle_moderation_key(&mut self, code: KeyCode) -> bool {
+        let m = &mut self.moderation;
+        let Some(ref mut input) = m.domain_input else {
+            return false;
+        };
+        match code {
+            KeyCode::Esc => {
+                m.domain_input = None;
+                m.message.clear();
+            }
+            KeyCode::Enter => self.submit_domain_block(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// Actions of the moderation view: next tab switches list, undo lifts the selected mute
+    /// or block, block domain asks for a hostname. r[moderation.undo] r[moderation.domain-add]
+    fn moderation_action(&mut self, action: Action) {
+        let m = &mut self.moderation;
+        match action {
+            Action::Back => self.view = View::Timeline,
+            Action::Up => m.selected = m.selected.saturating_sub(1),
+            Action::Down => {
+                m.selected = (m.selected + 1).min(m.len().saturating_sub(1));
+            }
+            Action::NextTab => {
+                let tab = m.tab.next();
+                self.open_moderation(tab);
+            }
+            Action::Undo => self.undo_moderation(),
+            Action::BlockDomain => {
+                m.domain_input = Some(String::new());
+                m.message.clear();
+            }
+            Action::LoadMore => self.load_moderation(true),
+            Action::Refresh => {
+                m.message.clear();
+                self.load_moderation(false);
+            }
+            _ => {}
+        }
+    }
+
+    /// Unmute, unblock or unblock the domain of the selected row; it stays listed on failure.
+    /// r[moderation.undo]
+    fn undo_moderation(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let m = &mut self.moderation;
+        let i = m.selected;
+        let result = match m.tab {
+            ModerationTab::Mutes | ModerationTab::Blocks => {
+                let Some(account) = m.accounts.get(i) else {
+                    return;
+                };
+                let (result, done) = if m.tab == ModerationTab::Mutes {
+                    let r = self
+                        .runtime
+                        .block_on(client.mute_account(&account.id, false));
+                    (r, "Unmuted")
+                } else {
+                    let r = self
+                        .runtime
+                        .block_on(client.block_account(&account.id, false));
+                    (r, "Unblocked")
+                };
+                result.map(|_| format!("{done} @{}.", m.accounts.remove(i).handle()))
+            }
+            ModerationTab::Domains => {
+                let Some(domain) = m.domains.get(i) else {
+                    return;
+                };
+                self.runtime
+                    .block_on(client.block_domain(domain, false))
+                    .map(|()| format!("Unblocked {}.", m.domains.remove(i)))
+            }
+        };
+        m.message = result.unwrap_or_else(|e| format!("Failed: {e}"));
+        m.selected = m.selected.min(m.len().saturating_sub(1));
+    }
+
+    /// Block the typed hostname, then show the Domains tab. r[moderation.domain-add]
+    fn submit_domain_block(&mut self) {
+        let input = self.moderation.domain_input.clone().unwrap_or_default();
+        let Some(domain) = normalize_domain(&input) else {
+            self.moderation.message = "Enter a hostname such as example.com.".to_string();
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.block_domain(&domain, true)) {
+            Ok(()) => {
+                self.open_moderation(ModerationTab::Domains);
+                self.moderation.selected = self
+                    .moderation
+                    .domains
+                    .iter()
+                    .position(|d| *d == domain)
+                    .unwrap_or(0);
+                self.moderation.message = format!("Blocked {domain}.");
+            }
+            Err(e) => self.moderation.message = format!("Failed to block {domain}: {e}"),
+        }
+    }
+
+    /// Start a report about `account`, with `status` preselected, and load the instance
+    /// rules and the author's recent posts. r[report.open]
+    fn open_report(&mut self, account: Account, status: Option<crate::api::Status>) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let mut form = ReportForm::new(account, status, self.view);
+        match self.runtime.block_on(client.get_instance_rules()) {
+            Ok(rules) => form.rules = rules,
+            Err(e) => form.message = format!("Could not load server rules: {e}"),
+        }
+        match self
+            .runtime
+            .block_on(client.get_account_statuses(&form.account.id))
+        {
+            Ok(statuses) => {
+                let known: HashSet<String> = form.statuses.iter().map(|s| s.id.clone()).collect();
+                form.statuses
+                    .extend(statuses.into_iter().filter(|s| !known.contains(&s.id)));
+            }
+            Err(e) => form.message = format!("Could not load recent posts: {e}"),
+        }
+        self.report = Some(form);
+        self.view = View::Report;
+    }
+
+    /// Keys for the comment step of the report flow, where they are typed: Enter sends, Tab
+    /// toggles forwarding, Esc goes back. Returns false on the other steps. r[report.comment]
+    fn handle_report_key(&mut self, code: KeyCode) -> bool {
+        let Some(ref mut form) = self.report else {
+            self.view = View::Timeline;
+            return true;
+        };
+        if form.step != ReportStep::Comment {
+            return false;
+        }
+        match code {
+            KeyCode::Esc => {
+                form.go(false);
+            }
+            KeyCode::Enter => self.submit_report(),
+            KeyCode::Tab if form.is_remote() => form.forward = !form.forward,
+            KeyCode::Backspace => {
+                form.comment.pop();
+            }
+            KeyCode::Char(c) if form.comment.chars().count() < ReportForm::COMMENT_LIMIT => {
+                form.comment.push(c);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Actions of the report flow before the comment: pick a category, rules and posts.
+    /// Next moves on; back goes to the previous step or cancels.
+    /// r[report.category] r[report.rules] r[report.statuses]
+    fn report_action(&mut self, action: Action) {
+        let Some(ref mut form) = self.report else {
+            return;
+        };
+        match (form.step, action) {
+            (_, Action::Back) => {
+                let went_back = form.go(false);
+                if !went_back {
+                    self.view = form.return_to;
+                    self.report = None;
+                }
+            }
+            (_, Action::Up) => form.cursor = form.cursor.saturating_sub(1),
+            (_, Action::Down) => {
+                form.cursor = (form.cursor + 1).min(form.rows().saturating_sub(1));
+            }
+            (ReportStep::Rules, Action::Mark) => {
+                if let Some(rule) = form.rules.get(form.cursor) {
+                    if !form.rule_ids.remove(&rule.id) {
+                        form.rule_ids.insert(rule.id.clone());
+                    }
+                }
+            }
+            (ReportStep::Statuses, Action::Mark) => {
+                if let Some(status) = form.statuses.get(form.cursor) {
+                    if !form.status_ids.remove(&status.id) {
+                        form.status_ids.insert(status.id.clone());
+                    }
+                }
+            }
+            (ReportStep::Category, Action::Next) => {
+                form.category = ReportCategory::ALL[form.cursor.min(ReportCategory::ALL.len() - 1)];
+                if form.category == ReportCategory::Violation && form.rules.is_empty() {
+                    form.message =
+                        "This server lists no rules; choose another category.".to_string();
+                } else {
+                    form.go(true);
+                }
+            }
+            (ReportStep::Rules, Action::Next) => {
+                if form.rule_ids.is_empty() {
+                    form.message = "Select at least one rule with [Space].".to_string();
+                } else {
+                    form.go(true);
+                }
+            }
+            (ReportStep::Statuses, Action::Next) => {
+                form.go(true);
+            }
+            _ => {}
+        }
+    }
+
+    /// Send the report; on failure the form stays open with the error. r[report.submit]
+    fn submit_report(&mut self) {
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let Some(ref mut form) = self.report else {
+            return;
+        };
+        match self
+            .runtime
+            .block_on(client.create_report(&form.to_report()))
+        {
+            Ok(()) => {
+                let done = format!("Reported @{} to the moderators.", form.account.handle());
+                self.view = form.return_to;
+                match self.view {
+                    View::TootDetail => self.detail_message = done,
+                    View::Follows => self.follows.message = done,
+                    _ => {}
+                }
+                self.report = None;
+            }
+            Err(e) => form.message = format!("Failed to send report: {e}"),
+        }
+    }
+
+    /// Open the Explore view on a tab and load it, logged in or browsing anonymously.
+    /// r[explore.trends] r[explore.anonymous]
+    fn open_explore(&mut self, tab: TrendsKind) {
+        self.explore = ExploreBrowser {
+            tab,
+            ..ExploreBrowser::default()
+        };
+        self.view = View::Explore;
+        let result = if let Some(ref client) = self.client {
+            self.runtime.block_on(client.get_trends(tab))
+        } else if let Some(ref public) = self.anonymous_instance {
+            self.runtime.block_on(public.get_trends(tab))
+        } else {
+            return;
+        };
+        match result {
+            Ok(trends) => self.explore.trends = Some(trends),
+            Err(e) => self.explore.message = format!("Failed to load trends: {e}"),
+        }
+    }
+
+    /// Actions of Explore: next tab switches tags/posts/links, open shows a trending post.
+    /// r[explore.trends]
+    fn explore_action(&mut self, action: Action) {
+        let e = &mut self.explore;
+        let len = e.trends.as_ref().map_or(0, Trends::len);
+        match action {
+            Action::Back => self.view = View::Timeline,
+            Action::Up => e.selected = e.selected.saturating_sub(1),
+            Action::Down => {
+                e.selected = (e.selected + 1).min(len.saturating_sub(1));
+            }
+            Action::NextTab => {
+                let tab = match e.tab {
+                    TrendsKind::Tags => TrendsKind::Statuses,
+                    TrendsKind::Statuses => TrendsKind::Links,
+                    TrendsKind::Links => TrendsKind::Tags,
+                };
+                self.open_explore(tab);
+            }
+            Action::Refresh => {
+                let tab = e.tab;
+                self.open_explore(tab);
+            }
+            Action::Open => {
+                if let Some(Trends::Statuses(statuses)) = &e.trends {
+                    if let Some(s) = statuses.get(e.selected) {
+                        self.detail_status = Some(s.clone());
+                        self.detail_message.clear();
+                        self.detail_revealed = false;
+                        self.detail_return_to = View::Explore;
+                        self.view = View::TootDetail;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys for the reschedule prompt and the cancel confirmation. Returns false when neither
+    /// is open.
+    fn handle_scheduled_key(&mut self, code: KeyCode) -> bool {
+        if let Some(ref mut input) = self.scheduled_reschedule_input {
+            match code {
+                KeyCode::Esc => {
+                    self.scheduled_reschedule_input = None;
+                    self.scheduled_message.clear();
+                }
+                KeyCode::Enter => self.submit_reschedule(),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return true;
+        }
+        if self.scheduled_confirm_cancel {
+            self.scheduled_confirm_cancel = false;
+            self.scheduled_message.clear();
+            if code == KeyCode::Char('y') {
+                self.cancel_selected_scheduled();
+            }
+            return true;
+        }
+        false
+    }
+
+    /// Actions of the scheduled posts view: move, reschedule, cancel (then y), refresh.
+    /// r[toot.schedule.list]
+    fn scheduled_action(&mut self, action: Action) {
+        match action {
+            Action::Back => {
+                self.view = View::Timeline;
+                self.scheduled_message.clear();
+            }
+            Action::Up => {
+                self.scheduled_selected = self.scheduled_selected.saturating_sub(1);
+            }
+            Action::Down => {
+                self.scheduled_selected = (self.scheduled_selected + 1)
+                    .min(self.scheduled_statuses.len().saturating_sub(1));
+            }
+            Action::Refresh => self.load_scheduled_posts(),
+            Action::Edit if !self.scheduled_statuses.is_empty() => {
+                self.scheduled_reschedule_input = Some(String::new());
+                self.scheduled_message.clear();
+            }
+            Action::Delete if !self.scheduled_statuses.is_empty() => {
+                self.scheduled_confirm_cancel = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// r[toot.schedule.reschedule]: PUT the new time for the selected post.
+    fn submit_reschedule(&mut self) {
+        let Some(input) = self.scheduled_reschedule_input.clone() else {
+            return;
+        };
+        let when = match parse_schedule(&input, chrono::Local::now()) {
+            Ok(when) => to_api_timestamp(when),
+            Err(e) => {
+                self.scheduled_message = e.to_string();
+                return;
+            }
+        };
+        let Some(id) = self
+            .scheduled_statuses
+            .get(self.scheduled_selected)
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.reschedule_status(&id, &when)) {
+            Ok(updated) => {
+                self.scheduled_reschedule_input = None;
+                self.load_scheduled_posts();
+                self.scheduled_selected = self
+                    .scheduled_statuses
+                    .iter()
+                    .position(|s| s.id == id)
+                    .unwrap_or(0);
+                self.scheduled_message =
+                    format!("Rescheduled for {}.", format_local(&updated.scheduled_at));
+            }
+            Err(e) => self.scheduled_message = format!("Reschedule failed: {e}"),
+        }
+    }
+
+    /// r[toot.schedule.cancel]: DELETE the selected scheduled post.
+    fn cancel_selected_scheduled(&mut self) {
+        let Some(id) = self
+            .scheduled_statuses
+            .get(self.scheduled_selected)
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        let Some(ref client) = self.client else {
+            return;
+        };
+        match self.runtime.block_on(client.cancel_scheduled_status(&id)) {
+            Ok(()) => {
+                self.scheduled_statuses.retain(|s| s.id != id);
+                self.scheduled_selected = self
+                    .scheduled_selected
+                    .min(self.scheduled_statuses.len().saturating_sub(1));
+                self.scheduled_message = "Scheduled post cancelled.".to_string();
+            }
+            Err(e) => self.scheduled_message = format!("Cancel failed: {e}"),
+        }
+    }
+
+    /// append: false = refresh from top (replace); true = load next page (append).
+    fn load_timeline(&mut self, append: bool) {
+        if let Some(ref client) = self.client {
+            self.loading = true;
+            self.timeline_message.clear();
+            let max_id_str = if append && !self.statuses.is_empty() {
+                self.statuses.last().map(|s| s.id.clone())
+            } else {
+                None
+            };
+            let max_id = max_id_str.as_deref();
+            let result = match &self.current_timeline {
+                TimelineSelection::Home => self.runtime.block_on(client.get_timeline_home(max_id)),
+                TimelineSelection::Local => {
+                    self.runtime.block_on(client.get_timeline_local(max_id))
+                }
+                TimelineSelection::Public => {
+                    self.runtime.block_on(client.get_timeline_public(max_id))
+                }
+                TimelineSelection::List { id, .. } => {
+                    self.runtime.block_on(client.get_timeline_list(id, max_id))
+                }
+            };
+            match result {
+                Ok(mut new_statuses) => {
+                    if max_id.is_some() {
+                        self.statuses.append(&mut new_statuses);
+                    } else {
+                        self.statuses = new_statuses;
+                    }
+                    self.store_timeline_cache();
+                    if !append {
+                        self.restore_read_position();
+                    }
+                    if self.mute_rules.needs_relationships() {
+                        self.load_relationships();
+                    }
+                }
+                Err(MastotuiError::NotAuthenticated) => {
+                    self.client = None;
+                    self.view = View::Login;
+                    self.login_message = "Session expired. Please log in again.".to_string();
+                    if self.config.is_some() {
+                        let _ = self.start_login_flow();
+                    }
+                }
+                Err(e) => self.timeline_load_failed(append, &e),
+            }
+            self.loading = false;
+        } else if let Some(ref public) = self.anonymous_instance {
+            self.loading = true;
+            self.timeline_message.clear();
+            let max_id = if append && !self.statuses.is_empty() {
+                self.statuses.last().map(|s| s.id.as_str())
+            } else {
+                None
+            };
+            match self.runtime.block_on(public.get_timeline_public(max_id)) {
+                Ok(mut new_statuses) => {
+                    if max_id.is_some() {
+                        self.statuses.append(&mut new_statuses);
+                    } else {
+                        self.statuses = new_statuses;
+                    }
+                    self.store_timeline_cache();
+                }
+                Err(e) => self.timeline_load_failed(append, &e),
+            }
+            self.loading = false;
+        }
+        self.rebuild_visible();
+    }
+
+    /// Fetch the saved markers once per session and, on Home, load pages until the last-read
+    /// status is reached and select it. The notifications marker seeds which follow request
+    /// notifications count as new. r[markers.restore]
+    fn restore_read_position(&mut self) {
+        if self.markers_restored || self.current_timeline != TimelineSelection::Home {
+            return;
+        }
+        let Some(ref client) = self.client else {
+            return;
+        };
+        self.markers_restored = true;
+        let Ok(markers) = self.runtime.block_on(client.get_markers()) else {
+            return;
+        };
+        if self.follow_request_seen.is_none() {
+            self.follow_request_seen = markers.notifications.map(|m| m.last_read_id);
+        }
+        let Some(marker) = markers.home.map(|m| m.last_read_id) else {
+            return;
+        };
+        for _ in 0..MARKER_MAX_PAGES {
+            if self.statuses.iter().any(|s| !id_newer(&s.id, &marker)) {
+                break;
+            }
+            let Some(last) = self.statuses.last().map(|s| s.id.clone()) else {
+                break;
+            };
+            match self.runtime.block_on(client.get_timeline_home(Some(&last))) {
+                Ok(page) if !page.is_empty() => self.statuses.extend(page),
+                _ => break,
+            }
+        }
+        self.home_marker = Some(marker);
+        self.rebuild_visible();
+        let unread = self.unread_count();
+        self.selected = unread;
+        self.scroll = unread;
+    }
+
+    /// Visible Home statuses newer than the home marker, shown above the "unread" divider.
+    /// 0 when there is no marker or it is not among the loaded statuses. r[markers.divider]
+    #[must_use]
+    pub fn unread_count(&self) -> usize {
+        let Some(ref marker) = self.home_marker else {
+            return 0;
+        };
+        if self.current_timeline != TimelineSelection::Home {
+            return 0;
+        }
+        let unread = self
+            .timeline_visible
+            .iter()
+            .take_while(|&&i| id_newer(&self.statuses[i].id, marker))
+            .count();
+        if unread < self.timeline_visible.len() {
+            unread
+        } else {
+            0
+        }
+    }
+
+    /// Move the home marker up to the newest status scrolled into view. r[markers.save]
+    fn note_read_position(&mut self) {
+        if self.client.is_none() || self.current_timeline != TimelineSelection::Home {
+            return;
+        }
+        let Some(id) = self
+            .timeline_visible
+            .get(self.scroll)
+            .and_then(|&i| self.statuses.get(i))
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        if self
+            .home_marker
+            .as_deref()
+            .map_or(true, |m| id_newer(&id, m))
+        {
+            self.home_marker = Some(id);
+            self.home_marker_dirty = true;
+        }
+    }
+
+    /// Save the home marker if it moved, at most every `MARKER_SAVE_INTERVAL` unless `force`
+    /// (on quit). Called from the main loop; a failed save is retried later. r[markers.save]
+    pub fn save_markers(&mut self, force: bool) {
+        if !self.home_marker_dirty
+            || (!force
+                && self
+                    .home_marker_saved_at
+                    .is_some_and(|t| t.elapsed() < MARKER_SAVE_INTERVAL))
+        {
+            return;
+        }
+        let (Some(client), Some(id)) = (self.client.as_ref(), self.home_marker.as_deref()) else {
+            return;
+        };
+        self.home_marker_saved_at = Some(Instant::now());
+        if self.runtime.block_on(client.save_home_marker(id)).is_ok() {
+            self.home_marker_dirty = false;
+        }
+    }
+
+    /// A timeline load failed: fall back to the cached timeline (offline mode) when there is
+    /// one, otherwise show the error. r[cache.offline]
+    fn timeline_load_failed(&mut self, append: bool, e: &MastotuiError) {
+        if !append && self.show_cached_timeline() {
+            self.offline = true;
+        } else {
+            self.timeline_message = format!("Failed to load timeline: {e}");
+            if !append {
+                self.timeline_cached_at = None;
+                self.offline = false;
+            }
+        }
+    }
+
+    /// Cache file name for the current source: the logged-in instance or the anonymous one.
+    fn cache_key(&self) -> Option<String> {
+        if self.client.is_some() {
+            let url = self.config.as_ref().map(|c| c.instance_url.as_str())?;
+            instance_host_from_url(url)
+                .ok()
+                .map(|host| TimelineCache::account_key(&host, false))
+        } else {
+            let url = self.anonymous_instance.as_ref()?.instance_url();
+            instance_host_from_url(url)
+                .ok()
+                .map(|host| TimelineCache::account_key(&host, true))
+        }
+    }
+
+    /// The cache for the current source, loaded from disk when the source changed.
+    fn timeline_cache(&mut self) -> Option<&mut TimelineCache> {
+        let key = self.cache_key()?;
+        if self.cache.as_ref().map_or(true, |(k, _)| *k != key) {
+            self.cache = Some((key.clone(), TimelineCache::load(&key)));
+        }
+        self.cache.as_mut().map(|(_, cache)| cache)
+    }
+
+    /// Show the current timeline from the cache, if it has one. r[cache.stale]
+    fn show_cached_timeline(&mut self) -> bool {
+        let key = self.current_timeline.rule_key();
+        let Some((statuses, fetched_at)) = self
+            .timeline_cache()
+            .and_then(|c| c.timeline(&key))
+            .map(|(s, at)| (s, at.to_string()))
+        else {
+            return false;
+        };
+        self.statuses = statuses;
+        self.timeline_cached_at = Some(fetched_at);
+        self.rebuild_visible();
+        true
+    }
+
+    /// Remember the freshly loaded timeline on disk. Cache write errors are ignored; the
+    /// cache is only a convenience. r[cache.store]
+    fn store_timeline_cache(&mut self) {
+        self.timeline_cached_at = None;
+        self.offline = false;
+        let key = self.current_timeline.rule_key();
+        let statuses = std::mem::take(&mut self.statuses);
+        if let Some(cache) = self.timeline_cache() {
+            cache.store(&key, &statuses, chrono::Utc::now().to_rfc3339());
+        }
+        self.statuses = statuses;
+        if let Some((ref account, ref cache)) = self.cache {
+            let _ = cache.save(account);
+        }
+    }
+
+    /// Fetch follow state for reply targets not seen before (for `replies_to_unfollowed`).
+    /// Failures leave them unknown, and unknown targets are not hidden.
+    fn load_relationships(&mut self) {
+        self.ensure_me();
+        let Some(ref client) = self.client else {
+            return;
+        };
+        let mut ids: Vec<String> = self
+            .statuses
+            .iter()
+            .filter_map(|s| {
+                s.reblog
+                    .as_deref()
+                    .unwrap_or(s)
+                    .in_reply_to_account_id
+                    .clone()
+            })
+            .filter(|id| !self.following.contains_key(id))
+            .collect();
+        ids.sort();
+        ids.dedup();
+        if let Ok(relationships) = self.runtime.block_on(client.get_relationships(&ids)) {
+            self.following
+                .extend(relationships.into_iter().map(|r| (r.id, r.following)));
+        }
+    }
+
+    /// Recompute which statuses are shown: drop those a server filter or mute rule hides,
+    /// recording why. r[filter.server.hide] r[mute.rules]
+    fn rebuild_visible(&mut self) {
+        let timeline = self.current_timeline.rule_key();
+        let ctx = MuteContext {
+            timeline: &timeline,
+            me: self.me.as_ref().map(|a| a.id.as_str()),
+            following: &self.following,
+        };
+        self.timeline_hidden = self
+            .statuses
+            .iter()
+            .map(|s| match s.server_filter() {
+                Some((FilterAction::Hide, title)) => Some(format!("filter \"{title}\"")),
+                _ => self.mute_rules.reason(s, &ctx),
+            })
+            .collect();
+        self.timeline_visible = self
+            .timeline_hidden
+            .iter()
+            .enumerate()
+            .filter(|(_, reason)| self.show_hidden || reason.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        self.selected = self
+            .selected
+            .min(self.timeline_visible.len().saturating_sub(1));
+    }
+
+    /// Put the cursor back on status `id` after the visible set changed, or on the next shown
+    /// status if `id` is now hidden.
+    fn reselect(&mut self, id: Option<&str>) {
+        let Some(index) = id.and_then(|id| self.statuses.iter().position(|s| s.id == id)) else {
+            return;
+        };
+        self.selected = self
+            .timeline_visible
+            .partition_point(|&i| i < index)
+            .min(self.timeline_visible.len().saturating_sub(1));
+    }
+
+    /// Timeline header label, with the hidden count when rules or filters hid anything.
+    /// r[mute.show-hidden]
+    fn timeline_label(&self) -> String {
+        let label = match self.timeline_hidden.iter().flatten().count() {
+            0 => self.current_timeline.label(),
+            n if self.show_hidden => {
+                format!("{} · showing {n} hidden [h]", self.current_timeline.label())
+            }
+            n => format!("{} · {n} hidden [h]", self.current_timeline.label()),
+        };
+        // r[mute.config]
+        let label = match self.mute_rules.skipped() {
+            [] => label,
+            [n] => format!("{label} · invalid mute rule {n} skipped"),
+            skipped => {
+                let numbers: Vec<String> = skipped.iter().map(ToString::to_string).collect();
+                format!(
+                    "{label} · invalid mute rules {} skipped",
+                    numbers.join(", ")
+                )
+            }
+        };
+        let label = cache_state_label(label, self.timeline_cached_at.as_ref(), self.offline);
+        // r[outbox.view]
+        let (pending, refused) = self
+            .outbox
+            .as_ref()
+            .map_or((0, 0), |(_, o)| (o.pending(), o.items.len() - o.pending()));
+        let label = match (pending, refused) {
+            (0, 0) => label,
+            (n, 0) => format!("{label} · {n} queued [o]"),
+            (0, f) => format!("{label} · {f} refused [o]"),
+            (n, f) => format!("{label} · {n} queued, {f} refused [o]"),
+        };
+        // r[follow-requests.count] r[follow-requests.notify]
+        if let Some(ref handle) = self.follow_request_notice {
+            return format!("{label} · @{handle} wants to follow you [R]");
+        }
+        match self.follow_request_count {
+            Some((n, more)) if n > 0 => {
+                let plus = if more { "+" } else { "" };
+                let s = if n == 1 && !more { "" } else { "s" };
+                format!("{label} · {n}{plus} follow request{s} [R]")
+            }
+            _ => label,
+        }
+    }
+
+    /// The status under the timeline cursor (after filtering).
+    fn selected_status(&self) -> Option<&crate::api::Status> {
+        self.timeline_visible
+            .get(self.selected)
+            .and_then(|&i| self.statuses.get(i))
+    }
+
+    /// Called each tick; fetches timeline when on home view with client or anonymous instance, not loading, empty statuses, no prior error.
+    /// At startup a cached timeline is shown for one tick before the fetch.
+    pub fn ensure_timeline_loaded(&mut self) -> Result<()> {
+        let has_source = self.client.is_some() || self.anonymous_instance.is_some();
+        if self.view != View::Timeline || !has_source || self.loading {
+            return Ok(());
+        }
+        // r[cache.stale]: the first time, show the cached timeline and return so the main loop
+        // draws it; the real fetch happens on the next call.
+        if self.refresh_pending {
+            self.refresh_pending = false;
+            self.load_timeline(false);
+        } else if self.statuses.is_empty() && self.timeline_message.is_empty() {
+            if !self.cache_shown_at_start {
+                self.cache_shown_at_start = true;
+                if self.show_cached_timeline() {
+                    self.refresh_pending = true;
+                    return Ok(());
+                }
+            }
+            self.load_timeline(false);
+        } else if let Some(i) =
+            (0..self.columns.len()).find(|&i| i != self.focused_column && !self.columns[i].loaded)
+        {
+            // r[layout.columns]: the other columns load after the focused one, one per pass.
+            self.load_column(i);
+        }
+        Ok(())
+    }
+}
+
+/// A timeline label with where its statuses come from while they are cached: offline after
+/// a failed refresh, or still refreshing. r[cache.stale] r[cache.offline]
+fn cache_state_label(label: String, cached_at: Option<&String>, offline: bool) -> String {
+    match (cached_at, offline) {
+        (Some(at), true) => format!("{label} · offline, cached {}", format_local(at)),
+        (Some(at), false) => format!("{label} · cached {}, refreshing…", format_local(at)),
+        (None, _) => label,
+    }
+}
+
+/// Hostname from what the user typed for a domain block: accepts `example.com`, a URL, or a
+/// handle like `@user@example.com`. None if it is not a plausible hostname.
+fn normalize_domain(input: &str) -> Option<String> {
+    let input = input.trim();
+    let input = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    let host = input.split('/').next()?;
+    let host = host
+        .rsplit('@')
+        .next()?
+        .trim_end_matches('.')
+        .to_lowercase();
+    let valid = host.contains('.')
+        && !host.starts_with('.')
+        && !host.contains("..")
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    valid.then_some(host)
+}
+
+/// Keys of the views whose keys are not configurable, for the help overlay. r[keys.help]
+const fn fixed_keys(view: View) -> (&'static str, &'static [(&'static str, &'static str)]) {
+    match view {
+        View::Login => (
+            "Login",
+            &[
+                ("enter", "Submit the instance URL, then the code"),
+                ("q", "Quit (while entering the URL)"),
+                ("i", "Instance info (while entering the URL)"),
+            ],
+        ),
+        View::Compose => (
+            "Compose",
+            &[
+                ("enter", "Post or schedule"),
+                ("alt-enter", "New line"),
+                ("tab", "Switch to the schedule field"),
+                ("ctrl-t", "Split into a thread"),
+                ("ctrl-i", "Instance info"),
+                ("esc", "Cancel"),
+            ],
+        ),
+        View::InstancePicker => (
+            "Browse instance",
+            &[
+                ("up, down", "Pick a known instance"),
+                ("enter", "Open its public timeline"),
+                ("esc", "Cancel"),
+            ],
+        ),
+        View::TimelinePicker => (
+            "Select timeline",
+            &[
+                ("k, j", "Move"),
+                ("enter", "Switch"),
+                (":", "Command palette"),
+                ("esc", "Cancel"),
+            ],
+        ),
+        View::ListPicker => (
+            "Add to lists",
+            &[
+                ("k, j", "Move"),
+                ("enter, space", "Add to / remove from the list"),
+                (":", "Command palette"),
+                ("esc", "Back"),
+            ],
+        ),
+        View::Debug => (
+            "Last API error",
+            &[(":", "Command palette"), ("esc", "Back")],
+        ),
+        _ => ("", &[]),
+    }
+}
+
+/// `layout` and `columns` from config.toml, refused when a name is unknown. r[layout.columns]
+fn layout_from_config(config: &AppConfig) -> Result<LayoutMode> {
+    if let Some(column) = config
+        .columns
+        .iter()
+        .find(|c| !matches!(c.as_str(), "home" | "local" | "public") && !c.starts_with("list:"))
+    {
+        return Err(MastotuiError::Config(format!(
+            "Unknown column `{column}` in columns (use home, local, public or list:<title>)"
+        )));
+    }
+    config
+        .layout
+        .as_deref()
+        .map_or(Ok(LayoutMode::Single), |name| {
+            LayoutMode::from_name(name).ok_or_else(|| {
+                MastotuiError::Config(format!(
+                    "Unknown layout `{name}` (use single, split or columns)"
+                ))
+            })
+        })
+}
+
+/// Status ids are numeric strings (snowflakes); a longer id is newer.
+fn id_newer(a: &str, b: &str) -> bool {
+    (a.len(), a) > (b.len(), b)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn k(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+
+    fn ctrl_i() -> KeyEvent {
+        KeyEvent::new(KeyCode::Char('i'), KeyModifiers::CONTROL)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    // r[verify toot.post.validation]
+    #[test]
+    fn compose_rejects_over_char_limit() {
+        const LIMIT: usize = 500;
+        let over = "x".repeat(LIMIT + 1);
+        assert!(over.chars().count() > LIMIT);
+    }
+
+    /// Condition under which `ensure_timeline_loaded` triggers a fetch. Must match `ensure_timeline_loaded()`.
+    #[allow(clippy::fn_params_excessive_bools)]
+    fn should_auto_fetch_timeline(
+        view: View,
+        client_is_some: bool,
+        loading: bool,
+        statuses_empty: bool,
+        timeline_message_empty: bool,
+    ) -> bool {
+        view == View::Timeline
+            && client_is_some
+            && !loading
+            && statuses_empty
+            && timeline_message_empty
+    }
+
+    #[test]
+    fn auto_fetch_timeline_requires_not_loading() {
+        assert!(!should_auto_fetch_timeline(
+            View::Timeline,
+            true,
+            true,
+            true,
+            true
+        ));
+    }
+
+    #[test]
+    fn auto_fetch_timeline_requires_empty_statuses() {
+        assert!(!should_auto_fetch_timeline(
+            View::Timeline,
+            true,
+            false,
+            false,
+            true
+        ));
+    }
+
+    #[test]
+    fn auto_fetch_timeline_requires_no_prior_error() {
+        assert!(!should_auto_fetch_timeline(
+            View::Timeline,
+            true,
+            false,
+            true,
+            false
+        ));
+    }
+
+    #[test]
+    fn auto_fetch_timeline_when_conditions_met() {
+        assert!(should_auto_fetch_timeline(
+            View::Timeline,
+            true,
+            false,
+            true,
+            true
+        ));
+    }
+
+    // r[verify browse.instance.dialog] r[verify browse.instance.cancel]
+    #[test]
+    fn instance_picker_opens_and_esc_cancels() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.open_instance_picker(View::Timeline);
+        assert_eq!(app.view, View::InstancePicker);
+        assert_eq!(app.return_to_view, View::Timeline);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify instance.info.dialog]
+    #[test]
+    fn instance_info_opens_on_i_and_esc_returns() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.handle_key(k(KeyCode::Char('i'))).unwrap();
+        assert_eq!(app.view, View::InstanceInfo);
+        assert_eq!(app.return_to_view, View::Timeline);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify instance.info.browse]
+    #[test]
+    fn instance_info_b_opens_instance_picker() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.handle_key(k(KeyCode::Char('i'))).unwrap();
+        assert_eq!(app.view, View::InstanceInfo);
+        app.handle_key(k(KeyCode::Char('b'))).unwrap();
+        assert_eq!(app.view, View::InstancePicker);
+        assert_eq!(app.return_to_view, View::InstanceInfo);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::InstanceInfo);
+    }
+
+    // r[verify instance.info.login]
+    #[test]
+    fn instance_info_l_when_not_logged_in_goes_to_login() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.client = None;
+        app.handle_key(k(KeyCode::Char('i'))).unwrap();
+        assert_eq!(app.view, View::InstanceInfo);
+        app.handle_key(k(KeyCode::Char('l'))).unwrap();
+        assert_eq!(app.view, View::Login);
+    }
+
+    // r[verify instance.info.logout]
+    #[test]
+    fn instance_info_l_when_logged_in_logs_out_and_goes_to_login() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client =
+            Some(crate::api::MastodonClient::new("https://example.com", "fake-token").unwrap());
+        app.instance_url = "https://example.com".to_string();
+        app.view = View::InstanceInfo;
+        app.return_to_view = View::Timeline;
+        app.handle_key(k(KeyCode::Char('l'))).unwrap();
+        assert_eq!(app.view, View::Login);
+        assert!(app.client.is_none());
+        assert_eq!(app.login_message, "Logged out.");
+    }
+
+    // r[verify timeline.select.header]
+    #[test]
+    fn timeline_selection_label_for_header() {
+        assert_eq!(TimelineSelection::Home.label(), "Home");
+        assert_eq!(TimelineSelection::Local.label(), "Local");
+        assert_eq!(TimelineSelection::Public.label(), "Public");
+        assert_eq!(
+            TimelineSelection::List {
+                id: "1".to_string(),
+                title: "Friends".to_string()
+            }
+            .label(),
+            "Friends"
+        );
+    }
+
+    // r[verify timeline.select.dialog] r[verify timeline.select.submit]
+    #[test]
+    fn timeline_picker_opens_and_esc_cancels() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.open_timeline_picker();
+        assert_eq!(app.view, View::TimelinePicker);
+        assert!(!app.timeline_picker_options.is_empty());
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify browse.instance.submit]
+    #[test]
+    fn instance_picker_submit_invalid_url_shows_message() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.open_instance_picker(View::Timeline);
+        app.instance_picker_input = "not-a-valid-url".to_string();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(!app.instance_picker_message.is_empty());
+    }
+
+    #[test]
+    fn compose_plain_letters_insert_including_i_ctrl_i_opens_instance_info() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Compose;
+        app.compose_buffer.clear();
+        app.handle_key(k(KeyCode::Char('h'))).unwrap();
+        app.handle_key(k(KeyCode::Char('i'))).unwrap();
+        assert_eq!(app.compose_buffer, "hi");
+        assert_eq!(app.view, View::Compose);
+        app.handle_key(ctrl_i()).unwrap();
+        assert_eq!(app.view, View::InstanceInfo);
+        assert_eq!(app.return_to_view, View::Compose);
+    }
+
+    // r[verify toot.thread.preview]
+    #[test]
+    fn separators_open_thread_preview_and_esc_returns_to_compose() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.open_compose(None);
+        app.compose_buffer = "first\n---\nsecond".to_string();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::ThreadPreview);
+        assert_eq!(app.thread_parts, vec!["first 1/2", "second 2/2"]);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Compose);
+        assert_eq!(app.compose_buffer, "first\n---\nsecond");
+    }
+
+    // r[verify toot.thread.post] r[verify toot.thread.resume]
+    #[test]
+    fn thread_post_failure_keeps_progress_for_resume() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        // Nothing listens on the discard port, so every post fails fast.
+        app.client =
+            Some(crate::api::MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.open_compose(None);
+        app.compose_buffer = "a\n---\nb\n---\nc".to_string();
+        app.handle_key(ctrl('t')).unwrap();
+        assert_eq!(app.view, View::ThreadPreview);
+        assert_eq!(app.thread_keys.len(), 3);
+        let keys = app.thread_keys.clone();
+        app.thread_posted_ids = vec!["1".to_string()];
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::ThreadPreview);
+        assert_eq!(app.thread_posted_ids, vec!["1"]);
+        assert!(app.thread_message.starts_with("Posted 1/3; part 2 failed"));
+        // A resume sends the failed part with the same key.
+        assert_eq!(app.thread_keys, keys);
+    }
+
+    #[test]
+    fn over_limit_without_separators_suggests_thread() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.open_compose(None);
+        app.compose_buffer = "word ".repeat(120);
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::Compose);
+        assert!(app.compose_error.contains("Ctrl+t"));
+        app.handle_key(ctrl('t')).unwrap();
+        assert_eq!(app.view, View::ThreadPreview);
+        assert!(app
+            .thread_parts
+            .iter()
+            .all(|p| p.chars().count() <= CHAR_LIMIT));
+    }
+
+    fn status_fixture(id: &str, account_id: &str, acct: &str) -> crate::api::Status {
+        serde_json::from_value(status_json(id, account_id, acct)).unwrap()
+    }
+
+    fn status_json(id: &str, account_id: &str, acct: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "uri": format!("https://example.com/statuses/{id}"),
+            "content": "<p>hello</p>",
+            "account": { "id": account_id, "display_name": acct, "username": acct, "acct": acct },
+            "created_at": "2025-06-01T12:00:00.000Z",
+            "visibility": "direct"
+        })
+    }
+
+    fn conversation_fixture(unread: bool) -> crate::api::Conversation {
+        serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "unread": unread,
+            "accounts": [
+                { "id": "2", "display_name": "Ann", "username": "ann", "acct": "ann" },
+                { "id": "3", "display_name": "Bob", "username": "bob", "acct": "bob@remote.example" }
+            ],
+            "last_status": status_json("10", "2", "ann")
+        }))
+        .unwrap()
+    }
+
+    // r[verify dm.list] r[verify dm.thread]
+    #[test]
+    fn conversations_navigation_and_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Conversations;
+        app.conversations = vec![conversation_fixture(false), conversation_fixture(false)];
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        assert_eq!(app.conversations_selected, 1);
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::ConversationThread);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Conversations);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify dm.reply]
+    #[test]
+    fn conversation_reply_is_direct_and_mentions_participants() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client =
+            Some(crate::api::MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::ConversationThread;
+        app.conversations = vec![conversation_fixture(false)];
+        app.conversation_statuses = vec![status_fixture("10", "2", "ann")];
+        app.handle_key(k(KeyCode::Char('r'))).unwrap();
+        assert_eq!(app.view, View::Compose);
+        assert_eq!(app.compose_visibility, Some(Visibility::Direct));
+        assert_eq!(app.compose_reply_to_id.as_deref(), Some("10"));
+        assert_eq!(app.compose_buffer, "@ann @bob@remote.example ");
+        assert_eq!(app.compose_title(), " Direct message ");
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::ConversationThread);
+    }
+
+    // r[verify dm.unread]
+    #[test]
+    fn unread_flag_defaults_to_false() {
+        let conv: crate::api::Conversation = serde_json::from_value(serde_json::json!({
+            "id": "c2", "accounts": [], "last_status": null
+        }))
+        .unwrap();
+        assert!(!conv.unread);
+        assert!(conversation_fixture(true).unread);
+    }
+
+    fn filtered_status(id: &str, action: &str) -> crate::api::Status {
+        let mut json = status_json(id, "2", "ann");
+        json["filtered"] = serde_json::json!([{
+            "filter": {
+                "id": "f1", "title": "Spoilers", "context": ["home"],
+                "expires_at": null, "filter_action": action
+            },
+            "keyword_matches": ["finale"]
+        }]);
+        serde_json::from_value(json).unwrap()
+    }
+
+    // r[verify filter.server.hide]
+    #[test]
+    fn hide_filtered_statuses_are_skipped_in_navigation() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.statuses = vec![
+            status_fixture("1", "2", "ann"),
+            filtered_status("2", "hide"),
+            status_fixture("3", "2", "ann"),
+        ];
+        app.rebuild_visible();
+        app.view = View::Timeline;
+        assert_eq!(app.timeline_visible, vec![0, 2]);
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        assert_eq!(app.selected_status().map(|s| s.id.as_str()), Some("3"));
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        assert_eq!(app.selected, 1);
+    }
+
+    // r[verify filter.server.warn]
+    #[test]
+    fn warn_filtered_status_opens_collapsed_and_v_reveals() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.statuses = vec![filtered_status("1", "warn")];
+        app.rebuild_visible();
+        app.view = View::Timeline;
+        assert_eq!(
+            app.statuses[0].server_filter(),
+            Some((FilterAction::Warn, "Spoilers"))
+        );
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::TootDetail);
+        assert!(!app.detail_revealed);
+        app.handle_key(k(KeyCode::Char('v'))).unwrap();
+        assert!(app.detail_revealed);
+    }
+
+    // r[verify filter.manage.edit]
+    #[test]
+    fn filter_form_edits_fields_and_rejects_empty_title() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Filters;
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app.filters_message.contains("title"));
+        app.handle_key(k(KeyCode::Char('s'))).unwrap();
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        app.handle_key(k(KeyCode::Up)).unwrap();
+        app.handle_key(k(KeyCode::Up)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        let form = app.filter_form.clone().unwrap();
+        assert_eq!(form.title, "s");
+        assert!(!form.context[0]);
+        assert_eq!(form.action, FilterAction::Hide);
+        assert_eq!(form.selected_contexts(), vec!["public", "thread"]);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(app.filter_form.is_none());
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify mute.rules] r[verify mute.show-hidden]
+    #[test]
+    fn mute_rules_hide_statuses_and_h_shows_them_with_reason() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.mute_rules = MuteRules::compile(&[crate::mute::MuteRule {
+            kind: crate::mute::MuteKind::Regex {
+                pattern: "hello".into(),
+            },
+            timelines: vec!["home".into()],
+        }]);
+        app.current_timeline = TimelineSelection::Home;
+        app.statuses = vec![
+            status_fixture("1", "2", "ann"),
+            filtered_status("2", "hide"),
+        ];
+        app.rebuild_visible();
+        assert!(app.timeline_visible.is_empty());
+        assert_eq!(app.timeline_label(), "Home · 2 hidden [h]");
+        app.handle_key(k(KeyCode::Char('h'))).unwrap();
+        assert_eq!(app.timeline_visible, vec![0, 1]);
+        assert_eq!(app.timeline_hidden[0].as_deref(), Some("matches /hello/"));
+        assert_eq!(
+            app.timeline_hidden[1].as_deref(),
+            Some("filter \"Spoilers\"")
+        );
+        app.current_timeline = TimelineSelection::Local;
+        app.rebuild_visible();
+        assert!(app.timeline_hidden[0].is_none());
+    }
+
+    // r[verify mute.config]
+    #[test]
+    fn invalid_mute_rule_in_config_is_reported_and_skipped() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("mastotui");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            "instance_url = \"https://example.com\"\nclient_id = \"c\"\n\n\
+             [[mute_rules]]\nkind = \"regex\"\npattern = \"(\"\n\n\
+             [[mute_rules]]\nkind = \"missing_alt_text\"\n",
+        )
+        .unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let app = App::new();
+        let app = app.unwrap();
+        assert_eq!(app.mute_rules.skipped(), [1]);
+        assert!(app
+            .timeline_label()
+            .ends_with("· invalid mute rule 1 skipped"));
+    }
+
+    // r[verify mute.show-hidden]
+    #[test]
+    fn toggling_hidden_keeps_the_cursor_on_the_same_status() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.statuses = vec![
+            filtered_status("1", "hide"),
+            status_fixture("2", "10", "ann"),
+            filtered_status("3", "hide"),
+            status_fixture("4", "10", "ann"),
+        ];
+        app.rebuild_visible();
+        app.selected = 1;
+        assert_eq!(app.selected_status().map(|s| s.id.as_str()), Some("4"));
+        app.handle_key(k(KeyCode::Char('h'))).unwrap();
+        assert_eq!(app.selected_status().map(|s| s.id.as_str()), Some("4"));
+        app.selected = 2;
+        app.handle_key(k(KeyCode::Char('h'))).unwrap();
+        assert_eq!(
+            app.selected_status().map(|s| s.id.as_str()),
+            Some("4"),
+            "a status that gets hidden hands the cursor to the next shown one"
+        );
+    }
+
+    fn list_fixture(id: &str, title: &str) -> crate::api::List {
+        serde_json::from_value(serde_json::json!({ "id": id, "title": title })).unwrap()
+    }
+
+    // r[verify list.manage.list] r[verify list.manage.edit]
+    #[test]
+    fn list_form_cycles_options_and_rejects_empty_title() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Lists;
+        app.lists = vec![list_fixture("1", "Friends"), list_fixture("2", "Work")];
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Char('e'))).unwrap();
+        assert_eq!(
+            app.list_form.as_ref().map(|f| f.id.as_deref()),
+            Some(Some("2"))
+        );
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app.lists_message.contains("title"));
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        let form = app.list_form.clone().unwrap();
+        assert_eq!(form.title, "x");
+        assert_eq!(form.replies_policy, RepliesPolicy::None);
+        assert!(form.exclusive);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert!(app.lists_confirm_delete);
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        assert!(!app.lists_confirm_delete);
+        assert_eq!(app.lists.len(), 2);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify list.manage.members]
+    #[test]
+    fn list_member_prompt_opens_and_cancels() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::ListMembers;
+        app.lists = vec![list_fixture("1", "Friends")];
+        app.handle_key(k(KeyCode::Char('a'))).unwrap();
+        for c in "bob".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(app.list_member_input.as_deref(), Some("bob"));
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app.lists_message.starts_with("Account not found"));
+        assert!(app.list_member_input.is_some());
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(app.list_member_input.is_none());
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Lists);
+    }
+
+    // r[verify list.add-author]
+    #[test]
+    fn list_picker_opens_for_author_and_reports_failures() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::TootDetail;
+        app.detail_status = Some(status_fixture("1", "2", "ann"));
+        app.handle_key(k(KeyCode::Char('l'))).unwrap();
+        assert_eq!(app.view, View::ListPicker);
+        assert_eq!(
+            app.list_picker_account.as_ref().map(|a| a.acct.as_str()),
+            Some("ann")
+        );
+        app.lists = vec![list_fixture("1", "Friends")];
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        assert!(app.lists_message.starts_with("Could not add @ann"));
+        assert!(app.list_picker_member_of.is_empty());
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::TootDetail);
+    }
+
+    fn account_fixture(id: &str, acct: &str) -> Account {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "display_name": acct.to_uppercase(), "username": acct, "acct": acct
+        }))
+        .unwrap()
+    }
+
+    // r[verify follows.search]
+    #[test]
+    fn follows_search_narrows_list_and_space_marks() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Follows;
+        app.follows.accounts = vec![
+            account_fixture("1", "ann"),
+            account_fixture("2", "bob@remote.example"),
+            account_fixture("3", "bobby"),
+        ];
+        app.handle_key(k(KeyCode::Char('/'))).unwrap();
+        for c in "BOB".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.follows.filtered(), vec![1, 2]);
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        assert!(app.follows.marked.contains("3"));
+        app.handle_key(k(KeyCode::Char('/'))).unwrap();
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.follows.filtered().len(), 3);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify follows.bulk]
+    #[test]
+    fn bulk_unfollow_runs_stepwise_and_records_each_failure() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::Follows;
+        app.follows.accounts = vec![account_fixture("1", "ann"), account_fixture("2", "bob")];
+        app.follows.marked = HashSet::from(["1".to_string(), "2".to_string()]);
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert_eq!(app.follows.confirm, Some(BulkAction::Unfollow));
+        app.handle_key(k(KeyCode::Char('y'))).unwrap();
+        assert_eq!(app.follows.job.as_ref().map(|j| j.total), Some(2));
+        app.handle_key(k(KeyCode::Char('M'))).unwrap();
+        assert!(app.follows.confirm.is_none());
+        app.step_bulk();
+        assert_eq!(app.follows.job.as_ref().map(BulkJob::done), Some(1));
+        assert!(app.follows.message.contains("1/2"));
+        app.step_bulk();
+        assert!(app.follows.job.is_none());
+        assert_eq!(app.follows.errors.len(), 2);
+        assert_eq!(app.follows.marked.len(), 2);
+        assert!(app.follows.message.contains("0 of 2 done"));
+    }
+
+    // r[verify follow-requests.count] r[verify follow-requests.notify] r[verify follow-requests.list]
+    #[test]
+    fn header_shows_request_count_and_notice_until_view_opens() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::Timeline;
+        app.current_timeline = TimelineSelection::Home;
+        assert_eq!(app.timeline_label(), "Home");
+        app.follow_request_count = Some((1, false));
+        assert_eq!(app.timeline_label(), "Home · 1 follow request [R]");
+        app.follow_request_count = Some((80, true));
+        assert_eq!(app.timeline_label(), "Home · 80+ follow requests [R]");
+        app.follow_request_notice = Some("ann".into());
+        assert_eq!(app.timeline_label(), "Home · @ann wants to follow you [R]");
+        app.handle_key(k(KeyCode::Char('R'))).unwrap();
+        assert_eq!(app.view, View::FollowRequests);
+        assert!(app.follow_request_notice.is_none());
+        assert!(app
+            .follow_requests_message
+            .starts_with("Failed to load requests"));
+    }
+
+    // r[verify follow-requests.decide]
+    #[test]
+    fn failed_authorize_keeps_request_listed() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::FollowRequests;
+        app.follow_requests = vec![account_fixture("1", "ann"), account_fixture("2", "bob")];
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Char('a'))).unwrap();
+        assert_eq!(app.follow_requests.len(), 2);
+        assert!(app.follow_requests_message.starts_with("Failed"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify moderation.domain-add]
+    #[test]
+    fn normalize_domain_accepts_hosts_urls_and_handles() {
+        assert_eq!(
+            normalize_domain(" Spam.Example "),
+            Some("spam.example".into())
+        );
+        assert_eq!(
+            normalize_domain("https://spam.example/@bot"),
+            Some("spam.example".into())
+        );
+        assert_eq!(
+            normalize_domain("@bot@spam.example"),
+            Some("spam.example".into())
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("bad host.example"), None);
+        assert_eq!(normalize_domain(""), None);
+    }
+
+    // r[verify moderation.mutes] r[verify moderation.blocks] r[verify moderation.domains]
+    // r[verify moderation.undo] r[verify moderation.domain-add]
+    #[test]
+    fn moderation_view_undo_failure_keeps_row_and_prompt_validates_host() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::Timeline;
+        app.handle_key(k(KeyCode::Char('M'))).unwrap();
+        assert_eq!(app.view, View::Moderation);
+        assert_eq!(app.moderation.tab, ModerationTab::Mutes);
+        assert!(app.moderation.message.starts_with("Failed to load"));
+
+        app.moderation.accounts = vec![account_fixture("1", "ann")];
+        app.handle_key(k(KeyCode::Char('u'))).unwrap();
+        assert_eq!(app.moderation.accounts.len(), 1);
+        assert!(app.moderation.message.starts_with("Failed"));
+
+        app.handle_key(k(KeyCode::Char('a'))).unwrap();
+        for c in "not a host".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app.moderation.domain_input.is_some());
+        assert!(app.moderation.message.contains("hostname"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(app.moderation.domain_input.is_none());
+
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        assert_eq!(app.moderation.tab, ModerationTab::Blocks);
+        assert!(app.moderation.accounts.is_empty());
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        assert_eq!(app.moderation.tab, ModerationTab::Domains);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify report.open] r[verify report.submit]
     #[test]
-    fn auto_fetch_timeline_requires_no_prior_error() {
-        assert!(!should_auto_fetch_timeline(
-            View::Timeline,
-            true,
-            false,
-            true,
-            false
-        ));
+    fn report_opens_from_toot_with_post_preselected_and_failed_send_keeps_form() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.view = View::TootDetail;
+        app.detail_status = Some(status_fixture("5", "20", "troll@remote.example"));
+        app.handle_key(k(KeyCode::Char('!'))).unwrap();
+        assert_eq!(app.view, View::Report);
+        let form = app.report.as_ref().unwrap();
+        assert_eq!(form.account.id, "20");
+        assert!(form.status_ids.contains("5"));
+        assert!(form.message.starts_with("Could not load"));
+
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.report.as_ref().unwrap().step, ReportStep::Comment);
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        let form = app.report.as_ref().unwrap();
+        assert_eq!(app.view, View::Report);
+        assert!(form.message.starts_with("Failed to send report"));
+
+        for _ in 0..3 {
+            app.handle_key(k(KeyCode::Esc)).unwrap();
+        }
+        assert!(app.report.is_none());
+        assert_eq!(app.view, View::TootDetail);
     }
 
+    // r[verify report.category] r[verify report.rules] r[verify report.statuses]
+    // r[verify report.comment]
     #[test]
-    fn auto_fetch_timeline_when_conditions_met() {
-        assert!(should_auto_fetch_timeline(
-            View::Timeline,
-            true,
-            false,
-            true,
-            true
-        ));
+    fn report_flow_requires_a_rule_for_violations_and_builds_request() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Report;
+        let mut form = ReportForm::new(
+            account_fixture("20", "troll@remote.example"),
+            Some(status_fixture("5", "20", "troll@remote.example")),
+            View::TootDetail,
+        );
+        form.rules = vec![
+            Rule {
+                id: "1".into(),
+                text: "No spam".into(),
+            },
+            Rule {
+                id: "2".into(),
+                text: "Be nice".into(),
+            },
+        ];
+        form.statuses
+            .push(status_fixture("6", "20", "troll@remote.example"));
+        app.report = Some(form);
+
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        let form = app.report.as_ref().unwrap();
+        assert_eq!(form.category, ReportCategory::Violation);
+        assert_eq!(form.step, ReportStep::Rules);
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app
+            .report
+            .as_ref()
+            .unwrap()
+            .message
+            .contains("at least one rule"));
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.report.as_ref().unwrap().step, ReportStep::Statuses);
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Char(' '))).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        for c in "abuse".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+
+        let report = app.report.as_ref().unwrap().to_report();
+        assert_eq!(
+            report,
+            NewReport {
+                account_id: "20".into(),
+                status_ids: vec!["5".into(), "6".into()],
+                category: ReportCategory::Violation,
+                rule_ids: vec!["2".into()],
+                comment: "abuse".into(),
+                forward: true,
+            }
+        );
+
+        // Going back and picking another category drops the rules from the request.
+        for _ in 0..3 {
+            app.handle_key(k(KeyCode::Esc)).unwrap();
+        }
+        assert_eq!(app.report.as_ref().unwrap().step, ReportStep::Category);
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        let form = app.report.as_ref().unwrap();
+        assert_eq!(form.step, ReportStep::Statuses);
+        assert!(form.to_report().rule_ids.is_empty());
     }
 
-    // r[verify browse.instance.dialog] r[verify browse.instance.cancel]
+    // r[verify explore.trends] r[verify explore.anonymous]
     #[test]
-    fn instance_picker_opens_and_esc_cancels() {
+    fn explore_opens_anonymously_and_trending_post_returns_to_explore() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
         app.view = View::Timeline;
-        app.open_instance_picker(View::Timeline);
-        assert_eq!(app.view, View::InstancePicker);
-        assert_eq!(app.return_to_view, View::Timeline);
+        app.handle_key(k(KeyCode::Char('e'))).unwrap();
+        assert_eq!(app.view, View::Timeline);
+
+        app.anonymous_instance = Some(PublicClient::new("http://127.0.0.1:9").unwrap());
+        app.handle_key(k(KeyCode::Char('e'))).unwrap();
+        assert_eq!(app.view, View::Explore);
+        assert_eq!(app.explore.tab, TrendsKind::Tags);
+        assert!(app.explore.message.starts_with("Failed to load trends"));
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        assert_eq!(app.explore.tab, TrendsKind::Statuses);
+
+        app.explore.trends = Some(Trends::Statuses(vec![
+            status_fixture("1", "10", "ann"),
+            status_fixture("2", "11", "bob"),
+        ]));
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::TootDetail);
+        assert_eq!(app.detail_status.as_ref().map(|s| s.id.as_str()), Some("2"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Explore);
         app.handle_key(k(KeyCode::Esc)).unwrap();
         assert_eq!(app.view, View::Timeline);
     }
 
-    // r[verify instance.info.dialog]
+    // r[verify markers.divider] r[verify markers.save]
     #[test]
-    fn instance_info_opens_on_i_and_esc_returns() {
+    fn home_marker_counts_unread_and_advances_when_scrolling_up() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
         app.view = View::Timeline;
-        app.handle_key(k(KeyCode::Char('i'))).unwrap();
-        assert_eq!(app.view, View::InstanceInfo);
-        assert_eq!(app.return_to_view, View::Timeline);
-        app.handle_key(k(KeyCode::Esc)).unwrap();
-        assert_eq!(app.view, View::Timeline);
+        app.current_timeline = TimelineSelection::Home;
+        app.statuses = ["1000", "999", "998", "997"]
+            .iter()
+            .map(|id| status_fixture(id, "10", "ann"))
+            .collect();
+        app.rebuild_visible();
+        assert!(id_newer("1000", "999"));
+        assert!(!id_newer("998", "998"));
+
+        app.home_marker = Some("998".into());
+        assert_eq!(app.unread_count(), 2);
+        app.current_timeline = TimelineSelection::Local;
+        assert_eq!(app.unread_count(), 0);
+        app.current_timeline = TimelineSelection::Home;
+        app.home_marker = Some("990".into());
+        assert_eq!(app.unread_count(), 0, "marker not loaded: no divider");
+
+        app.home_marker = Some("998".into());
+        app.selected = 2;
+        app.scroll = 2;
+        app.handle_key(k(KeyCode::Down)).unwrap();
+        assert!(
+            !app.home_marker_dirty,
+            "scrolling down past read posts keeps the marker"
+        );
+        app.handle_key(k(KeyCode::Up)).unwrap();
+        app.handle_key(k(KeyCode::Up)).unwrap();
+        assert_eq!(app.home_marker.as_deref(), Some("999"));
+        assert!(app.home_marker_dirty);
+        assert_eq!(app.unread_count(), 1);
+
+        app.save_markers(false);
+        assert!(app.home_marker_dirty, "failed save is retried");
+        let saved_at = app.home_marker_saved_at;
+        assert!(saved_at.is_some());
+        app.save_markers(false);
+        assert_eq!(app.home_marker_saved_at, saved_at, "throttled");
     }
 
-    // r[verify instance.info.browse]
+    // r[verify markers.restore]
     #[test]
-    fn instance_info_b_opens_instance_picker() {
+    fn marker_restore_runs_once_and_failure_keeps_top_of_home() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.current_timeline = TimelineSelection::Local;
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.restore_read_position();
+        assert!(!app.markers_restored, "only Home restores a position");
+        app.current_timeline = TimelineSelection::Home;
+        app.statuses = vec![
+            status_fixture("2", "10", "ann"),
+            status_fixture("1", "10", "ann"),
+        ];
+        app.rebuild_visible();
+        app.restore_read_position();
+        assert!(app.markers_restored);
+        assert!(app.home_marker.is_none());
+        assert_eq!((app.selected, app.scroll), (0, 0));
+    }
+
+    // r[verify cache.stale] r[verify cache.offline]
+    #[test]
+    fn cached_timeline_shows_first_then_stays_when_offline() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
         app.view = View::Timeline;
-        app.handle_key(k(KeyCode::Char('i'))).unwrap();
-        assert_eq!(app.view, View::InstanceInfo);
+        app.anonymous_instance = Some(PublicClient::new("http://127.0.0.1:9").unwrap());
+        app.current_timeline = TimelineSelection::Public;
+        let mut cache = TimelineCache::default();
+        cache.store(
+            "public",
+            &[
+                status_fixture("2", "10", "ann"),
+                status_fixture("1", "10", "ann"),
+            ],
+            "2025-06-01T12:00:00Z".into(),
+        );
+        let key = TimelineCache::account_key("127.0.0.1", true);
+        app.cache = Some((key, cache));
+
+        app.ensure_timeline_loaded().unwrap();
+        assert_eq!(app.statuses.len(), 2);
+        assert!(app.refresh_pending);
+        assert!(app.timeline_label().contains("refreshing"));
+
+        app.ensure_timeline_loaded().unwrap();
+        assert!(!app.refresh_pending);
+        assert!(app.offline);
+        assert_eq!(app.statuses.len(), 2);
+        assert!(app.timeline_message.is_empty());
+        assert!(app
+            .timeline_label()
+            .starts_with("Public · offline, cached "));
+
+        // A timeline with nothing cached still reports the error.
+        app.current_timeline = TimelineSelection::Local;
+        app.statuses.clear();
+        app.load_timeline(false);
+        assert!(!app.offline);
+        assert!(app.timeline_message.starts_with("Failed to load timeline"));
+    }
+
+    // r[verify layout.columns] r[verify cache.offline]
+    #[test]
+    fn background_column_keeps_its_own_offline_state() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.anonymous_instance = Some(PublicClient::new("http://127.0.0.1:9").unwrap());
+        app.layout = LayoutMode::Columns;
+        app.columns = vec![
+            TimelineColumn::new(TimelineSelection::Public),
+            TimelineColumn::new(TimelineSelection::Local),
+        ];
+        app.current_timeline = TimelineSelection::Public;
+        app.statuses = vec![status_fixture("3", "10", "ann")];
+        app.rebuild_visible();
+        let mut cache = TimelineCache::default();
+        cache.store(
+            "local",
+            &[
+                status_fixture("2", "10", "ann"),
+                status_fixture("1", "10", "ann"),
+            ],
+            "2025-06-01T12:00:00Z".into(),
+        );
+        let key = TimelineCache::account_key("127.0.0.1", true);
+        app.cache = Some((key, cache));
+
+        app.load_column(1);
+        let column = &app.columns[1];
+        assert!(column.offline);
+        assert!(column.cached_at.is_some());
+        assert_eq!(column.statuses.len(), 2);
+        assert!(!app.offline, "the focused column was not touched");
+        assert!(app.timeline_cached_at.is_none());
+        assert_eq!(app.statuses.len(), 1);
+
+        app.focus_column(1);
+        assert!(app.offline);
+        assert!(app.timeline_label().starts_with("Local · offline, cached "));
+    }
+
+    // r[verify outbox.queue] r[verify outbox.reconcile] r[verify outbox.view]
+    #[test]
+    fn unreachable_server_queues_writes_optimistically() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.config = Some(AppConfig::new("http://127.0.0.1:9", "client"));
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        app.detail_status = Some(status_fixture("5", "10", "ann"));
+        app.view = View::TootDetail;
+
+        app.handle_key(k(KeyCode::Char('f'))).unwrap();
+        let shown = app.detail_status.as_ref().unwrap();
+        assert_eq!(
+            shown.favourited,
+            Some(true),
+            "shown before the server answers"
+        );
+        assert_eq!(
+            Outbox::load("127.0.0.1").unwrap().items[0].attempts,
+            0,
+            "the key press itself does not wait for the network"
+        );
+        app.flush_outbox();
+        assert!(app.detail_message.starts_with("Favourite queued"));
+        assert!(app.timeline_label().ends_with("1 queued [o]"));
+        let saved = Outbox::load("127.0.0.1").unwrap();
+        assert_eq!(saved.items.len(), 1);
+        assert_eq!(saved.items[0].attempts, 1);
+
+        app.compose_buffer = "hello".into();
+        app.compose_return_to = View::Timeline;
+        app.view = View::Compose;
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+        assert!(app.compose_buffer.is_empty());
+        assert!(app.timeline_message.starts_with("Post queued"));
+
+        app.handle_key(k(KeyCode::Char('o'))).unwrap();
+        assert_eq!(app.view, View::Outbox);
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert_eq!(app.detail_status.as_ref().unwrap().favourited, Some(false));
+        let items = &app.outbox.as_ref().unwrap().1.items;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0].action, OutboxAction::Post { .. }));
+    }
+
+    // r[verify toot.optimistic]
+    #[test]
+    fn toggles_update_every_copy_and_roll_back() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.config = Some(AppConfig::new("http://127.0.0.1:9", "client"));
+        app.client = Some(MastodonClient::new("http://127.0.0.1:9", "fake-token").unwrap());
+        let mut original = status_json("5", "10", "ann");
+        original["reblogs_count"] = 2.into();
+        let mut boost = status_json("6", "11", "bob");
+        boost["reblog"] = original.clone();
+        app.statuses = vec![
+            serde_json::from_value(boost).unwrap(),
+            serde_json::from_value(original.clone()).unwrap(),
+        ];
+        app.explore.trends = Some(Trends::Statuses(vec![serde_json::from_value(
+            original.clone(),
+        )
+        .unwrap()]));
+        let mut column = TimelineColumn::new(TimelineSelection::Local);
+        column.statuses = vec![serde_json::from_value(original).unwrap()];
+        app.columns = vec![column];
+        app.detail_status = Some(app.statuses[0].clone());
+        app.view = View::TootDetail;
+
         app.handle_key(k(KeyCode::Char('b'))).unwrap();
-        assert_eq!(app.view, View::InstancePicker);
-        assert_eq!(app.return_to_view, View::InstanceInfo);
+        let boosted = |s: &crate::api::Status| {
+            let s = s.reblog.as_deref().unwrap_or(s);
+            (s.reblogged, s.reblogs_count)
+        };
+        assert_eq!(boosted(&app.statuses[0]), (Some(true), 3));
+        assert_eq!(boosted(&app.statuses[1]), (Some(true), 3));
+        assert_eq!(
+            boosted(app.detail_status.as_ref().unwrap()),
+            (Some(true), 3)
+        );
+        let Some(Trends::Statuses(ref trending)) = app.explore.trends else {
+            panic!("trends replaced");
+        };
+        assert_eq!(boosted(&trending[0]), (Some(true), 3));
+        assert_eq!(
+            boosted(&app.columns[0].statuses[0]),
+            (Some(true), 3),
+            "the same post in another column"
+        );
+        assert_eq!(
+            app.statuses[0].reblogs_count, 0,
+            "the boost itself is untouched"
+        );
+
+        // Discarding the queued boost takes it back everywhere.
+        app.view = View::Outbox;
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert_eq!(boosted(&app.statuses[0]), (Some(false), 2));
+        assert_eq!(boosted(&app.statuses[1]), (Some(false), 2));
+        assert_eq!(boosted(&app.columns[0].statuses[0]), (Some(false), 2));
+    }
+
+    // r[verify errors.debug]
+    #[test]
+    fn f12_opens_debug_panel_from_any_view() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Compose;
+        app.handle_key(k(KeyCode::F(12))).unwrap();
+        assert_eq!(app.view, View::Debug);
         app.handle_key(k(KeyCode::Esc)).unwrap();
-        assert_eq!(app.view, View::InstanceInfo);
+        assert_eq!(app.view, View::Compose);
     }
 
-    // r[verify instance.info.login]
+    // r[verify keys.sequence] r[verify keys.config]
     #[test]
-    fn instance_info_l_when_not_logged_in_goes_to_login() {
+    fn timeline_keys_go_through_the_keymap() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
         app.view = View::Timeline;
-        app.client = None;
-        app.handle_key(k(KeyCode::Char('i'))).unwrap();
-        assert_eq!(app.view, View::InstanceInfo);
+        app.statuses = (1..=4)
+            .map(|i| status_fixture(&i.to_string(), "2", "ann"))
+            .collect();
+        app.rebuild_visible();
+        app.handle_key(k(KeyCode::Char('G'))).unwrap();
+        assert_eq!(app.selected, 3);
+        app.handle_key(k(KeyCode::Char('g'))).unwrap();
+        assert_eq!(app.selected, 3, "waits for the second g");
+        app.handle_key(k(KeyCode::Char('g'))).unwrap();
+        assert_eq!(app.selected, 0);
+        app.handle_key(k(KeyCode::Char('g'))).unwrap();
+        app.handle_key(k(KeyCode::Char('j'))).unwrap();
+        assert_eq!(
+            app.selected, 1,
+            "a key that ends no sequence counts on its own"
+        );
+
+        let keys = toml::from_str("[timeline]\ndown = \"n\"\nopen = \"l\"\n").unwrap();
+        app.keymap = Keymap::from_config(&keys).unwrap();
+        app.handle_key(k(KeyCode::Char('j'))).unwrap();
+        assert_eq!(app.selected, 1);
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        assert_eq!(app.selected, 2);
         app.handle_key(k(KeyCode::Char('l'))).unwrap();
-        assert_eq!(app.view, View::Login);
+        assert_eq!(app.view, View::TootDetail);
+        assert_eq!(app.detail_status.as_ref().unwrap().id, "3");
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
     }
 
-    // r[verify instance.info.logout]
+    // r[verify keys.config]
     #[test]
-    fn instance_info_l_when_logged_in_logs_out_and_goes_to_login() {
+    fn list_views_use_the_keymap_and_prompts_keep_typed_keys() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
-        app.client =
-            Some(crate::api::MastodonClient::new("https://example.com", "fake-token").unwrap());
-        app.instance_url = "https://example.com".to_string();
-        app.view = View::InstanceInfo;
-        app.return_to_view = View::Timeline;
-        app.handle_key(k(KeyCode::Char('l'))).unwrap();
-        assert_eq!(app.view, View::Login);
-        assert!(app.client.is_none());
-        assert_eq!(app.login_message, "Logged out.");
+        let keys =
+            toml::from_str("[scheduled_posts]\ndown = \"n\"\ndelete = \"d\"\nedit = \"ctrl-e\"\n")
+                .unwrap();
+        app.keymap = Keymap::from_config(&keys).unwrap();
+        app.view = View::ScheduledPosts;
+        app.scheduled_statuses = vec![
+            scheduled_fixture("1", "2030-01-01T09:00:00.000Z"),
+            scheduled_fixture("2", "2030-01-02T09:00:00.000Z"),
+        ];
+        app.handle_key(k(KeyCode::Char('j'))).unwrap();
+        assert_eq!(app.scheduled_selected, 0);
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        assert_eq!(app.scheduled_selected, 1);
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert!(!app.scheduled_confirm_cancel);
+        app.handle_key(k(KeyCode::Char('d'))).unwrap();
+        assert!(app.scheduled_confirm_cancel);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(!app.scheduled_confirm_cancel);
+        app.handle_key(ctrl('e')).unwrap();
+        for c in "in 2d".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(
+            app.scheduled_reschedule_input.as_deref(),
+            Some("in 2d"),
+            "bound keys are typed in the prompt"
+        );
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        app.handle_key(k(KeyCode::Char('?'))).unwrap();
+        assert!(app.help_open);
+        let (title, rows) = app.help_rows();
+        assert_eq!(title, "Scheduled posts");
+        assert!(rows.contains(&("d".to_string(), "Cancel the post")));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
     }
 
-    // r[verify timeline.select.header]
+    // r[verify keys.conflict]
     #[test]
-    fn timeline_selection_label_for_header() {
-        assert_eq!(TimelineSelection::Home.label(), "Home");
-        assert_eq!(TimelineSelection::Local.label(), "Local");
-        assert_eq!(TimelineSelection::Public.label(), "Public");
+    fn conflicting_keys_in_config_are_a_startup_error() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("mastotui");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            "instance_url = \"https://example.com\"\nclient_id = \"c\"\n\n\
+             [keys.toot_detail]\nboost = \"f\"\n",
+        )
+        .unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let result = App::new();
+        assert!(
+            matches!(result, Err(MastotuiError::Config(m)) if m.contains("`f` (boost) and `f` (favourite)"))
+        );
+    }
+
+    // r[verify keys.help]
+    #[test]
+    fn help_overlay_shows_the_view_keys_and_any_key_closes_it() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::InstanceInfo;
+        app.return_to_view = View::Timeline;
+        app.handle_key(k(KeyCode::Char('?'))).unwrap();
+        assert!(app.help_open);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = terminal.backend().to_string();
+        assert!(screen.contains("Keys: Instance"));
+        assert!(screen.contains("Browse another instance"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(!app.help_open);
         assert_eq!(
-            TimelineSelection::List {
-                id: "1".to_string(),
-                title: "Friends".to_string()
-            }
-            .label(),
-            "Friends"
+            app.view,
+            View::InstanceInfo,
+            "the closing key does nothing else"
         );
     }
 
-    // r[verify timeline.select.dialog] r[verify timeline.select.submit]
+    // r[verify keys.help]
     #[test]
-    fn timeline_picker_opens_and_esc_cancels() {
+    fn help_covers_every_view_and_question_mark_stays_text_while_typing() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Lists;
+        app.handle_key(k(KeyCode::Char('?'))).unwrap();
+        assert!(app.help_open);
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = terminal.backend().to_string();
+        assert!(screen.contains("Keys: Lists"));
+        assert!(screen.contains("New list"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Lists);
+
+        app.view = View::Compose;
+        app.handle_key(k(KeyCode::Char('?'))).unwrap();
+        assert!(!app.help_open);
+        assert_eq!(app.compose_buffer, "?");
+        app.handle_key(k(KeyCode::F(1))).unwrap();
+        assert!(app.help_open);
+    }
+
+    // r[verify palette.open] r[verify palette.commands]
+    #[test]
+    fn palette_runs_the_picked_action_and_esc_closes_it() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
         app.view = View::Timeline;
-        app.open_timeline_picker();
-        assert_eq!(app.view, View::TimelinePicker);
-        assert!(!app.timeline_picker_options.is_empty());
+        app.handle_key(k(KeyCode::Char(':'))).unwrap();
+        let labels: Vec<String> = app
+            .palette
+            .as_ref()
+            .unwrap()
+            .matches()
+            .iter()
+            .map(|(c, _)| c.label())
+            .collect();
+        assert!(labels.contains(&"Explore trends".to_string()));
+        assert!(!labels.contains(&"Log out".to_string()), "not logged in");
+        assert!(!labels.contains(&"Move down".to_string()));
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        assert!(terminal.backend().to_string().contains(" Command "));
+
+        for c in "keys".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert!(app.palette.is_none());
+        assert!(app.help_open);
         app.handle_key(k(KeyCode::Esc)).unwrap();
+
+        app.handle_key(k(KeyCode::Char(':'))).unwrap();
+        app.handle_key(k(KeyCode::Char('q'))).unwrap();
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(app.palette.is_none());
+        assert_eq!(app.view, View::Timeline, "typed keys do not reach the view");
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        }
+    }
+
+    // r[verify mouse.select] r[verify mouse.scroll] r[verify mouse.hints]
+    #[test]
+    fn clicks_select_and_open_rows_and_run_hints() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::Timeline;
+        app.statuses = (1..=4)
+            .map(|i| status_fixture(&i.to_string(), "2", "ann"))
+            .collect();
+        app.rebuild_visible();
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 5, 4);
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+
+        // Rows start inside the border under the header line, two lines each.
+        app.handle_mouse(click).unwrap();
+        assert_eq!(app.selected, 1);
         assert_eq!(app.view, View::Timeline);
+        app.handle_mouse(mouse(MouseEventKind::ScrollDown, 5, 4))
+            .unwrap();
+        assert_eq!(app.selected, 2);
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, 5, 4))
+            .unwrap();
+        app.handle_mouse(mouse(MouseEventKind::ScrollUp, 5, 4))
+            .unwrap();
+        assert_eq!(app.selected, 0);
+        app.handle_mouse(click).unwrap();
+        app.handle_mouse(click).unwrap();
+        assert_eq!(app.view, View::TootDetail, "a double click opens the row");
+        assert_eq!(app.detail_status.as_ref().unwrap().id, "2");
+
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let footer: String = (0..80).map(|x| buffer[(x, 23)].symbol()).collect();
+        let back = u16::try_from(footer.find("[esc]").unwrap()).unwrap();
+        app.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), back, 23))
+            .unwrap();
+        assert_eq!(app.view, View::Timeline, "[esc] back was clicked");
     }
 
-    // r[verify browse.instance.submit]
+    // r[verify layout.split]
     #[test]
-    fn instance_picker_submit_invalid_url_shows_message() {
+    fn split_layout_keeps_the_timeline_beside_the_toot() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
-        app.open_instance_picker(View::Timeline);
-        app.instance_picker_input = "not-a-valid-url".to_string();
-        app.handle_key(k(KeyCode::Enter)).unwrap();
-        assert!(!app.instance_picker_message.is_empty());
+        app.view = View::Timeline;
+        app.statuses = (1..=3)
+            .map(|i| {
+                let mut s = status_fixture(&i.to_string(), "2", "ann");
+                s.content = format!("<p>post number {i}</p>");
+                s
+            })
+            .collect();
+        app.rebuild_visible();
+        app.handle_key(k(KeyCode::Char('|'))).unwrap();
+        assert_eq!(app.layout, LayoutMode::Split);
+        app.handle_key(k(KeyCode::Char('j'))).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = terminal.backend().to_string();
+        assert_eq!(
+            screen.matches("post number 2").count(),
+            2,
+            "row and preview"
+        );
+
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        assert_eq!(app.view, View::TootDetail);
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let screen = terminal.backend().to_string();
+        assert!(screen.contains("post number 1") && screen.contains("[b] boost"));
+        app.handle_key(k(KeyCode::BackTab)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+        assert_eq!(app.selected, 1);
     }
 
+    // r[verify layout.columns]
     #[test]
-    fn compose_plain_letters_insert_including_i_ctrl_i_opens_instance_info() {
+    fn unknown_columns_or_layout_in_config_are_a_startup_error() {
+        let mut config = AppConfig::new("https://example.com", "c");
+        config.layout = Some("columns".into());
+        assert_eq!(layout_from_config(&config).unwrap(), LayoutMode::Columns);
+        config.columns = vec!["home".into(), "list:Friends".into(), "mentions".into()];
+        assert!(
+            matches!(layout_from_config(&config), Err(MastotuiError::Config(m)) if m.contains("`mentions`"))
+        );
+        config.columns.clear();
+        config.layout = Some("grid".into());
+        assert!(layout_from_config(&config).is_err());
+    }
+
+    fn scheduled_fixture(id: &str, at: &str) -> crate::api::ScheduledStatus {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "scheduled_at": at,
+            "params": { "text": "later", "in_reply_to_id": null, "visibility": null }
+        }))
+        .unwrap()
+    }
+
+    // r[verify toot.schedule.compose] r[verify toot.schedule.validation]
+    #[test]
+    fn compose_tab_edits_schedule_and_rejects_too_soon() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let mut app = App::new().unwrap();
-        std::env::remove_var("XDG_CONFIG_HOME");
-        app.view = View::Compose;
-        app.compose_buffer.clear();
-        app.handle_key(k(KeyCode::Char('h'))).unwrap();
-        app.handle_key(k(KeyCode::Char('i'))).unwrap();
-        assert_eq!(app.compose_buffer, "hi");
+        app.open_compose(None);
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        app.handle_key(k(KeyCode::Tab)).unwrap();
+        for c in "in 1m".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(app.compose_buffer, "x");
+        assert_eq!(app.compose_schedule, "in 1m");
+        app.handle_key(k(KeyCode::Enter)).unwrap();
         assert_eq!(app.view, View::Compose);
-        app.handle_key(ctrl_i()).unwrap();
-        assert_eq!(app.view, View::InstanceInfo);
-        assert_eq!(app.return_to_view, View::Compose);
+        assert!(app.compose_error.contains("5 minutes"));
+    }
+
+    // r[verify toot.schedule.list] r[verify toot.schedule.cancel]
+    #[test]
+    fn scheduled_cancel_requires_confirmation() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::ScheduledPosts;
+        app.scheduled_statuses = vec![
+            scheduled_fixture("1", "2030-01-01T09:00:00.000Z"),
+            scheduled_fixture("2", "2030-01-02T09:00:00.000Z"),
+        ];
+        app.handle_key(k(KeyCode::Char('j'))).unwrap();
+        assert_eq!(app.scheduled_selected, 1);
+        app.handle_key(k(KeyCode::Char('x'))).unwrap();
+        assert!(app.scheduled_confirm_cancel);
+        app.handle_key(k(KeyCode::Char('n'))).unwrap();
+        assert!(!app.scheduled_confirm_cancel);
+        assert_eq!(app.scheduled_statuses.len(), 2);
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert_eq!(app.view, View::Timeline);
+    }
+
+    // r[verify toot.schedule.reschedule]
+    #[test]
+    fn reschedule_validates_new_time() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut app = App::new().unwrap();
+        app.view = View::ScheduledPosts;
+        app.scheduled_statuses = vec![scheduled_fixture("1", "2030-01-01T09:00:00.000Z")];
+        app.handle_key(k(KeyCode::Char('e'))).unwrap();
+        for c in "soon".chars() {
+            app.handle_key(k(KeyCode::Char(c))).unwrap();
+        }
+        app.handle_key(k(KeyCode::Enter)).unwrap();
+        assert_eq!(app.scheduled_reschedule_input.as_deref(), Some("soon"));
+        assert!(app.scheduled_message.contains("Unrecognised"));
+        app.handle_key(k(KeyCode::Esc)).unwrap();
+        assert!(app.scheduled_reschedule_input.is_none());
+        assert_eq!(app.view, View::ScheduledPosts);
     }
 }
@@ -0,0 +1,212 @@
+//! Offline cache of fetched timelines under the XDG cache dir (e.g. ~/.cache/mastotui).
+//! r[cache.store]
+//!
+//! One JSON file per account (`<instance host>.json`, or `anonymous.<host>.json` when browsing
+//! without logging in) holds the cached statuses by id, with their accounts embedded, and
+//! the order of each timeline as a list of ids keyed by `TimelineSelection::rule_key`.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::Status;
+use crate::error::{MastotuiError, Result};
+
+/// Statuses kept per timeline; older ones are dropped when a timeline is stored.
+pub const MAX_PER_TIMELINE: usize = 100;
+
+/// Returns the XDG cache directory for mastotui (e.g. ~/.cache/mastotui).
+pub fn cache_dir() -> Result<PathBuf> {
+    directories::ProjectDirs::from("org", "mastotui", "mastotui")
+        .map(|d| d.cache_dir().to_path_buf())
+        .ok_or_else(|| MastotuiError::Config("Could not determine cache directory".into()))
+}
+
+/// Create `dir` and any missing parents, and make `dir` itself readable by the user only
+/// (0700 on Unix): cached timelines and queued posts can hold private statuses.
+pub fn create_private_dir(dir: &Path) -> Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    builder.mode(0o700);
+    builder.create(dir)?;
+    #[cfg(unix)]
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    Ok(())
+}
+
+/// Write `contents` to `path`, readable and writable by the user only (0600 on Unix), also
+/// when the file already existed with wider permissions.
+pub fn write_private(path: &Path, contents: &str) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// A timeline's order as last fetched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CachedTimeline {
+    /// RFC 3339 time of the fetch.
+    fetched_at: String,
+    /// Status ids, newest first.
+    ids: Vec<String>,
+}
+
+/// Cached timelines of one account. r[cache.store]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineCache {
+    statuses: HashMap<String, Status>,
+    timelines: HashMap<String, CachedTimeline>,
+}
+
+impl TimelineCache {
+    /// File name for an account: the instance host, prefixed when browsing anonymously.
+    #[must_use]
+    pub fn account_key(host: &str, anonymous: bool) -> String {
+        let host: String = host
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if anonymous {
+            format!("anonymous.{host}")
+        } else {
+            host
+        }
+    }
+
+    /// Load an account's cache; a missing or unreadable file gives an empty cache.
+    #[must_use]
+    pub fn load(account_key: &str) -> Self {
+        cache_dir()
+            .ok()
+            .and_then(|dir| std::fs::read_to_string(dir.join(format!("{account_key}.json"))).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write an account's cache to disk, private to the user.
+    pub fn save(&self, account_key: &str) -> Result<()> {
+        let dir = cache_dir()?;
+        create_private_dir(&dir)?;
+        let json = serde_json::to_string(self)
+            .map_err(|e| MastotuiError::Config(format!("Failed to serialize cache: {e}")))?;
+        write_private(&dir.join(format!("{account_key}.json")), &json)
+    }
+
+    /// Cached statuses of a timeline, newest first, with the time they were fetched.
+    #[must_use]
+    pub fn timeline(&self, key: &str) -> Option<(Vec<Status>, &str)> {
+        let cached = self.timelines.get(key)?;
+        let statuses: Vec<Status> = cached
+            .ids
+            .iter()
+            .filter_map(|id| self.statuses.get(id).cloned())
+            .collect();
+        (!statuses.is_empty()).then_some((statuses, cached.fetched_at.as_str()))
+    }
+
+    /// Replace a timeline with freshly fetched statuses (capped at `MAX_PER_TIMELINE`) and
+    /// drop statuses no timeline refers to any more.
+    pub fn store(&mut self, key: &str, statuses: &[Status], fetched_at: String) {
+        let kept = &statuses[..statuses.len().min(MAX_PER_TIMELINE)];
+        for s in kept {
+            self.statuses.insert(s.id.clone(), s.clone());
+        }
+        self.timelines.insert(
+            key.to_string(),
+            CachedTimeline {
+                fetched_at,
+                ids: kept.iter().map(|s| s.id.clone()).collect(),
+            },
+        );
+        let referenced: HashSet<&String> = self.timelines.values().flat_map(|t| &t.ids).collect();
+        self.statuses.retain(|id, _| referenced.contains(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: &str) -> Status {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "uri": format!("https://example.com/statuses/{id}"),
+            "content": "<p>hello</p>",
+            "account": { "id": "10", "display_name": "Ann", "username": "ann", "acct": "ann" },
+            "created_at": "2025-06-01T12:00:00.000Z",
+            "filtered": [{
+                "filter": { "id": "1", "title": "Spoilers", "context": ["home"],
+                            "expires_at": null, "filter_action": "warn" },
+                "keyword_matches": ["spoiler"]
+            }]
+        }))
+        .unwrap()
+    }
+
+    // r[verify cache.store]
+    #[test]
+    fn store_caps_prunes_and_round_trips_through_disk() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut cache = TimelineCache::default();
+        let many: Vec<Status> = (0..MAX_PER_TIMELINE + 5)
+            .map(|i| status(&format!("{}", 1000 - i)))
+            .collect();
+        cache.store("home", &many, "2025-06-01T12:00:00Z".into());
+        cache.store(
+            "local",
+            &[status("1000"), status("5")],
+            "2025-06-01T12:05:00Z".into(),
+        );
+        assert_eq!(cache.statuses.len(), MAX_PER_TIMELINE + 1);
+        cache.store("home", &[status("2000")], "2025-06-01T13:00:00Z".into());
+        assert_eq!(
+            cache.statuses.len(),
+            3,
+            "statuses only home used are dropped"
+        );
+
+        let key = TimelineCache::account_key("mastodon.example", false);
+        cache.save(&key).unwrap();
+        let loaded = TimelineCache::load(&key);
+        let (home, fetched_at) = loaded.timeline("home").unwrap();
+        assert_eq!(fetched_at, "2025-06-01T13:00:00Z");
+        assert_eq!(home[0].id, "2000");
+        assert_eq!(home[0].account.handle(), "ann");
+        assert_eq!(home[0].server_filter().map(|(_, t)| t), Some("Spoilers"));
+        let local: Vec<String> = loaded
+            .timeline("local")
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(local, vec!["1000", "5"]);
+        assert!(loaded.timeline("public").is_none());
+        #[cfg(unix)]
+        {
+            let dir = cache_dir().unwrap();
+            let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&dir), 0o700);
+            assert_eq!(mode(&dir.join(format!("{key}.json"))), 0o600);
+        }
+        assert_eq!(TimelineCache::account_key("a b", true), "anonymous.a_b");
+    }
+}
@@ -4,6 +4,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::error::{MastotuiError, Result};
+use crate::keymap::KeysConfig;
+use crate::mute::MuteRule;
 
 /// Non-sensitive app configuration persisted to disk.
 /// r[config.persist-after-login]: instance URL and client id are stored here; secrets go to keyring.
@@ -13,6 +15,25 @@ pub struct AppConfig {
     pub instance_url: String,
     /// OAuth app client id (public; safe in config file)
     pub client_id: String,
+    /// Client-side mute rules (`[[mute_rules]]`). r[mute.config]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mute_rules: Vec<MuteRule>,
+    /// Key binding overrides per view (`[keys.timeline]` etc.). r[keys.config]
+    #[serde(default, skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+    /// Built-in theme or a file in `themes/` next to this one. r[theme.user]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Mouse support; on unless set to false. r[mouse.capture]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mouse: Option<bool>,
+    /// `single`, `split` or `columns`; single when unset. r[layout.split] r[layout.columns]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<String>,
+    /// Timelines of the columns layout (`home`, `local`, `public`, `list:<title>`).
+    /// r[layout.columns]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
 }
 
 impl AppConfig {
@@ -21,6 +42,12 @@ impl AppConfig {
         Self {
             instance_url: instance_url.trim_end_matches('/').to_string(),
             client_id: client_id.to_string(),
+            mute_rules: Vec::new(),
+            keys: KeysConfig::new(),
+            theme: None,
+            mouse: None,
+            layout: None,
+            columns: Vec::new(),
         }
     }
 }
@@ -72,9 +99,8 @@ mod tests {
     #[test]
     fn load_config_returns_none_when_file_missing() {
         let temp = tempfile::tempdir().unwrap();
-        std::env::set_var("XDG_CONFIG_HOME", temp.path());
+        let _env = crate::test_env::isolate(temp.path());
         let result = load_config();
-        std::env::remove_var("XDG_CONFIG_HOME");
         assert!(result.is_ok());
         assert!(result.unwrap().is_none());
     }
@@ -87,4 +113,22 @@ mod tests {
         assert!(!toml.to_lowercase().contains("secret"));
         assert!(!toml.to_lowercase().contains("token"));
     }
+
+    // r[verify mute.config]
+    #[test]
+    fn mute_rules_survive_save_round_trip() {
+        let mut config = AppConfig::new("https://example.com", "client-id");
+        config.mute_rules.push(MuteRule {
+            kind: crate::mute::MuteKind::BoostsFrom {
+                accounts: vec!["bob".into()],
+            },
+            timelines: vec!["home".into()],
+        });
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let back: AppConfig = toml::from_str(&toml).unwrap();
+        assert_eq!(back.mute_rules, config.mute_rules);
+        let plain: AppConfig =
+            toml::from_str("instance_url = \"https://a.example\"\nclient_id = \"c\"").unwrap();
+        assert!(plain.mute_rules.is_empty());
+    }
 }
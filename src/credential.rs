@@ -1,5 +1,11 @@
 //! Secure storage for secrets (access token, client secret) via system credential store.
 //! Ensures sensitive information is encrypted at rest; no plain-text secrets on disk.
+//!
+//! Secrets go through a `CredentialStore`: the system keyring unless another store was
+//! installed with `set_store` (e.g. `MemoryStore` in tests).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
 
 use keyring::Entry;
 
@@ -7,6 +13,94 @@ use crate::error::{MastotuiError, Result};
 
 const SERVICE_NAME: &str = "mastotui";
 
+/// Where secrets are kept, by key (e.g. `mastodon.social@oauth`).
+pub trait CredentialStore: Send + Sync {
+    /// The secret, or None if none is stored under `key`.
+    fn get(&self, key: &str) -> Result<Option<String>>;
+    fn set(&self, key: &str, secret: &str) -> Result<()>;
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// The system keyring (Secret Service, Keychain or Credential Manager).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(key: &str) -> Result<Entry> {
+        Entry::new(SERVICE_NAME, key).map_err(|e| MastotuiError::Credential(e.to_string()))
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match Self::entry(key)?.get_password() {
+            Ok(s) => Ok(Some(s)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(MastotuiError::Credential(e.to_string())),
+        }
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<()> {
+        Self::entry(key)?
+            .set_password(secret)
+            .map_err(|e| MastotuiError::Credential(e.to_string()))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        Self::entry(key)?
+            .delete_credential()
+            .map_err(|e| MastotuiError::Credential(e.to_string()))
+    }
+}
+
+/// Secrets kept in memory until the process exits; for tests.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    secrets: Mutex<HashMap<String, String>>,
+}
+
+impl CredentialStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.lock()?.get(key).cloned())
+    }
+
+    fn set(&self, key: &str, secret: &str) -> Result<()> {
+        self.lock()?.insert(key.to_string(), secret.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        self.lock()?.remove(key);
+        Ok(())
+    }
+}
+
+impl MemoryStore {
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, String>>> {
+        self.secrets
+            .lock()
+            .map_err(|e| MastotuiError::Credential(e.to_string()))
+    }
+}
+
+/// Store installed with `set_store`; None means the system keyring.
+static STORE: RwLock<Option<Arc<dyn CredentialStore>>> = RwLock::new(None);
+
+/// Keep all secrets in `store` from now on instead of the system keyring.
+pub fn set_store(store: Arc<dyn CredentialStore>) {
+    if let Ok(mut current) = STORE.write() {
+        *current = Some(store);
+    }
+}
+
+fn store() -> Arc<dyn CredentialStore> {
+    STORE
+        .read()
+        .ok()
+        .and_then(|s| s.clone())
+        .unwrap_or_else(|| Arc::new(KeyringStore))
+}
+
 /// Keyring account key for a given instance (hostname). Avoids storing secrets in config file.
 fn account_key(instance_host: &str) -> String {
     format!("{}@{}", instance_host, "oauth")
@@ -15,59 +109,27 @@ fn account_key(instance_host: &str) -> String {
 /// Store access token in system keyring (encrypted at rest by OS).
 /// r[config.persist-after-login]: token is persisted securely, not in plain text.
 pub fn set_access_token(instance_host: &str, token: &str) -> Result<()> {
-    let key = account_key(instance_host);
-    let entry =
-        Entry::new(SERVICE_NAME, &key).map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    entry
-        .set_password(token)
-        .map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    Ok(())
+    store().set(&account_key(instance_host), token)
 }
 
 /// Retrieve access token from keyring. Returns None if not found or error.
 pub fn get_access_token(instance_host: &str) -> Result<Option<String>> {
-    let key = account_key(instance_host);
-    let entry =
-        Entry::new(SERVICE_NAME, &key).map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    match entry.get_password() {
-        Ok(t) => Ok(Some(t)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(e) => Err(MastotuiError::Credential(e.to_string())),
-    }
+    store().get(&account_key(instance_host))
 }
 
 /// Remove stored access token (e.g. on logout or 401).
 pub fn delete_access_token(instance_host: &str) -> Result<()> {
-    let key = account_key(instance_host);
-    let entry =
-        Entry::new(SERVICE_NAME, &key).map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    entry
-        .delete_credential()
-        .map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    Ok(())
+    store().delete(&account_key(instance_host))
 }
 
 /// Store client secret in keyring (Mastodon app secret; must not be in config file).
 pub fn set_client_secret(instance_host: &str, secret: &str) -> Result<()> {
-    let key = format!("{instance_host}@client_secret");
-    let entry =
-        Entry::new(SERVICE_NAME, &key).map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    entry
-        .set_password(secret)
-        .map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    Ok(())
+    store().set(&format!("{instance_host}@client_secret"), secret)
 }
 
 /// Retrieve client secret from keyring.
 pub fn get_client_secret(instance_host: &str) -> Result<Option<String>> {
-    let key = format!("{instance_host}@client_secret");
-    let entry =
-        Entry::new(SERVICE_NAME, &key).map_err(|e| MastotuiError::Credential(e.to_string()))?;
-    match entry.get_password() {
-        Ok(s) => Ok(Some(s)),
-        Err(keyring::Error::NoEntry) => Ok(None),
-        Err(e) => Err(MastotuiError::Credential(e.to_string())),
-    }
+    store().get(&format!("{instance_host}@client_secret"))
 }
 
 /// Extract host from instance URL for use as keyring account scope.
@@ -1,5 +1,9 @@
-//! Application error type.
+//! Application error type. r[errors.decode] r[errors.messages]
 
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Deserialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -7,7 +11,7 @@ pub enum MastotuiError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("HTTP/client error: {0}")]
+    #[error("{}", describe_reqwest(.0))]
     Reqwest(#[from] reqwest::Error),
 
     #[error("OAuth error: {0}")]
@@ -19,19 +23,207 @@ pub enum MastotuiError {
     #[error("Credential error: {0}")]
     Credential(String),
 
+    /// Any other error status; `message` is the decoded server message.
     #[error("API error: {status} - {message}")]
     Api { status: u16, message: String },
 
+    /// 403: the token may not do this (e.g. a missing scope), or the account may not.
+    #[error("Not allowed: {0}")]
+    Forbidden(Box<ApiError>),
+
+    /// 404: deleted, or not visible to this account.
+    #[error("Not found; it may have been deleted or be private ({0})")]
+    NotFound(Box<ApiError>),
+
+    /// 410: the account or post is gone for good (e.g. suspended).
+    #[error("No longer available: {0}")]
+    Gone(Box<ApiError>),
+
+    /// 422: the server rejected the input; the message says why.
+    #[error("{0}")]
+    Unprocessable(Box<ApiError>),
+
+    /// 429: the rate limit is used up until `reset` (RFC 3339, when the server said).
+    #[error("Rate limited by the server{}", until(.reset.as_deref()))]
+    RateLimited { reset: Option<String> },
+
+    /// 502, 503 or 504: the server is down or overloaded.
+    #[error("Server unavailable; try again later")]
+    ServerUnavailable,
+
+    /// A success response whose body is not what the endpoint returns.
+    #[error("Unexpected response from the server: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    /// Input rejected before any request is sent (e.g. schedule time too soon).
+    #[error("{0}")]
+    Validation(String),
+
     #[error("Not authenticated")]
     NotAuthenticated,
 }
 
 pub type Result<T> = std::result::Result<T, MastotuiError>;
 
+impl MastotuiError {
+    /// Error for a failed response: the status picks the variant, the body is decoded.
+    /// r[errors.decode]
+    #[must_use]
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let error = Box::new(ApiError::parse(body));
+        match status {
+            403 => Self::Forbidden(error),
+            404 => Self::NotFound(error),
+            410 => Self::Gone(error),
+            422 => Self::Unprocessable(error),
+            429 => Self::RateLimited { reset: None },
+            502..=504 => Self::ServerUnavailable,
+            _ => Self::Api {
+                status,
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+/// Error body Mastodon sends with failed requests:
+/// `{"error": "...", "error_description": "...", "details": {"field": [{...}]}}`.
+/// r[errors.decode]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ApiError {
+    #[serde(default)]
+    pub error: String,
+    /// Longer explanation (OAuth errors).
+    #[serde(default)]
+    pub error_description: Option<String>,
+    /// Per-field validation errors (422).
+    #[serde(default)]
+    pub details: HashMap<String, Vec<ErrorDetail>>,
+    /// The body as received, for the debug panel.
+    #[serde(skip)]
+    pub raw: String,
+}
+
+/// One validation error of a field, e.g. `{"error": "ERR_TAKEN", "description": "..."}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ErrorDetail {
+    #[serde(default)]
+    pub error: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+impl ApiError {
+    /// Decode a response body; a body that is not Mastodon's error JSON is kept as `raw`.
+    #[must_use]
+    pub fn parse(body: &str) -> Self {
+        let mut error: Self = serde_json::from_str(body).unwrap_or_default();
+        error.raw = body.to_string();
+        error
+    }
+}
+
+/// The most readable message available: the description, the error, the field details, or
+/// a short plain-text body.
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(description) = self.error_description.as_deref().filter(|d| !d.is_empty()) {
+            return f.write_str(description);
+        }
+        if !self.error.is_empty() {
+            return f.write_str(&self.error);
+        }
+        let mut fields: Vec<_> = self.details.iter().collect();
+        fields.sort_by_key(|(field, _)| field.as_str());
+        let details: Vec<String> = fields
+            .into_iter()
+            .flat_map(|(field, errors)| {
+                errors
+                    .iter()
+                    .map(move |e| format!("{field} {}", e.description))
+            })
+            .collect();
+        if !details.is_empty() {
+            return f.write_str(&details.join("; "));
+        }
+        let raw = self.raw.trim();
+        if raw.is_empty() || raw.len() > 200 || raw.starts_with('<') || raw.starts_with('{') {
+            f.write_str("no details from the server")
+        } else {
+            f.write_str(raw)
+        }
+    }
+}
+
+/// Plain words for network failures instead of reqwest's chain of causes.
+fn describe_reqwest(e: &reqwest::Error) -> String {
+    if e.is_connect() {
+        "Cannot reach the server; check your connection".to_string()
+    } else if e.is_timeout() {
+        "The server took too long to answer".to_string()
+    } else if e.is_decode() {
+        format!("Unexpected response from the server: {e}")
+    } else {
+        format!("Network error: {e}")
+    }
+}
+
+/// " until HH:MM" for a rate limit reset time, in local time.
+fn until(reset: Option<&str>) -> String {
+    reset
+        .map(|r| format!(" until {}", crate::schedule::format_local(r)))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // r[verify errors.decode] r[verify errors.messages]
+    #[test]
+    fn error_bodies_decode_into_readable_messages() {
+        let e = MastotuiError::from_response(
+            422,
+            r#"{"error":"Validation failed: Text character limit of 500 exceeded"}"#,
+        );
+        assert_eq!(
+            e.to_string(),
+            "Validation failed: Text character limit of 500 exceeded"
+        );
+        let e = MastotuiError::from_response(
+            400,
+            r#"{"error":"invalid_grant","error_description":"The grant is invalid."}"#,
+        );
+        assert_eq!(e.to_string(), "API error: 400 - The grant is invalid.");
+        let e = MastotuiError::from_response(
+            422,
+            r#"{"error":"","details":{"username":[{"error":"ERR_TAKEN","description":"is taken"}]}}"#,
+        );
+        assert_eq!(e.to_string(), "username is taken");
+        let MastotuiError::NotFound(ref error) =
+            MastotuiError::from_response(404, r#"{"error":"Record not found"}"#)
+        else {
+            panic!("404 is NotFound");
+        };
+        assert_eq!(error.raw, r#"{"error":"Record not found"}"#);
+        assert!(matches!(
+            MastotuiError::from_response(403, "{}"),
+            MastotuiError::Forbidden(_)
+        ));
+        assert!(matches!(
+            MastotuiError::from_response(410, ""),
+            MastotuiError::Gone(_)
+        ));
+        assert!(matches!(
+            MastotuiError::from_response(503, "<html>"),
+            MastotuiError::ServerUnavailable
+        ));
+        assert_eq!(
+            MastotuiError::from_response(500, "<html>oops</html>").to_string(),
+            "API error: 500 - no details from the server"
+        );
+    }
+
     // r[verify auth.login.invalid-token]
     #[test]
     fn invalid_token_returns_not_authenticated() {
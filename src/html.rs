@@ -0,0 +1,23 @@
+//! Plain text from the HTML Mastodon sends as status content.
+
+/// Strip HTML tags from Mastodon content for plain-text display.
+///
+/// Ensures a space after hyperlinks so "link</a>next" becomes "link next".
+/// Block tags like </p> and <br> become newlines so content keeps paragraph breaks.
+#[must_use]
+pub fn strip_html(html: &str) -> String {
+    let s = html
+        .replace("</a>", "</a> ")
+        .replace("</p>", "\n")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+    let fragment = scraper::Html::parse_fragment(&s);
+    fragment
+        .root_element()
+        .text()
+        .collect::<Vec<_>>()
+        .join("")
+        .trim()
+        .to_string()
+}
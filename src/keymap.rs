@@ -0,0 +1,1030 @@
+//! Key bindings of the views, with overrides from config.toml.
+//! r[keys.config] r[keys.sequence] r[keys.conflict] r[keys.help]
+//!
+//! Each view has a `[keys.<view>]` table mapping an action to one key sequence or a list of
+//! them. Keys in a sequence are separated by spaces; actions not named keep their defaults,
+//! and an empty list unbinds an action:
+//!
+//! ```toml
+//! [keys.timeline]
+//! refresh = "ctrl-r"
+//! down = ["j", "down", "ctrl-n"]
+//! top = "g g"
+//!
+//! [keys.toot_detail]
+//! report = []
+//! ```
+//!
+//! Views where keys are typed as text (login, compose, pickers) keep fixed keys, and so do
+//! the forms, prompts and confirmations that open over a configurable view.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MastotuiError, Result};
+
+/// `[keys]` as written in the config file: view name to action name to binding.
+pub type KeysConfig = BTreeMap<String, BTreeMap<String, KeyBinding>>;
+
+/// One sequence (`"g g"`) or several (`["k", "up"]`) for an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    fn sequences(&self) -> &[String] {
+        match self {
+            Self::One(s) => std::slice::from_ref(s),
+            Self::Many(v) => v,
+        }
+    }
+}
+
+/// Views with configurable keys; the name is the config section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyView {
+    Timeline,
+    TootDetail,
+    InstanceInfo,
+    ScheduledPosts,
+    ThreadPreview,
+    Conversations,
+    Conversation,
+    Filters,
+    FilterKeywords,
+    Lists,
+    ListMembers,
+    Follows,
+    FollowRequests,
+    Moderation,
+    Report,
+    Explore,
+    Outbox,
+}
+
+impl KeyView {
+    pub const ALL: [Self; 17] = [
+        Self::Timeline,
+        Self::TootDetail,
+        Self::InstanceInfo,
+        Self::ScheduledPosts,
+        Self::ThreadPreview,
+        Self::Conversations,
+        Self::Conversation,
+        Self::Filters,
+        Self::FilterKeywords,
+        Self::Lists,
+        Self::ListMembers,
+        Self::Follows,
+        Self::FollowRequests,
+        Self::Moderation,
+        Self::Report,
+        Self::Explore,
+        Self::Outbox,
+    ];
+
+    /// Section name under `[keys]`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Timeline => "timeline",
+            Self::TootDetail => "toot_detail",
+            Self::InstanceInfo => "instance_info",
+            Self::ScheduledPosts => "scheduled_posts",
+            Self::ThreadPreview => "thread_preview",
+            Self::Conversations => "conversations",
+            Self::Conversation => "conversation",
+            Self::Filters => "filters",
+            Self::FilterKeywords => "filter_keywords",
+            Self::Lists => "lists",
+            Self::ListMembers => "list_members",
+            Self::Follows => "follows",
+            Self::FollowRequests => "follow_requests",
+            Self::Moderation => "moderation",
+            Self::Report => "report",
+            Self::Explore => "explore",
+            Self::Outbox => "outbox",
+        }
+    }
+
+    /// Heading of the help overlay.
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Timeline => "Timeline",
+            Self::TootDetail => "Toot",
+            Self::InstanceInfo => "Instance",
+            Self::ScheduledPosts => "Scheduled posts",
+            Self::ThreadPreview => "Thread",
+            Self::Conversations => "Direct messages",
+            Self::Conversation => "Conversation",
+            Self::Filters => "Filters",
+            Self::FilterKeywords => "Keywords",
+            Self::Lists => "Lists",
+            Self::ListMembers => "List members",
+            Self::Follows => "Following/followers",
+            Self::FollowRequests => "Follow requests",
+            Self::Moderation => "Mutes and blocks",
+            Self::Report => "Report",
+            Self::Explore => "Explore",
+            Self::Outbox => "Outbox",
+        }
+    }
+}
+
+/// Something a key can do. Which actions a view offers is given by its defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    Palette,
+    /// Single, split or columns layout. r[layout.split] r[layout.columns]
+    CycleLayout,
+    NextPane,
+    PreviousPane,
+    Back,
+    Up,
+    Down,
+    Top,
+    Bottom,
+    Open,
+    Refresh,
+    LoadMore,
+    ToggleHidden,
+    PickTimeline,
+    Compose,
+    Reply,
+    Boost,
+    Favourite,
+    Bookmark,
+    Reveal,
+    AddToList,
+    Report,
+    Explore,
+    Outbox,
+    Scheduled,
+    Conversations,
+    Filters,
+    Lists,
+    Follows,
+    FollowRequests,
+    Moderation,
+    InstanceInfo,
+    LogInOut,
+    BrowseInstance,
+    New,
+    Edit,
+    Delete,
+    /// Switch to the view's next tab (following/followers, trend kinds, mutes/blocks).
+    NextTab,
+    MarkRead,
+    WholeWord,
+    Search,
+    ToggleFollow,
+    /// Mark or unmark the selected row for a later action.
+    Mark,
+    ClearMarks,
+    Unfollow,
+    Mute,
+    Authorize,
+    Reject,
+    Undo,
+    BlockDomain,
+    Next,
+    Send,
+    Discard,
+}
+
+impl Action {
+    pub const ALL: [Self; 55] = [
+        Self::Quit,
+        Self::Help,
+        Self::Palette,
+        Self::CycleLayout,
+        Self::NextPane,
+        Self::PreviousPane,
+        Self::Back,
+        Self::Up,
+        Self::Down,
+        Self::Top,
+        Self::Bottom,
+        Self::Open,
+        Self::Refresh,
+        Self::LoadMore,
+        Self::ToggleHidden,
+        Self::PickTimeline,
+        Self::Compose,
+        Self::Reply,
+        Self::Boost,
+        Self::Favourite,
+        Self::Bookmark,
+        Self::Reveal,
+        Self::AddToList,
+        Self::Report,
+        Self::Explore,
+        Self::Outbox,
+        Self::Scheduled,
+        Self::Conversations,
+        Self::Filters,
+        Self::Lists,
+        Self::Follows,
+        Self::FollowRequests,
+        Self::Moderation,
+        Self::InstanceInfo,
+        Self::LogInOut,
+        Self::BrowseInstance,
+        Self::New,
+        Self::Edit,
+        Self::Delete,
+        Self::NextTab,
+        Self::MarkRead,
+        Self::WholeWord,
+        Self::Search,
+        Self::ToggleFollow,
+        Self::Mark,
+        Self::ClearMarks,
+        Self::Unfollow,
+        Self::Mute,
+        Self::Authorize,
+        Self::Reject,
+        Self::Undo,
+        Self::BlockDomain,
+        Self::Next,
+        Self::Send,
+        Self::Discard,
+    ];
+
+    /// Name used in the config file.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Help => "help",
+            Self::Palette => "palette",
+            Self::CycleLayout => "layout",
+            Self::NextPane => "next_pane",
+            Self::PreviousPane => "previous_pane",
+            Self::Back => "back",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Open => "open",
+            Self::Refresh => "refresh",
+            Self::LoadMore => "load_more",
+            Self::ToggleHidden => "toggle_hidden",
+            Self::PickTimeline => "pick_timeline",
+            Self::Compose => "compose",
+            Self::Reply => "reply",
+            Self::Boost => "boost",
+            Self::Favourite => "favourite",
+            Self::Bookmark => "bookmark",
+            Self::Reveal => "reveal",
+            Self::AddToList => "add_to_list",
+            Self::Report => "report",
+            Self::Explore => "explore",
+            Self::Outbox => "outbox",
+            Self::Scheduled => "scheduled",
+            Self::Conversations => "conversations",
+            Self::Filters => "filters",
+            Self::Lists => "lists",
+            Self::Follows => "follows",
+            Self::FollowRequests => "follow_requests",
+            Self::Moderation => "moderation",
+            Self::InstanceInfo => "instance_info",
+            Self::LogInOut => "log_in_out",
+            Self::BrowseInstance => "browse_instance",
+            Self::New => "new",
+            Self::Edit => "edit",
+            Self::Delete => "delete",
+            Self::NextTab => "next_tab",
+            Self::MarkRead => "mark_read",
+            Self::WholeWord => "whole_word",
+            Self::Search => "search",
+            Self::ToggleFollow => "follow",
+            Self::Mark => "mark",
+            Self::ClearMarks => "clear_marks",
+            Self::Unfollow => "unfollow",
+            Self::Mute => "mute",
+            Self::Authorize => "authorize",
+            Self::Reject => "reject",
+            Self::Undo => "undo",
+            Self::BlockDomain => "block_domain",
+            Self::Next => "next",
+            Self::Send => "send",
+            Self::Discard => "discard",
+        }
+    }
+
+    /// Line in the help overlay.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Help => "Show these keys",
+            Self::Palette => "Command palette",
+            Self::CycleLayout => "Layout: single, split, columns",
+            Self::NextPane => "Focus the next column or pane",
+            Self::PreviousPane => "Focus the previous column or pane",
+            Self::Back => "Back",
+            Self::Up => "Move up",
+            Self::Down => "Move down",
+            Self::Top => "Go to the newest post",
+            Self::Bottom => "Go to the last loaded post",
+            Self::Open => "Open toot",
+            Self::Refresh => "Refresh from the top",
+            Self::LoadMore => "Load more",
+            Self::ToggleHidden => "Show/hide hidden posts",
+            Self::PickTimeline => "Switch timeline",
+            Self::Compose => "New toot",
+            Self::Reply => "Reply",
+            Self::Boost => "Boost",
+            Self::Favourite => "Favourite",
+            Self::Bookmark => "Bookmark",
+            Self::Reveal => "Show a filtered toot",
+            Self::AddToList => "Add the author to a list",
+            Self::Report => "Report",
+            Self::Explore => "Explore trends",
+            Self::Outbox => "Outbox",
+            Self::Scheduled => "Scheduled posts",
+            Self::Conversations => "Direct messages",
+            Self::Filters => "Filters",
+            Self::Lists => "Lists",
+            Self::Follows => "Following/followers",
+            Self::FollowRequests => "Follow requests",
+            Self::Moderation => "Mutes and blocks",
+            Self::InstanceInfo => "Instance",
+            Self::LogInOut => "Log out / log in",
+            Self::BrowseInstance => "Browse another instance",
+            Self::New => "New",
+            Self::Edit => "Edit",
+            Self::Delete => "Delete",
+            Self::NextTab => "Next tab",
+            Self::MarkRead => "Mark read",
+            Self::WholeWord => "Whole word on/off",
+            Self::Search => "Search",
+            Self::ToggleFollow => "Follow/unfollow",
+            Self::Mark => "Mark",
+            Self::ClearMarks => "Clear marks",
+            Self::Unfollow => "Unfollow marked",
+            Self::Mute => "Mute marked",
+            Self::Authorize => "Authorize",
+            Self::Reject => "Reject",
+            Self::Undo => "Unmute/unblock",
+            Self::BlockDomain => "Block a domain",
+            Self::Next => "Next step",
+            Self::Send => "Send",
+            Self::Discard => "Discard",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
+
+/// Built-in bindings, in help-overlay order. A view offers exactly the actions listed here.
+const DEFAULTS: &[(KeyView, Action, &[&str])] = &[
+    (KeyView::Timeline, Action::Up, &["k", "up"]),
+    (KeyView::Timeline, Action::Down, &["j", "down"]),
+    (KeyView::Timeline, Action::Top, &["g g"]),
+    (KeyView::Timeline, Action::Bottom, &["G"]),
+    (KeyView::Timeline, Action::Open, &["enter"]),
+    (KeyView::Timeline, Action::Refresh, &["r"]),
+    (KeyView::Timeline, Action::LoadMore, &["m"]),
+    (KeyView::Timeline, Action::ToggleHidden, &["h"]),
+    (KeyView::Timeline, Action::PickTimeline, &["t"]),
+    (KeyView::Timeline, Action::Compose, &["p"]),
+    (KeyView::Timeline, Action::Scheduled, &["s"]),
+    (KeyView::Timeline, Action::Conversations, &["d"]),
+    (KeyView::Timeline, Action::Filters, &["F"]),
+    (KeyView::Timeline, Action::Lists, &["L"]),
+    (KeyView::Timeline, Action::Follows, &["w"]),
+    (KeyView::Timeline, Action::FollowRequests, &["R"]),
+    (KeyView::Timeline, Action::Moderation, &["M"]),
+    (KeyView::Timeline, Action::Explore, &["e"]),
+    (KeyView::Timeline, Action::Outbox, &["o"]),
+    (KeyView::Timeline, Action::InstanceInfo, &["i"]),
+    (KeyView::Timeline, Action::Help, &["?"]),
+    (KeyView::Timeline, Action::Palette, &[":"]),
+    (KeyView::Timeline, Action::CycleLayout, &["|"]),
+    (KeyView::Timeline, Action::NextPane, &["tab"]),
+    (KeyView::Timeline, Action::PreviousPane, &["backtab"]),
+    (KeyView::Timeline, Action::Quit, &["q"]),
+    (KeyView::TootDetail, Action::Reply, &["r"]),
+    (KeyView::TootDetail, Action::Boost, &["b"]),
+    (KeyView::TootDetail, Action::Favourite, &["f"]),
+    (KeyView::TootDetail, Action::Bookmark, &["B"]),
+    (KeyView::TootDetail, Action::Reveal, &["v"]),
+    (KeyView::TootDetail, Action::AddToList, &["l"]),
+    (KeyView::TootDetail, Action::Report, &["!"]),
+    (KeyView::TootDetail, Action::InstanceInfo, &["i"]),
+    (KeyView::TootDetail, Action::Help, &["?"]),
+    (KeyView::TootDetail, Action::Palette, &[":"]),
+    (KeyView::TootDetail, Action::CycleLayout, &["|"]),
+    (KeyView::TootDetail, Action::NextPane, &["tab"]),
+    (KeyView::TootDetail, Action::PreviousPane, &["backtab"]),
+    (KeyView::TootDetail, Action::Back, &["esc"]),
+    (KeyView::InstanceInfo, Action::LogInOut, &["l"]),
+    (KeyView::InstanceInfo, Action::BrowseInstance, &["b"]),
+    (KeyView::InstanceInfo, Action::Help, &["?"]),
+    (KeyView::InstanceInfo, Action::Palette, &[":"]),
+    (KeyView::InstanceInfo, Action::Back, &["esc"]),
+    (KeyView::ScheduledPosts, Action::Up, &["k", "up"]),
+    (KeyView::ScheduledPosts, Action::Down, &["j", "down"]),
+    (KeyView::ScheduledPosts, Action::Edit, &["e"]),
+    (KeyView::ScheduledPosts, Action::Delete, &["x"]),
+    (KeyView::ScheduledPosts, Action::Refresh, &["r"]),
+    (KeyView::ScheduledPosts, Action::Help, &["?"]),
+    (KeyView::ScheduledPosts, Action::Palette, &[":"]),
+    (KeyView::ScheduledPosts, Action::Back, &["esc"]),
+    (KeyView::ThreadPreview, Action::Up, &["k", "up"]),
+    (KeyView::ThreadPreview, Action::Down, &["j", "down"]),
+    (KeyView::ThreadPreview, Action::Send, &["enter"]),
+    (KeyView::ThreadPreview, Action::Help, &["?"]),
+    (KeyView::ThreadPreview, Action::Palette, &[":"]),
+    (KeyView::ThreadPreview, Action::Back, &["esc"]),
+    (KeyView::Conversations, Action::Up, &["k", "up"]),
+    (KeyView::Conversations, Action::Down, &["j", "down"]),
+    (KeyView::Conversations, Action::Open, &["enter"]),
+    (KeyView::Conversations, Action::MarkRead, &["m"]),
+    (KeyView::Conversations, Action::Refresh, &["r"]),
+    (KeyView::Conversations, Action::Help, &["?"]),
+    (KeyView::Conversations, Action::Palette, &[":"]),
+    (KeyView::Conversations, Action::Back, &["esc"]),
+    (KeyView::Conversation, Action::Up, &["k", "up"]),
+    (KeyView::Conversation, Action::Down, &["j", "down"]),
+    (KeyView::Conversation, Action::Reply, &["r"]),
+    (KeyView::Conversation, Action::Help, &["?"]),
+    (KeyView::Conversation, Action::Palette, &[":"]),
+    (KeyView::Conversation, Action::Back, &["esc"]),
+    (KeyView::Filters, Action::Up, &["k", "up"]),
+    (KeyView::Filters, Action::Down, &["j", "down"]),
+    (KeyView::Filters, Action::New, &["n"]),
+    (KeyView::Filters, Action::Edit, &["e"]),
+    (KeyView::Filters, Action::Delete, &["x"]),
+    (KeyView::Filters, Action::Open, &["enter"]),
+    (KeyView::Filters, Action::Refresh, &["r"]),
+    (KeyView::Filters, Action::Help, &["?"]),
+    (KeyView::Filters, Action::Palette, &[":"]),
+    (KeyView::Filters, Action::Back, &["esc"]),
+    (KeyView::FilterKeywords, Action::Up, &["k", "up"]),
+    (KeyView::FilterKeywords, Action::Down, &["j", "down"]),
+    (KeyView::FilterKeywords, Action::New, &["a"]),
+    (KeyView::FilterKeywords, Action::Edit, &["e"]),
+    (KeyView::FilterKeywords, Action::WholeWord, &["w"]),
+    (KeyView::FilterKeywords, Action::Delete, &["x"]),
+    (KeyView::FilterKeywords, Action::Help, &["?"]),
+    (KeyView::FilterKeywords, Action::Palette, &[":"]),
+    (KeyView::FilterKeywords, Action::Back, &["esc"]),
+    (KeyView::Lists, Action::Up, &["k", "up"]),
+    (KeyView::Lists, Action::Down, &["j", "down"]),
+    (KeyView::Lists, Action::New, &["n"]),
+    (KeyView::Lists, Action::Edit, &["e"]),
+    (KeyView::Lists, Action::Delete, &["x"]),
+    (KeyView::Lists, Action::Open, &["enter"]),
+    (KeyView::Lists, Action::Refresh, &["r"]),
+    (KeyView::Lists, Action::Help, &["?"]),
+    (KeyView::Lists, Action::Palette, &[":"]),
+    (KeyView::Lists, Action::Back, &["esc"]),
+    (KeyView::ListMembers, Action::Up, &["k", "up"]),
+    (KeyView::ListMembers, Action::Down, &["j", "down"]),
+    (KeyView::ListMembers, Action::New, &["a"]),
+    (KeyView::ListMembers, Action::Delete, &["x"]),
+    (KeyView::ListMembers, Action::Refresh, &["r"]),
+    (KeyView::ListMembers, Action::Help, &["?"]),
+    (KeyView::ListMembers, Action::Palette, &[":"]),
+    (KeyView::ListMembers, Action::Back, &["esc"]),
+    (KeyView::Follows, Action::Up, &["k", "up"]),
+    (KeyView::Follows, Action::Down, &["j", "down"]),
+    (KeyView::Follows, Action::NextTab, &["tab"]),
+    (KeyView::Follows, Action::Search, &["/"]),
+    (KeyView::Follows, Action::ToggleFollow, &["f"]),
+    (KeyView::Follows, Action::Mark, &["space"]),
+    (KeyView::Follows, Action::ClearMarks, &["c"]),
+    (KeyView::Follows, Action::Unfollow, &["x"]),
+    (KeyView::Follows, Action::Mute, &["M"]),
+    (KeyView::Follows, Action::AddToList, &["a"]),
+    (KeyView::Follows, Action::Report, &["!"]),
+    (KeyView::Follows, Action::LoadMore, &["m"]),
+    (KeyView::Follows, Action::Refresh, &["r"]),
+    (KeyView::Follows, Action::Help, &["?"]),
+    (KeyView::Follows, Action::Palette, &[":"]),
+    (KeyView::Follows, Action::Back, &["esc"]),
+    (KeyView::FollowRequests, Action::Up, &["k", "up"]),
+    (KeyView::FollowRequests, Action::Down, &["j", "down"]),
+    (KeyView::FollowRequests, Action::Authorize, &["a"]),
+    (KeyView::FollowRequests, Action::Reject, &["x"]),
+    (KeyView::FollowRequests, Action::LoadMore, &["m"]),
+    (KeyView::FollowRequests, Action::Refresh, &["r"]),
+    (KeyView::FollowRequests, Action::Help, &["?"]),
+    (KeyView::FollowRequests, Action::Palette, &[":"]),
+    (KeyView::FollowRequests, Action::Back, &["esc"]),
+    (KeyView::Moderation, Action::Up, &["k", "up"]),
+    (KeyView::Moderation, Action::Down, &["j", "down"]),
+    (KeyView::Moderation, Action::NextTab, &["tab"]),
+    (KeyView::Moderation, Action::Undo, &["u"]),
+    (KeyView::Moderation, Action::BlockDomain, &["a"]),
+    (KeyView::Moderation, Action::LoadMore, &["m"]),
+    (KeyView::Moderation, Action::Refresh, &["r"]),
+    (KeyView::Moderation, Action::Help, &["?"]),
+    (KeyView::Moderation, Action::Palette, &[":"]),
+    (KeyView::Moderation, Action::Back, &["esc"]),
+    (KeyView::Report, Action::Up, &["k", "up"]),
+    (KeyView::Report, Action::Down, &["j", "down"]),
+    (KeyView::Report, Action::Mark, &["space"]),
+    (KeyView::Report, Action::Next, &["enter"]),
+    (KeyView::Report, Action::Help, &["?"]),
+    (KeyView::Report, Action::Palette, &[":"]),
+    (KeyView::Report, Action::Back, &["esc"]),
+    (KeyView::Explore, Action::Up, &["k", "up"]),
+    (KeyView::Explore, Action::Down, &["j", "down"]),
+    (KeyView::Explore, Action::NextTab, &["tab"]),
+    (KeyView::Explore, Action::Open, &["enter"]),
+    (KeyView::Explore, Action::Refresh, &["r"]),
+    (KeyView::Explore, Action::Help, &["?"]),
+    (KeyView::Explore, Action::Palette, &[":"]),
+    (KeyView::Explore, Action::Back, &["esc"]),
+    (KeyView::Outbox, Action::Up, &["k", "up"]),
+    (KeyView::Outbox, Action::Down, &["j", "down"]),
+    (KeyView::Outbox, Action::Send, &["r"]),
+    (KeyView::Outbox, Action::Discard, &["x"]),
+    (KeyView::Outbox, Action::Help, &["?"]),
+    (KeyView::Outbox, Action::Palette, &[":"]),
+    (KeyView::Outbox, Action::Back, &["esc"]),
+];
+
+/// What an action is called in one view's help where its general description would be vague,
+/// e.g. `open` in the lists view shows the list's members.
+const LABELS: &[(KeyView, Action, &str)] = &[
+    (KeyView::ScheduledPosts, Action::Edit, "Reschedule"),
+    (KeyView::ScheduledPosts, Action::Delete, "Cancel the post"),
+    (KeyView::ThreadPreview, Action::Up, "Scroll up"),
+    (KeyView::ThreadPreview, Action::Down, "Scroll down"),
+    (KeyView::ThreadPreview, Action::Send, "Post the thread"),
+    (KeyView::ThreadPreview, Action::Back, "Back to editing"),
+    (KeyView::Conversations, Action::Open, "Open conversation"),
+    (
+        KeyView::Conversation,
+        Action::Up,
+        "Scroll to older messages",
+    ),
+    (
+        KeyView::Conversation,
+        Action::Down,
+        "Scroll to newer messages",
+    ),
+    (KeyView::Filters, Action::New, "New filter"),
+    (KeyView::Filters, Action::Open, "Keywords"),
+    (KeyView::FilterKeywords, Action::New, "Add"),
+    (KeyView::Lists, Action::New, "New list"),
+    (KeyView::Lists, Action::Open, "Members"),
+    (KeyView::ListMembers, Action::New, "Add by handle"),
+    (KeyView::ListMembers, Action::Delete, "Remove"),
+    (
+        KeyView::Follows,
+        Action::NextTab,
+        "Switch following/followers",
+    ),
+    (KeyView::Follows, Action::AddToList, "Add marked to a list"),
+    (
+        KeyView::Moderation,
+        Action::NextTab,
+        "Muted, blocked, blocked domains",
+    ),
+    (KeyView::Report, Action::Mark, "Select"),
+    (KeyView::Explore, Action::NextTab, "Hashtags, posts, news"),
+    (KeyView::Explore, Action::Open, "Open a trending post"),
+    (KeyView::Outbox, Action::Send, "Send now"),
+];
+
+/// What `action` does in `view`, for the help overlay and the palette.
+#[must_use]
+pub fn label(view: KeyView, action: Action) -> &'static str {
+    LABELS
+        .iter()
+        .find(|(v, a, _)| *v == view && *a == action)
+        .map_or_else(|| action.description(), |(_, _, label)| label)
+}
+
+/// One key press as bindings see it. Shift is carried by the character (`G`, `!`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 13] = [
+    ("esc", KeyCode::Esc),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("space", KeyCode::Char(' ')),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+impl Key {
+    #[must_use]
+    pub const fn from_event(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(KeyModifiers::ALT),
+        }
+    }
+
+    /// Parse one key: a character (`g`, `G`, `?`), a name (`enter`, `esc`, `up`, `space`,
+    /// `f5`, …), optionally prefixed by `ctrl-` and/or `alt-`.
+    pub fn parse(s: &str) -> std::result::Result<Self, String> {
+        let mut rest = s;
+        let (mut ctrl, mut alt) = (false, false);
+        loop {
+            if let Some(r) = rest.strip_prefix("ctrl-").filter(|r| !r.is_empty()) {
+                ctrl = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("alt-").filter(|r| !r.is_empty()) {
+                alt = true;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => NAMED_KEYS
+                .iter()
+                .find(|(name, _)| *name == rest)
+                .map(|(_, code)| *code)
+                .or_else(|| {
+                    rest.strip_prefix('f')
+                        .and_then(|n| n.parse::<u8>().ok())
+                        .filter(|n| (1..=12).contains(n))
+                        .map(KeyCode::F)
+                })
+                .ok_or_else(|| format!("unknown key `{s}`"))?,
+        };
+        Ok(Self { code, ctrl, alt })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl-")?;
+        }
+        if self.alt {
+            f.write_str("alt-")?;
+        }
+        match self.code {
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::Char(c) if c != ' ' => write!(f, "{c}"),
+            code => f.write_str(
+                NAMED_KEYS
+                    .iter()
+                    .find(|(_, k)| *k == code)
+                    .map_or("?", |(name, _)| name),
+            ),
+        }
+    }
+}
+
+fn parse_sequence(s: &str) -> std::result::Result<Vec<Key>, String> {
+    let keys = s
+        .split_whitespace()
+        .map(Key::parse)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err("empty key sequence".into());
+    }
+    Ok(keys)
+}
+
+fn sequence_text(keys: &[Key]) -> String {
+    keys.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// What the keys pressed so far amount to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    Action(Action),
+    /// The keys start a longer sequence; wait for the next one.
+    Pending,
+    None,
+}
+
+/// Each action of a view with its key sequences, in help-overlay order.
+type Bindings = Vec<(Action, Vec<Vec<Key>>)>;
+
+/// Active bindings of every configurable view.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    views: HashMap<KeyView, Bindings>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut views: HashMap<KeyView, Bindings> = HashMap::new();
+        for (view, action, sequences) in DEFAULTS {
+            let sequences = sequences
+                .iter()
+                .map(|s| parse_sequence(s).expect("default key binding"))
+                .collect();
+            views.entry(*view).or_default().push((*action, sequences));
+        }
+        Self { views }
+    }
+}
+
+impl Keymap {
+    /// Defaults with the `[keys.*]` overrides applied. Unknown views, actions and keys, and
+    /// sequences that clash within a view, are errors. r[keys.config] r[keys.conflict]
+    pub fn from_config(config: &KeysConfig) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (section, actions) in config {
+            let view = KeyView::ALL
+                .into_iter()
+                .find(|v| v.name() == section)
+                .ok_or_else(|| {
+                    MastotuiError::Config(format!("Unknown key section [keys.{section}]"))
+                })?;
+            let bindings = keymap.views.entry(view).or_default();
+            for (name, binding) in actions {
+                let slot = Action::from_name(name)
+                    .and_then(|a| bindings.iter_mut().find(|(b, _)| *b == a))
+                    .ok_or_else(|| {
+                        MastotuiError::Config(format!(
+                            "Unknown action `{name}` in [keys.{section}]"
+                        ))
+                    })?;
+                slot.1 = binding
+                    .sequences()
+                    .iter()
+                    .map(|s| parse_sequence(s))
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|e| MastotuiError::Config(format!("[keys.{section}] {name}: {e}")))?;
+            }
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// A sequence may not equal or start another one in the same view, or the longer one
+    /// could never be typed, even when both belong to the same action. r[keys.conflict]
+    fn check_conflicts(&self) -> Result<()> {
+        for view in KeyView::ALL {
+            let all: Vec<(Action, &[Key])> = self
+                .views
+                .get(&view)
+                .into_iter()
+                .flatten()
+                .flat_map(|(a, seqs)| seqs.iter().map(move |s| (*a, s.as_slice())))
+                .collect();
+            for (i, (a, x)) in all.iter().enumerate() {
+                for (b, y) in &all[i + 1..] {
+                    let (short, long) = if x.len() <= y.len() { (x, y) } else { (y, x) };
+                    if long.starts_with(short) {
+                        return Err(MastotuiError::Config(format!(
+                            "Key conflict in [keys.{}]: `{}` ({}) and `{}` ({})",
+                            view.name(),
+                            sequence_text(x),
+                            a.name(),
+                            sequence_text(y),
+                            b.name()
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Match the keys pressed so far in `view`. r[keys.sequence]
+    #[must_use]
+    pub fn lookup(&self, view: KeyView, pressed: &[Key]) -> Lookup {
+        let mut pending = false;
+        for (action, sequences) in self.views.get(&view).into_iter().flatten() {
+            for seq in sequences {
+                if seq.as_slice() == pressed {
+                    return Lookup::Action(*action);
+                }
+                pending |= seq.starts_with(pressed);
+            }
+        }
+        if pending {
+            Lookup::Pending
+        } else {
+            Lookup::None
+        }
+    }
+
+    /// Every action `view` offers, bound or not, in help-overlay order.
+    pub fn actions(&self, view: KeyView) -> impl Iterator<Item = Action> + '_ {
+        self.views.get(&view).into_iter().flatten().map(|(a, _)| *a)
+    }
+
+    /// The sequences bound to `action` in `view`, e.g. `k, up`; empty when unbound.
+    #[must_use]
+    pub fn keys(&self, view: KeyView, action: Action) -> String {
+        self.views
+            .get(&view)
+            .into_iter()
+            .flatten()
+            .filter(|(a, _)| *a == action)
+            .flat_map(|(_, sequences)| sequences.iter().map(|s| sequence_text(s)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The first sequence bound to `action` in `view`, for footer hints; None when unbound.
+    #[must_use]
+    pub fn key(&self, view: KeyView, action: Action) -> Option<String> {
+        self.views
+            .get(&view)?
+            .iter()
+            .find(|(a, _)| *a == action)?
+            .1
+            .first()
+            .map(|s| sequence_text(s))
+    }
+
+    /// Rows of the help overlay: the keys of each bound action and what it does.
+    /// r[keys.help]
+    #[must_use]
+    pub fn help(&self, view: KeyView) -> Vec<(String, &'static str)> {
+        self.actions(view)
+            .map(|action| (self.keys(view, action), label(view, action)))
+            .filter(|(keys, _)| !keys.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(s: &str) -> Vec<Key> {
+        parse_sequence(s).unwrap()
+    }
+
+    fn config(toml: &str) -> KeysConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    // r[verify keys.config]
+    #[test]
+    fn key_notation_parses_and_prints_back() {
+        for s in [
+            "g",
+            "G",
+            "?",
+            "enter",
+            "esc",
+            "space",
+            "f12",
+            "ctrl-r",
+            "ctrl-alt-x",
+        ] {
+            assert_eq!(Key::parse(s).unwrap().to_string(), s);
+        }
+        assert!(Key::parse("hyper-x").is_err());
+        assert!(Key::parse("f13").is_err());
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(Key::from_event(event), Key::parse("G").unwrap());
+    }
+
+    // r[verify keys.config]
+    #[test]
+    fn config_overrides_only_the_named_actions() {
+        let keymap = Keymap::from_config(&config(
+            "[timeline]\nrefresh = \"ctrl-r\"\ndown = [\"j\", \"ctrl-n\"]\n\n\
+             [toot_detail]\nreport = []\n",
+        ))
+        .unwrap();
+        let t = KeyView::Timeline;
+        assert_eq!(
+            keymap.lookup(t, &keys("ctrl-r")),
+            Lookup::Action(Action::Refresh)
+        );
+        assert_eq!(keymap.lookup(t, &keys("r")), Lookup::None);
+        assert_eq!(
+            keymap.lookup(t, &keys("ctrl-n")),
+            Lookup::Action(Action::Down)
+        );
+        assert_eq!(keymap.lookup(t, &keys("down")), Lookup::None);
+        assert_eq!(
+            keymap.lookup(t, &keys("m")),
+            Lookup::Action(Action::LoadMore)
+        );
+        assert_eq!(keymap.lookup(KeyView::TootDetail, &keys("!")), Lookup::None);
+
+        for (toml, error) in [
+            ("[home]\nquit = \"q\"\n", "[keys.home]"),
+            ("[timeline]\nwarp = \"x\"\n", "`warp`"),
+            ("[instance_info]\nboost = \"x\"\n", "`boost`"),
+            ("[timeline]\nquit = \"hyper-q\"\n", "unknown key `hyper-q`"),
+        ] {
+            let err = Keymap::from_config(&config(toml)).unwrap_err();
+            assert!(err.to_string().contains(error), "{err}");
+        }
+    }
+
+    // r[verify keys.config]
+    #[test]
+    fn every_view_has_a_section_with_help_and_back() {
+        let keymap = Keymap::from_config(&config(
+            "[follows]\nmark = \"m\"\nload_more = \"ctrl-m\"\n\n[outbox]\nsend = \"s\"\n",
+        ))
+        .unwrap();
+        assert_eq!(
+            keymap.lookup(KeyView::Follows, &keys("m")),
+            Lookup::Action(Action::Mark)
+        );
+        assert_eq!(
+            keymap.lookup(KeyView::Outbox, &keys("s")),
+            Lookup::Action(Action::Send)
+        );
+        for view in KeyView::ALL {
+            assert!(keymap.actions(view).any(|a| a == Action::Help), "{view:?}");
+            let back = if view == KeyView::Timeline {
+                Action::Quit
+            } else {
+                Action::Back
+            };
+            assert!(keymap.actions(view).any(|a| a == back), "{view:?}");
+        }
+        assert_eq!(label(KeyView::Lists, Action::Open), "Members");
+        assert_eq!(label(KeyView::Timeline, Action::Open), "Open toot");
+    }
+
+    // r[verify keys.sequence]
+    #[test]
+    fn multi_key_sequences_wait_for_the_next_key() {
+        let keymap = Keymap::default();
+        let t = KeyView::Timeline;
+        assert_eq!(keymap.lookup(t, &keys("g")), Lookup::Pending);
+        assert_eq!(keymap.lookup(t, &keys("g g")), Lookup::Action(Action::Top));
+        assert_eq!(keymap.lookup(t, &keys("g j")), Lookup::None);
+        assert_eq!(keymap.lookup(t, &keys("G")), Lookup::Action(Action::Bottom));
+    }
+
+    // r[verify keys.conflict]
+    #[test]
+    fn clashing_bindings_are_rejected_at_load() {
+        assert!(Keymap::from_config(&KeysConfig::new()).is_ok());
+        for (toml, error) in [
+            (
+                "[timeline]\nrefresh = \"m\"\n",
+                "Key conflict in [keys.timeline]: `m` (refresh) and `m` (load_more)",
+            ),
+            ("[timeline]\nquit = \"g\"\n", "`g g` (top) and `g` (quit)"),
+            (
+                "[toot_detail]\nboost = [\"x\", \"x\"]\n",
+                "`x` (boost) and `x` (boost)",
+            ),
+            (
+                "[timeline]\ntop = [\"g\", \"g g\"]\n",
+                "`g` (top) and `g g` (top)",
+            ),
+        ] {
+            let err = Keymap::from_config(&config(toml)).unwrap_err();
+            assert!(err.to_string().contains(error), "{err}");
+        }
+        // The same key in two views is fine.
+        assert!(Keymap::from_config(&config("[toot_detail]\nreply = \"p\"\n")).is_ok());
+    }
+
+    // r[verify keys.help]
+    #[test]
+    fn help_lists_the_active_bindings() {
+        let keymap =
+            Keymap::from_config(&config("[timeline]\nup = [\"k\", \"ctrl-p\"]\nquit = []\n"))
+                .unwrap();
+        let help = keymap.help(KeyView::Timeline);
+        assert_eq!(help[0], ("k, ctrl-p".to_string(), "Move up"));
+        assert!(help.contains(&("g g".to_string(), "Go to the newest post")));
+        assert!(!help.iter().any(|(_, d)| *d == "Quit"));
+        assert_eq!(
+            keymap.key(KeyView::Timeline, Action::Up).as_deref(),
+            Some("k")
+        );
+        assert_eq!(keymap.key(KeyView::Timeline, Action::Quit), None);
+    }
+}
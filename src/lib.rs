@@ -9,7 +9,17 @@
 
 pub mod api;
 pub mod app;
+pub mod cache;
 pub mod config;
 pub mod credential;
 pub mod error;
+pub mod html;
+pub mod keymap;
+pub mod mute;
+pub mod outbox;
+pub mod palette;
+pub mod schedule;
+#[cfg(test)]
+mod test_env;
+pub mod thread;
 pub mod tui;
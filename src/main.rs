@@ -1,6 +1,9 @@
 //! mastotui — TUI client for Mastodon. r[config.first-run]
 
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+};
+use crossterm::execute;
 use ratatui::prelude::CrosstermBackend;
 use ratatui::Terminal;
 
@@ -9,8 +12,10 @@ use mastotui::error::Result;
 
 fn main() -> Result<()> {
     let mut app = App::new()?;
-    ratatui::run(|terminal| run_app(terminal, &mut app))
-        .map_err(mastotui::error::MastotuiError::Io)?;
+    let result = ratatui::run(|terminal| run_app(terminal, &mut app));
+    // r[mouse.capture]: hand the mouse back to the terminal however the loop ended.
+    let _ = execute!(std::io::stdout(), DisableMouseCapture);
+    result.map_err(mastotui::error::MastotuiError::Io)?;
     Ok(())
 }
 
@@ -18,13 +23,33 @@ fn run_app(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     app: &mut App,
 ) -> std::io::Result<()> {
+    let mut mouse_captured = false;
     loop {
         app.ensure_timeline_loaded()
             .map_err(std::io::Error::other)?;
+        app.step_bulk();
+        app.save_markers(false);
+        app.check_follow_requests();
         terminal.draw(|f| app.draw(f))?;
+        // r[toot.optimistic]: queued writes go out after the frame that shows them as done.
+        app.flush_outbox();
+        // r[mouse.capture]
+        if app.wants_mouse() != mouse_captured {
+            mouse_captured = !mouse_captured;
+            if mouse_captured {
+                execute!(std::io::stdout(), EnableMouseCapture)?;
+            } else {
+                execute!(std::io::stdout(), DisableMouseCapture)?;
+            }
+        }
 
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                if app.handle_mouse(mouse).map_err(std::io::Error::other)? {
+                    break;
+                }
+            } else if let Event::Key(key) = event {
                 if key.kind != KeyEventKind::Press {
                     continue;
                 }
@@ -40,5 +65,6 @@ fn run_app(
             }
         }
     }
+    app.save_markers(true);
     Ok(())
 }
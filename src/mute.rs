@@ -0,0 +1,303 @@
+//! Client-side mute rules from config.toml, applied on top of server filters.
+//! r[mute.config] r[mute.rules]
+//!
+//! Rules are `[[mute_rules]]` tables with a `kind` and optional `timelines`:
+//!
+//! ```toml
+//! [[mute_rules]]
+//! kind = "regex"
+//! pattern = "(?i)\\bcrypto\\b"
+//! timelines = ["public", "local"]
+//!
+//! [[mute_rules]]
+//! kind = "boosts_from"
+//! accounts = ["loud@example.com"]
+//!
+//! [[mute_rules]]
+//! kind = "replies_to_unfollowed"
+//!
+//! [[mute_rules]]
+//! kind = "missing_alt_text"
+//! ```
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::api::Status;
+use crate::html::strip_html;
+
+/// One rule as written in the config file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MuteRule {
+    #[serde(flatten)]
+    pub kind: MuteKind,
+    /// Timelines the rule applies to (`home`, `local`, `public`, `list` or `list:<title>`).
+    /// Empty means every timeline.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timelines: Vec<String>,
+}
+
+/// What a rule matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MuteKind {
+    /// Status text (HTML stripped) matches the regular expression.
+    Regex { pattern: String },
+    /// Boosts made by any of these accounts (`user` or `user@domain`).
+    BoostsFrom { accounts: Vec<String> },
+    /// Replies to accounts the logged-in user does not follow.
+    RepliesToUnfollowed,
+    /// Posts with at least one media attachment lacking a description.
+    MissingAltText,
+}
+
+/// Facts about the current session a rule may need.
+#[derive(Debug, Clone, Copy)]
+pub struct MuteContext<'a> {
+    /// Rule key of the current timeline, e.g. `home` or `list:Friends`.
+    pub timeline: &'a str,
+    /// Logged-in account id, if known.
+    pub me: Option<&'a str>,
+    /// Account id to "I follow them", from GET /api/v1/accounts/relationships.
+    pub following: &'a HashMap<String, bool>,
+}
+
+enum Matcher {
+    Regex(Regex),
+    BoostsFrom(Vec<String>),
+    RepliesToUnfollowed,
+    MissingAltText,
+}
+
+/// Compiled rules, ready to evaluate against each status.
+#[derive(Default)]
+pub struct MuteRules {
+    rules: Vec<(Matcher, Vec<String>)>,
+    /// 1-based numbers of config rules that did not compile and are not applied.
+    skipped: Vec<usize>,
+}
+
+impl MuteRules {
+    /// Compile config rules. A rule with an invalid regex is skipped and its 1-based number
+    /// kept for [`Self::skipped`], so one typo does not stop the client from starting.
+    #[must_use]
+    pub fn compile(rules: &[MuteRule]) -> Self {
+        let mut compiled = Self::default();
+        for (i, rule) in rules.iter().enumerate() {
+            let matcher = match &rule.kind {
+                MuteKind::Regex { pattern } => {
+                    let Ok(re) = Regex::new(pattern) else {
+                        compiled.skipped.push(i + 1);
+                        continue;
+                    };
+                    Matcher::Regex(re)
+                }
+                MuteKind::BoostsFrom { accounts } => Matcher::BoostsFrom(
+                    accounts
+                        .iter()
+                        .map(|a| a.trim_start_matches('@').to_lowercase())
+                        .collect(),
+                ),
+                MuteKind::RepliesToUnfollowed => Matcher::RepliesToUnfollowed,
+                MuteKind::MissingAltText => Matcher::MissingAltText,
+            };
+            compiled.rules.push((matcher, rule.timelines.clone()));
+        }
+        compiled
+    }
+
+    /// 1-based numbers of the rules `compile` skipped as invalid.
+    #[must_use]
+    pub fn skipped(&self) -> &[usize] {
+        &self.skipped
+    }
+
+    /// True if any rule needs follow relationships for reply targets.
+    #[must_use]
+    pub fn needs_relationships(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|(m, _)| matches!(m, Matcher::RepliesToUnfollowed))
+    }
+
+    /// Why the first matching rule hides `status` in this context, or None to show it.
+    #[must_use]
+    pub fn reason(&self, status: &Status, ctx: &MuteContext) -> Option<String> {
+        self.rules
+            .iter()
+            .filter(|(_, timelines)| applies_to(timelines, ctx.timeline))
+            .find_map(|(m, _)| m.reason(status, ctx))
+    }
+}
+
+impl Matcher {
+    fn reason(&self, status: &Status, ctx: &MuteContext) -> Option<String> {
+        let shown = status.reblog.as_deref().unwrap_or(status);
+        match self {
+            Self::Regex(re) => re
+                .is_match(&strip_html(&shown.content))
+                .then(|| format!("matches /{}/", re.as_str())),
+            Self::BoostsFrom(accounts) => {
+                let booster = status.account.handle().to_lowercase();
+                (status.reblog.is_some() && accounts.contains(&booster))
+                    .then(|| format!("boost by @{}", status.account.handle()))
+            }
+            Self::RepliesToUnfollowed => {
+                let target = shown.in_reply_to_account_id.as_deref()?;
+                let unfollowed = target != shown.account.id
+                    && Some(target) != ctx.me
+                    && ctx.following.get(target) == Some(&false);
+                unfollowed.then(|| "reply to someone you don't follow".to_string())
+            }
+            Self::MissingAltText => shown
+                .media_attachments
+                .iter()
+                .any(|m| {
+                    m.description
+                        .as_deref()
+                        .map_or(true, |d| d.trim().is_empty())
+                })
+                .then(|| "media without alt text".to_string()),
+        }
+    }
+}
+
+fn applies_to(timelines: &[String], timeline: &str) -> bool {
+    timelines.is_empty()
+        || timelines
+            .iter()
+            .any(|t| t == timeline || (t == "list" && timeline.starts_with("list:")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_json(json: serde_json::Value) -> serde_json::Value {
+        let mut base = serde_json::json!({
+            "id": "1",
+            "uri": "https://example.com/statuses/1",
+            "content": "<p>Buy crypto now</p>",
+            "account": { "id": "10", "display_name": "Ann", "username": "ann", "acct": "ann" },
+            "created_at": "2025-06-01T12:00:00.000Z"
+        });
+        if let (Some(base), serde_json::Value::Object(extra)) = (base.as_object_mut(), json) {
+            base.extend(extra);
+        }
+        base
+    }
+
+    fn status(json: serde_json::Value) -> Status {
+        serde_json::from_value(status_json(json)).unwrap()
+    }
+
+    fn ctx<'a>(timeline: &'a str, following: &'a HashMap<String, bool>) -> MuteContext<'a> {
+        MuteContext {
+            timeline,
+            me: Some("99"),
+            following,
+        }
+    }
+
+    // r[verify mute.config]
+    #[test]
+    fn rules_parse_from_toml_and_bad_regex_is_skipped() {
+        #[derive(Deserialize)]
+        struct Doc {
+            mute_rules: Vec<MuteRule>,
+        }
+        let doc: Doc = toml::from_str(
+            r#"
+            [[mute_rules]]
+            kind = "regex"
+            pattern = "(?i)crypto"
+            timelines = ["public"]
+
+            [[mute_rules]]
+            kind = "missing_alt_text"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(doc.mute_rules.len(), 2);
+        assert_eq!(doc.mute_rules[1].kind, MuteKind::MissingAltText);
+        assert!(doc.mute_rules[1].timelines.is_empty());
+        let bad = MuteRule {
+            kind: MuteKind::Regex {
+                pattern: "(".into(),
+            },
+            timelines: vec![],
+        };
+        let rules = MuteRules::compile(&[doc.mute_rules[0].clone(), bad]);
+        assert_eq!(rules.skipped(), [2]);
+        let following = HashMap::new();
+        let s = status(serde_json::json!({}));
+        assert!(rules.reason(&s, &ctx("public", &following)).is_some());
+    }
+
+    // r[verify mute.rules]
+    #[test]
+    fn regex_rule_respects_timelines() {
+        let rules = MuteRules::compile(&[MuteRule {
+            kind: MuteKind::Regex {
+                pattern: "(?i)CRYPTO".into(),
+            },
+            timelines: vec!["public".into(), "list".into()],
+        }]);
+        let following = HashMap::new();
+        let s = status(serde_json::json!({}));
+        assert!(rules.reason(&s, &ctx("public", &following)).is_some());
+        assert!(rules.reason(&s, &ctx("list:Friends", &following)).is_some());
+        assert!(rules.reason(&s, &ctx("home", &following)).is_none());
+    }
+
+    // r[verify mute.rules]
+    #[test]
+    fn boost_reply_and_alt_text_rules() {
+        let rules = MuteRules::compile(&[
+            MuteRule {
+                kind: MuteKind::BoostsFrom {
+                    accounts: vec!["@Bob@remote.example".into()],
+                },
+                timelines: vec![],
+            },
+            MuteRule {
+                kind: MuteKind::RepliesToUnfollowed,
+                timelines: vec![],
+            },
+            MuteRule {
+                kind: MuteKind::MissingAltText,
+                timelines: vec![],
+            },
+        ]);
+        assert!(rules.needs_relationships());
+        let following = HashMap::from([("20".to_string(), false), ("21".to_string(), true)]);
+        let c = ctx("home", &following);
+
+        let boost = status(serde_json::json!({
+            "account": { "id": "11", "display_name": "Bob", "username": "bob", "acct": "bob@remote.example" },
+            "content": "",
+            "reblog": status_json(serde_json::json!({}))
+        }));
+        assert_eq!(
+            rules.reason(&boost, &c).as_deref(),
+            Some("boost by @bob@remote.example")
+        );
+
+        let reply = status(serde_json::json!({ "in_reply_to_account_id": "20" }));
+        assert!(rules.reason(&reply, &c).unwrap().contains("don't follow"));
+        let reply = status(serde_json::json!({ "in_reply_to_account_id": "21" }));
+        assert!(rules.reason(&reply, &c).is_none());
+        let unknown = status(serde_json::json!({ "in_reply_to_account_id": "22" }));
+        assert!(rules.reason(&unknown, &c).is_none());
+
+        let media = status(serde_json::json!({
+            "media_attachments": [{ "description": "a cat" }, { "description": "" }]
+        }));
+        assert_eq!(
+            rules.reason(&media, &c).as_deref(),
+            Some("media without alt text")
+        );
+    }
+}
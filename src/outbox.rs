@@ -0,0 +1,370 @@
+//! Persistent queue of write actions (outbox) under the XDG data dir (e.g.
+//! ~/.local/share/mastotui/outbox/<instance host>.json). r[outbox.queue]
+//!
+//! Each action gets an idempotency key when queued and keeps it across retries, so a post
+//! the server received before the connection dropped is not created twice. r[outbox.idempotent]
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{Relationship, Status, Visibility};
+use crate::cache::{create_private_dir, write_private};
+use crate::error::{MastotuiError, Result};
+
+/// Longest wait between retries of one action.
+pub const MAX_BACKOFF_SECS: i64 = 600;
+
+/// Returns the XDG data directory for mastotui (e.g. ~/.local/share/mastotui).
+pub fn data_dir() -> Result<PathBuf> {
+    directories::ProjectDirs::from("org", "mastotui", "mastotui")
+        .map(|d| d.data_dir().to_path_buf())
+        .ok_or_else(|| MastotuiError::Config("Could not determine data directory".into()))
+}
+
+/// A write to send to the server. `on` is the state asked for (boost or unboost, …).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxAction {
+    /// A new post or reply.
+    Post {
+        text: String,
+        in_reply_to_id: Option<String>,
+        visibility: Option<Visibility>,
+    },
+    Boost {
+        status_id: String,
+        on: bool,
+    },
+    Favourite {
+        status_id: String,
+        on: bool,
+    },
+    Bookmark {
+        status_id: String,
+        on: bool,
+    },
+    Follow {
+        account_id: String,
+        handle: String,
+        on: bool,
+    },
+}
+
+impl OutboxAction {
+    /// Short description for the outbox view and messages.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        let toggle = |on: bool, yes: &str, no: &str| if on { yes } else { no }.to_string();
+        match self {
+            Self::Post {
+                text,
+                in_reply_to_id,
+                ..
+            } => {
+                let first = text.lines().next().unwrap_or_default();
+                let mut preview: String = first.chars().take(40).collect();
+                if preview.len() < text.len() {
+                    preview.push('…');
+                }
+                let kind = if in_reply_to_id.is_some() {
+                    "Reply"
+                } else {
+                    "Post"
+                };
+                format!("{kind}: {preview}")
+            }
+            Self::Boost { on, .. } => toggle(*on, "Boost", "Unboost"),
+            Self::Favourite { on, .. } => toggle(*on, "Favourite", "Unfavourite"),
+            Self::Bookmark { on, .. } => toggle(*on, "Bookmark", "Remove bookmark"),
+            Self::Follow { handle, on, .. } => {
+                format!("{} @{handle}", toggle(*on, "Follow", "Unfollow"))
+            }
+        }
+    }
+}
+
+/// What the server returned for an action, used to replace the optimistic local copy.
+/// r[outbox.reconcile]
+#[derive(Debug, Clone)]
+pub enum Performed {
+    Status(Box<Status>),
+    Relationship(Relationship),
+}
+
+/// A queued action with its retry state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxItem {
+    /// Sent as the `Idempotency-Key` header on every attempt.
+    pub key: String,
+    pub action: OutboxAction,
+    /// Failed attempts so far.
+    #[serde(default)]
+    pub attempts: u32,
+    /// RFC 3339 time of the next automatic attempt (None = as soon as possible).
+    #[serde(default)]
+    pub next_attempt_at: Option<String>,
+    /// Error of the last attempt.
+    #[serde(default)]
+    pub error: Option<String>,
+    /// The server rejected the action; it is kept for the user to retry or discard but
+    /// not retried automatically.
+    #[serde(default)]
+    pub failed: bool,
+}
+
+impl OutboxItem {
+    /// Due for an automatic attempt at `now`.
+    #[must_use]
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !self.failed
+            && self.next_attempt_at.as_deref().map_or(true, |at| {
+                DateTime::parse_from_rfc3339(at).map_or(true, |at| at <= now)
+            })
+    }
+}
+
+/// Queued actions of one account, oldest first. r[outbox.queue]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Outbox {
+    pub items: Vec<OutboxItem>,
+}
+
+impl Outbox {
+    /// Load an account's outbox; a missing file gives an empty outbox. A file that does not
+    /// parse is renamed to `<host>.json.bad` and reported, so the queued writes are not lost
+    /// by the next save.
+    pub fn load(account_key: &str) -> Result<Self> {
+        let path = data_dir()?
+            .join("outbox")
+            .join(format!("{account_key}.json"));
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&json).map_err(|e| {
+            let bad = path.with_extension("json.bad");
+            match std::fs::rename(&path, &bad) {
+                Ok(()) => MastotuiError::Config(format!(
+                    "Outbox could not be read ({e}); kept as {}",
+                    bad.display()
+                )),
+                Err(rename) => MastotuiError::Config(format!(
+                    "Outbox could not be read ({e}) nor set aside ({rename})"
+                )),
+            }
+        })
+    }
+
+    /// Write an account's outbox to disk, private to the user: to a temporary file first,
+    /// then renamed over the old one so a crash mid-write never leaves a truncated queue.
+    pub fn save(&self, account_key: &str) -> Result<()> {
+        let dir = data_dir()?.join("outbox");
+        create_private_dir(&dir)?;
+        let json = serde_json::to_string(self)
+            .map_err(|e| MastotuiError::Config(format!("Failed to serialize outbox: {e}")))?;
+        let tmp = dir.join(format!("{account_key}.json.tmp"));
+        write_private(&tmp, &json)?;
+        std::fs::rename(tmp, dir.join(format!("{account_key}.json")))?;
+        Ok(())
+    }
+
+    /// Queue an action with a fresh idempotency key; returns the key.
+    pub fn push(&mut self, action: OutboxAction) -> String {
+        let key = idempotency_key();
+        self.items.push(OutboxItem {
+            key: key.clone(),
+            action,
+            attempts: 0,
+            next_attempt_at: None,
+            error: None,
+            failed: false,
+        });
+        key
+    }
+
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&OutboxItem> {
+        self.items.iter().find(|i| i.key == key)
+    }
+
+    /// Drop an item (sent, or discarded by the user).
+    pub fn remove(&mut self, key: &str) -> Option<OutboxItem> {
+        let index = self.items.iter().position(|i| i.key == key)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Oldest item due for an automatic attempt. Actions are sent in order, so nothing is
+    /// due while an earlier pending item is waiting for its retry.
+    #[must_use]
+    pub fn next_due(&self, now: DateTime<Utc>) -> Option<&OutboxItem> {
+        let first = self.items.iter().find(|i| !i.failed)?;
+        first.is_due(now).then_some(first)
+    }
+
+    /// Record a failed attempt: retryable errors back off, others mark the item failed.
+    /// r[outbox.retry]
+    pub fn record_failure(&mut self, key: &str, error: &MastotuiError, now: DateTime<Utc>) {
+        let Some(item) = self.items.iter_mut().find(|i| i.key == key) else {
+            return;
+        };
+        item.attempts += 1;
+        item.error = Some(error.to_string());
+        if is_transient(error) {
+            let at = now + backoff(item.attempts);
+            item.next_attempt_at = Some(at.to_rfc3339());
+        } else {
+            item.failed = true;
+            item.next_attempt_at = None;
+        }
+    }
+
+    /// Make an item due now again (and clear a failure) after the user asks to retry.
+    pub fn retry_now(&mut self, key: &str) {
+        if let Some(item) = self.items.iter_mut().find(|i| i.key == key) {
+            item.failed = false;
+            item.next_attempt_at = None;
+        }
+    }
+
+    /// Items not yet given up on.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.items.iter().filter(|i| !i.failed).count()
+    }
+}
+
+/// Whether an error may go away by itself: no connection, timeouts, rate limits and server
+/// errors. Anything else the server said no to will not change on retry.
+#[must_use]
+pub const fn is_transient(error: &MastotuiError) -> bool {
+    match error {
+        MastotuiError::Reqwest(_)
+        | MastotuiError::Io(_)
+        | MastotuiError::RateLimited { .. }
+        | MastotuiError::ServerUnavailable => true,
+        MastotuiError::Api { status, .. } => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
+
+/// Wait before the next attempt after `attempts` failures: 5s, 10s, 20s, … up to
+/// `MAX_BACKOFF_SECS`.
+#[must_use]
+pub fn backoff(attempts: u32) -> chrono::Duration {
+    let secs = 5_i64.saturating_mul(1_i64 << attempts.saturating_sub(1).min(16));
+    chrono::Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+}
+
+/// A key unique to this action: time, process and a counter.
+#[must_use]
+pub fn idempotency_key() -> String {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("mastotui-{nanos:x}-{:x}-{n:x}", std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boost(id: &str) -> OutboxAction {
+        OutboxAction::Boost {
+            status_id: id.into(),
+            on: true,
+        }
+    }
+
+    // r[verify outbox.retry]
+    #[test]
+    fn transient_failures_back_off_and_rejections_stop_retrying() {
+        let now = Utc::now();
+        let mut outbox = Outbox::default();
+        let first = outbox.push(boost("1"));
+        let second = outbox.push(boost("2"));
+        assert_ne!(first, second);
+        assert_eq!(outbox.next_due(now).unwrap().key, first);
+
+        let offline = MastotuiError::Io(std::io::Error::other("unreachable"));
+        outbox.record_failure(&first, &offline, now);
+        outbox.record_failure(&first, &offline, now);
+        let item = outbox.get(&first).unwrap();
+        assert_eq!(item.attempts, 2);
+        assert!(!item.failed);
+        assert!(
+            outbox.next_due(now).is_none(),
+            "later items wait their turn"
+        );
+        assert!(outbox
+            .next_due(now + chrono::Duration::seconds(10))
+            .is_some());
+
+        let rejected = MastotuiError::from_response(422, r#"{"error":"Validation failed"}"#);
+        outbox.record_failure(&first, &rejected, now);
+        assert!(outbox.get(&first).unwrap().failed);
+        assert_eq!(outbox.next_due(now).unwrap().key, second);
+        assert_eq!(outbox.pending(), 1);
+
+        outbox.retry_now(&first);
+        assert_eq!(outbox.next_due(now).unwrap().key, first);
+        assert!(outbox.remove(&first).is_some());
+        assert_eq!(outbox.items.len(), 1);
+
+        assert_eq!(backoff(1).num_seconds(), 5);
+        assert_eq!(backoff(3).num_seconds(), 20);
+        assert_eq!(backoff(40).num_seconds(), MAX_BACKOFF_SECS);
+    }
+
+    // r[verify outbox.queue] r[verify outbox.idempotent]
+    #[test]
+    fn outbox_round_trips_through_disk_with_its_keys() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mut outbox = Outbox::default();
+        let key = outbox.push(OutboxAction::Post {
+            text: "hello\nworld".into(),
+            in_reply_to_id: Some("9".into()),
+            visibility: Some(Visibility::Unlisted),
+        });
+        outbox.save("mastodon.example").unwrap();
+        let loaded = Outbox::load("mastodon.example").unwrap();
+        assert_eq!(loaded.items.len(), 1);
+        assert_eq!(loaded.items[0].key, key);
+        assert_eq!(loaded.items[0].action, outbox.items[0].action);
+        assert_eq!(loaded.items[0].action.describe(), "Reply: hello…");
+        assert!(!temp
+            .path()
+            .join("mastotui/outbox/mastodon.example.json.tmp")
+            .exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let dir = data_dir().unwrap().join("outbox");
+            let mode = |p: PathBuf| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(dir.join("mastodon.example.json")), 0o600);
+            assert_eq!(mode(dir), 0o700);
+        }
+    }
+
+    #[test]
+    fn unreadable_outbox_is_reported_and_set_aside() {
+        let temp = tempfile::tempdir().unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        assert!(Outbox::load("mastodon.example").unwrap().items.is_empty());
+        let dir = data_dir().unwrap().join("outbox");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("mastodon.example.json"), "{\"items\": [").unwrap();
+
+        let err = Outbox::load("mastodon.example").unwrap_err();
+        assert!(err.to_string().contains("mastodon.example.json.bad"));
+        assert!(!dir.join("mastodon.example.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("mastodon.example.json.bad")).unwrap(),
+            "{\"items\": ["
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! `:` command palette: the current view's actions (bound to a key or not) and commands
+//! that work from any view, narrowed by a fuzzy match as the user types.
+//! r[palette.open] r[palette.fuzzy] r[palette.commands]
+
+use crate::app::TimelineSelection;
+use crate::keymap::Action;
+
+/// Something the palette can run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Action(Action),
+    /// Switch to this timeline (Home, Local, Public or a list).
+    Timeline(TimelineSelection),
+    /// The following/followers browser with its search open.
+    SearchFollows,
+    /// Compose a new toot, from views that have no key for it.
+    Compose,
+    LogOut,
+    /// Sign in to another account; the current one's token stays stored.
+    SwitchAccount,
+}
+
+impl Command {
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Action(action) => action.description().to_string(),
+            Self::Timeline(TimelineSelection::List { title, .. }) => format!("List: {title}"),
+            Self::Timeline(timeline) => format!("Timeline: {}", timeline.label()),
+            Self::SearchFollows => "Search following/followers".to_string(),
+            Self::Compose => Action::Compose.description().to_string(),
+            Self::LogOut => "Log out".to_string(),
+            Self::SwitchAccount => "Switch account".to_string(),
+        }
+    }
+}
+
+/// An open palette: what has been typed and which match is selected.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    pub input: String,
+    pub selected: usize,
+    /// Every command on offer, with its key in the current view (empty when unbound).
+    commands: Vec<(Command, String)>,
+}
+
+impl Palette {
+    #[must_use]
+    pub const fn new(commands: Vec<(Command, String)>) -> Self {
+        Self {
+            input: String::new(),
+            selected: 0,
+            commands,
+        }
+    }
+
+    /// Commands matching the input, best first; all of them, in order, when it is empty.
+    /// r[palette.fuzzy]
+    #[must_use]
+    pub fn matches(&self) -> Vec<&(Command, String)> {
+        let mut scored: Vec<(i32, &(Command, String))> = self
+            .commands
+            .iter()
+            .filter_map(|c| fuzzy_score(&self.input, &c.0.label()).map(|score| (score, c)))
+            .collect();
+        scored.sort_by_key(|(score, _)| -score);
+        scored.into_iter().map(|(_, c)| c).collect()
+    }
+
+    #[must_use]
+    pub fn selected_command(&self) -> Option<Command> {
+        self.matches()
+            .get(self.selected)
+            .map(|(command, _)| command.clone())
+    }
+}
+
+/// How well `query` matches `text`: its characters must appear in order (case and spaces
+/// ignored), and runs of adjacent characters and matches at word starts score higher.
+/// None when they do not all appear. r[palette.fuzzy]
+#[must_use]
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let i = (next..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if last.is_some_and(|l| l + 1 == i) {
+            score += 4;
+        }
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        last = Some(i);
+        next = i + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify palette.fuzzy]
+    #[test]
+    fn fuzzy_match_needs_the_letters_in_order_and_prefers_word_starts() {
+        assert!(fuzzy_score("lgo", "Log out").is_some());
+        assert!(fuzzy_score("LOGOUT", "Log out").is_some());
+        assert!(fuzzy_score("olg", "Log out").is_none());
+        assert_eq!(fuzzy_score("", "Anything"), Some(0));
+        assert!(fuzzy_score("lo", "Log out") > fuzzy_score("lo", "Follow requests"));
+        assert!(fuzzy_score("sa", "Switch account") > fuzzy_score("sa", "Search"));
+    }
+
+    // r[verify palette.fuzzy] r[verify palette.commands]
+    #[test]
+    fn palette_lists_best_matches_first_and_keeps_order_when_empty() {
+        let mut palette = Palette::new(vec![
+            (Command::Action(Action::Refresh), "r".to_string()),
+            (Command::Timeline(TimelineSelection::Local), String::new()),
+            (
+                Command::Timeline(TimelineSelection::List {
+                    id: "7".into(),
+                    title: "Friends".into(),
+                }),
+                String::new(),
+            ),
+            (Command::LogOut, String::new()),
+        ]);
+        assert_eq!(palette.matches().len(), 4);
+        assert_eq!(
+            palette.selected_command(),
+            Some(Command::Action(Action::Refresh))
+        );
+        palette.input = "fri".into();
+        assert_eq!(palette.matches().len(), 1);
+        assert_eq!(palette.matches()[0].0.label(), "List: Friends");
+        palette.input = "lo".into();
+        assert_eq!(palette.selected_command(), Some(Command::LogOut));
+        palette.input = "zzz".into();
+        assert!(palette.selected_command().is_none());
+    }
+}
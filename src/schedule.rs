@@ -0,0 +1,184 @@
+//! Parsing of scheduled-post times typed in Compose. r[toot.schedule.validation]
+//!
+//! Accepts relative offsets (`in 30m`, `+2h`, `in 1 day`), clock times (`18:30`, `today 18:30`,
+//! `tomorrow 9:00`) and absolute dates (`2025-06-01 09:00`, RFC 3339). Times without a zone are
+//! local time.
+
+use chrono::{
+    DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc,
+};
+
+use crate::error::{MastotuiError, Result};
+
+/// Mastodon rejects `scheduled_at` unless it is more than this many minutes in the future.
+pub const MIN_SCHEDULE_MINUTES: i64 = 5;
+
+/// Parse user input into a UTC instant and check it against the server's minimum lead time.
+pub fn parse_schedule(input: &str, now: DateTime<Local>) -> Result<DateTime<Utc>> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Err(MastotuiError::Validation(
+            "Enter a time to schedule.".into(),
+        ));
+    }
+    let when = parse_relative(&input, now)
+        .or_else(|| parse_day_and_time(&input, now))
+        .or_else(|| parse_absolute(&input))
+        .ok_or_else(|| {
+            MastotuiError::Validation(format!(
+                "Unrecognised time \"{input}\" (try \"in 30m\", \"tomorrow 9:00\" or \"2025-06-01 09:00\")."
+            ))
+        })?;
+    if when <= now.with_timezone(&Utc) + Duration::minutes(MIN_SCHEDULE_MINUTES) {
+        return Err(MastotuiError::Validation(format!(
+            "Scheduled time must be more than {MIN_SCHEDULE_MINUTES} minutes from now."
+        )));
+    }
+    Ok(when)
+}
+
+/// Format a UTC instant as Mastodon expects for `scheduled_at` (ISO 8601).
+#[must_use]
+pub fn to_api_timestamp(when: DateTime<Utc>) -> String {
+    when.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Render an API timestamp (e.g. a `ScheduledStatus::scheduled_at`) in local time for display.
+/// Falls back to the raw string if it does not parse.
+#[must_use]
+pub fn format_local(timestamp: &str) -> String {
+    DateTime::parse_from_rfc3339(timestamp).map_or_else(
+        |_| timestamp.to_string(),
+        |t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+/// `in 30m`, `+2h`, `in 1 day`, `in 90 minutes`. Offsets too large to represent yield `None`.
+fn parse_relative(input: &str, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    let rest = input
+        .strip_prefix("in ")
+        .or_else(|| input.strip_prefix('+'))?
+        .trim();
+    let split = rest.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = rest.split_at(split);
+    let amount: i64 = amount.parse().ok()?;
+    let delta = match unit.trim() {
+        "m" | "min" | "mins" | "minute" | "minutes" => TimeDelta::try_minutes(amount)?,
+        "h" | "hr" | "hrs" | "hour" | "hours" => TimeDelta::try_hours(amount)?,
+        "d" | "day" | "days" => TimeDelta::try_days(amount)?,
+        "w" | "week" | "weeks" => TimeDelta::try_weeks(amount)?,
+        _ => return None,
+    };
+    now.with_timezone(&Utc).checked_add_signed(delta)
+}
+
+/// `18:30` (next occurrence), `today 18:30`, `tomorrow 9:00`.
+fn parse_day_and_time(input: &str, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    let (day, time) = match input.split_once(' ') {
+        Some((d @ ("today" | "tomorrow"), t)) => (Some(d), t.trim()),
+        Some(_) => return None,
+        None => (None, input),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+    let today = now.date_naive();
+    let date = if day == Some("tomorrow") || (day.is_none() && time <= now.time()) {
+        today.succ_opt()?
+    } else {
+        today
+    };
+    local_to_utc(date.and_time(time))
+}
+
+/// `2025-06-01 09:00`, `2025-06-01T09:00` (local) or full RFC 3339 with offset.
+fn parse_absolute(input: &str) -> Option<DateTime<Utc>> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(&input.to_uppercase()) {
+        return Some(t.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M", "%Y-%m-%dt%H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(9, 0, 0))
+        })
+        .and_then(local_to_utc)
+}
+
+fn local_to_utc(naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    // r[verify toot.schedule.validation]
+    #[test]
+    fn relative_offsets_parse() {
+        let base = now().with_timezone(&Utc);
+        assert_eq!(
+            parse_schedule("in 30m", now()).unwrap(),
+            base + Duration::minutes(30)
+        );
+        assert_eq!(
+            parse_schedule("+2h", now()).unwrap(),
+            base + Duration::hours(2)
+        );
+        assert_eq!(
+            parse_schedule("in 1 day", now()).unwrap(),
+            base + Duration::days(1)
+        );
+    }
+
+    // r[verify toot.schedule.validation]
+    #[test]
+    fn rejects_times_within_five_minutes() {
+        assert!(matches!(
+            parse_schedule("in 5m", now()),
+            Err(MastotuiError::Validation(_))
+        ));
+        assert!(parse_schedule("in 6m", now()).is_ok());
+        assert!(parse_schedule("2020-01-01 09:00", now()).is_err());
+    }
+
+    #[test]
+    fn huge_relative_offsets_are_rejected_not_panicking() {
+        assert!(parse_schedule("in 99999999999999 w", now()).is_err());
+        assert!(parse_schedule("+9223372036854775807m", now()).is_err());
+        assert!(parse_schedule("in 99999999999999999999 days", now()).is_err());
+    }
+
+    #[test]
+    fn clock_time_rolls_over_to_tomorrow_when_past() {
+        let t = parse_schedule("11:00", now())
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(t.date_naive(), now().date_naive().succ_opt().unwrap());
+        let t = parse_schedule("tomorrow 9:00", now())
+            .unwrap()
+            .with_timezone(&Local);
+        assert_eq!(t.format("%H:%M").to_string(), "09:00");
+    }
+
+    #[test]
+    fn absolute_dates_and_garbage() {
+        assert!(parse_schedule("2025-06-02 09:30", now()).is_ok());
+        assert!(parse_schedule("2025-06-02T09:30:00Z", now()).is_ok());
+        assert!(parse_schedule("next blue moon", now()).is_err());
+        assert!(parse_schedule("", now()).is_err());
+    }
+
+    #[test]
+    fn api_timestamp_is_utc_iso8601() {
+        let t = Utc.with_ymd_and_hms(2025, 6, 1, 12, 30, 0).unwrap();
+        assert_eq!(to_api_timestamp(t), "2025-06-01T12:30:00Z");
+    }
+}
@@ -0,0 +1,37 @@
+//! Process environment for unit tests that touch config, cache or data files.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::credential::{set_store, MemoryStore};
+
+/// Tests share the process environment, so the ones that point the XDG dirs somewhere take
+/// turns, like `SERIAL` in tests/common.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+const XDG_VARS: [&str; 3] = ["XDG_CONFIG_HOME", "XDG_CACHE_HOME", "XDG_DATA_HOME"];
+
+/// Holds `ENV_LOCK` with the XDG config, cache and data dirs pointed at a temporary directory,
+/// and unsets them again when dropped.
+pub struct TestEnv {
+    _lock: MutexGuard<'static, ()>,
+}
+
+/// Point the XDG dirs at `dir` and keep secrets in memory until the returned guard is
+/// dropped, so nothing the test does reaches the developer's own files or keyring.
+pub fn isolate(dir: &Path) -> TestEnv {
+    let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    set_store(Arc::new(MemoryStore::default()));
+    for var in XDG_VARS {
+        std::env::set_var(var, dir);
+    }
+    TestEnv { _lock: lock }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        for var in XDG_VARS {
+            std::env::remove_var(var);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Splitting a long Compose buffer into a numbered chain of posts. r[toot.thread.split]
+//!
+//! A line containing only `---` forces a break. Any segment still over the limit is split at
+//! the last sentence boundary that fits, then at a word boundary, and only as a last resort
+//! mid-word. Each part gets a ` n/N` suffix that is counted against the limit.
+
+/// Line that separates posts explicitly when writing a thread.
+pub const THREAD_SEPARATOR: &str = "---";
+
+/// True if the text contains at least one explicit separator line.
+#[must_use]
+pub fn has_separator(text: &str) -> bool {
+    text.lines().any(|l| l.trim() == THREAD_SEPARATOR)
+}
+
+/// Split `text` into posts of at most `limit` characters each, numbered when there is more
+/// than one. Empty segments are dropped.
+#[must_use]
+pub fn split_thread(text: &str, limit: usize) -> Vec<String> {
+    let segments = explicit_segments(text);
+    // Reserve room for the " n/N" suffix; grow the reservation if the count gains digits.
+    let mut reserve = 0;
+    loop {
+        let max = limit.saturating_sub(reserve).max(1);
+        let parts: Vec<String> = segments
+            .iter()
+            .flat_map(|s| split_segment(s, max))
+            .collect();
+        if parts.len() <= 1 {
+            return parts;
+        }
+        let total = parts.len();
+        let needed = numbering(total, total).chars().count();
+        if needed <= reserve {
+            return parts
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| format!("{p}{}", numbering(i + 1, total)))
+                .collect();
+        }
+        reserve = needed;
+    }
+}
+
+fn numbering(index: usize, total: usize) -> String {
+    format!(" {index}/{total}")
+}
+
+fn explicit_segments(text: &str) -> Vec<String> {
+    let mut segments = vec![String::new()];
+    for line in text.lines() {
+        if line.trim() == THREAD_SEPARATOR {
+            segments.push(String::new());
+        } else if let Some(current) = segments.last_mut() {
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(line);
+        }
+    }
+    segments
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn split_segment(segment: &str, max: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = segment.trim();
+    while rest.chars().count() > max {
+        let cut = best_cut(rest, max);
+        parts.push(rest[..cut].trim_end().to_string());
+        rest = rest[cut..].trim_start();
+    }
+    if !rest.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+/// Byte index to cut `text` at so the head is at most `max` characters: after the last
+/// sentence end that fits, else the last whitespace, else exactly `max` characters.
+fn best_cut(text: &str, max: usize) -> usize {
+    let end = text.char_indices().nth(max).map_or(text.len(), |(i, _)| i);
+    let mut last_sentence = None;
+    let mut last_space = None;
+    let mut prev = None;
+    for (i, c) in text.char_indices() {
+        if i > end {
+            break;
+        }
+        if c.is_whitespace() && i > 0 {
+            last_space = Some(i);
+            if c == '\n' || matches!(prev, Some('.' | '!' | '?' | '…')) {
+                last_sentence = Some(i);
+            }
+        }
+        prev = Some(c);
+    }
+    last_sentence.or(last_space).unwrap_or(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify toot.thread.split]
+    #[test]
+    fn explicit_separators_split_and_number() {
+        let parts = split_thread("first\n---\nsecond\n  ---  \nthird", 500);
+        assert_eq!(parts, vec!["first 1/3", "second 2/3", "third 3/3"]);
+    }
+
+    #[test]
+    fn single_short_post_is_not_numbered() {
+        assert_eq!(split_thread("hello", 500), vec!["hello"]);
+        assert!(split_thread("\n---\n", 500).is_empty());
+        assert!(!has_separator("a - b"));
+        assert!(has_separator("a\n---\nb"));
+    }
+
+    // r[verify toot.thread.split]
+    #[test]
+    fn auto_split_prefers_sentence_boundaries_within_limit() {
+        let text = "One two three. Four five six. Seven eight nine.";
+        let parts = split_thread(text, 20);
+        assert!(parts.iter().all(|p| p.chars().count() <= 20), "{parts:?}");
+        assert!(parts[0].starts_with("One two three."));
+        assert!(parts
+            .last()
+            .unwrap()
+            .ends_with(&format!("/{}", parts.len())));
+    }
+
+    #[test]
+    fn long_word_is_hard_split() {
+        let text = "x".repeat(30);
+        let parts = split_thread(&text, 12);
+        assert!(parts.iter().all(|p| p.chars().count() <= 12), "{parts:?}");
+        let joined: String = parts.iter().map(|p| p.split(' ').next().unwrap()).collect();
+        assert_eq!(joined, text);
+    }
+}
@@ -1,9 +1,16 @@
 //! TUI views and rendering. r[timeline.home.empty-state] r[toot.view-detail] r[toot.post.validation]
 
+pub mod mouse;
+pub mod theme;
 mod views;
 
-pub use views::strip_html;
+pub use crate::html::strip_html;
 pub use views::{
-    draw_compose, draw_instance_info, draw_instance_picker, draw_login, draw_timeline,
-    draw_timeline_picker, draw_toot_detail, EMPTY_TIMELINE_MESSAGE,
+    draw_compose, draw_conversation_thread, draw_conversations, draw_debug, draw_explore,
+    draw_filter_keywords, draw_filters, draw_follow_requests, draw_follows, draw_help,
+    draw_instance_info, draw_instance_picker, draw_list_members, draw_list_picker, draw_lists,
+    draw_login, draw_moderation, draw_outbox, draw_palette, draw_report, draw_scheduled_posts,
+    draw_split, draw_thread_preview, draw_timeline, draw_timeline_columns, draw_timeline_pane,
+    draw_timeline_picker, draw_toot_detail, draw_toot_detail_pane, TimelinePane,
+    EMPTY_TIMELINE_MESSAGE,
 };
@@ -0,0 +1,72 @@
+//! Mouse hit-testing: the draw functions record which rectangle shows what (a timeline row,
+//! an action hint, a link) and the app looks clicks up here. r[mouse.select] r[mouse.hints]
+//! r[mouse.links]
+
+use ratatui::layout::{Position, Rect};
+
+use crate::keymap::Action;
+
+/// What a click on part of the screen means.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hit {
+    /// A timeline row, by index into the rows passed to `draw_timeline`.
+    Row(usize),
+    /// An action hint such as `[b] boost`.
+    Action(Action),
+    /// A link in a toot.
+    Link(String),
+    /// A timeline column out of focus, by index. r[layout.columns]
+    Column(usize),
+}
+
+/// The clickable areas of the last frame, in drawing order.
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    areas: Vec<(Rect, Hit)>,
+}
+
+impl HitMap {
+    pub fn push(&mut self, area: Rect, hit: Hit) {
+        if !area.is_empty() {
+            self.areas.push((area, hit));
+        }
+    }
+
+    /// Add `other`'s areas on top of these.
+    pub fn extend(&mut self, other: Self) {
+        self.areas.extend(other.areas);
+    }
+
+    /// What is at this cell; the area drawn last wins where areas overlap.
+    #[must_use]
+    pub fn at(&self, column: u16, row: u16) -> Option<&Hit> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(column, row)))
+            .map(|(_, hit)| hit)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.areas.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify mouse.select]
+    #[test]
+    fn later_areas_win_and_empty_ones_are_dropped() {
+        let mut hits = HitMap::default();
+        hits.push(Rect::new(0, 0, 10, 2), Hit::Row(0));
+        hits.push(Rect::new(2, 1, 3, 1), Hit::Action(Action::Boost));
+        hits.push(Rect::new(0, 5, 0, 1), Hit::Row(1));
+        assert_eq!(hits.at(0, 1), Some(&Hit::Row(0)));
+        assert_eq!(hits.at(3, 1), Some(&Hit::Action(Action::Boost)));
+        assert_eq!(hits.at(10, 0), None);
+        assert_eq!(hits.at(0, 5), None);
+    }
+}
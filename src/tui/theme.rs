@@ -0,0 +1,530 @@
+//! Colour themes: named style slots the views draw with. r[theme.slots] r[theme.builtin]
+//! r[theme.user] r[theme.no-color] r[theme.depth]
+//!
+//! `theme = "light"` in config.toml picks a built-in theme (`dark`, the default, `light`,
+//! `high-contrast`, `monochrome`) or a user theme in `~/.config/mastotui/themes/<name>.toml`,
+//! which starts from a built-in one and replaces the slots it names:
+//!
+//! ```toml
+//! base = "dark"
+//!
+//! [styles]
+//! author = { fg = "#a6e22e", bold = true }
+//! selection = { fg = "black", bg = "yellow" }
+//! cw = { fg = "magenta", italic = true }
+//! ```
+//!
+//! Colours are names (`cyan`, `light-red`), `#rrggbb` or a 256-colour index (`208`).
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::config::config_dir;
+use crate::error::{MastotuiError, Result};
+
+/// Styles of the things views draw, by meaning rather than colour. r[theme.slots]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// View titles and their border.
+    pub header: Style,
+    /// Display name of a post's author.
+    pub author: Style,
+    /// `@user@domain` of an author.
+    pub handle: Style,
+    /// When a post was made.
+    pub timestamp: Style,
+    /// "boosted" / "Boosted by" markers.
+    pub boost: Style,
+    /// Boost count of a post the user boosted.
+    pub boosted: Style,
+    /// Favourite count of a post the user favourited.
+    pub favourited: Style,
+    /// Content warnings and filtered posts.
+    pub cw: Style,
+    /// The selected row of a list.
+    pub selection: Style,
+    pub error: Style,
+    /// Status messages, the unread divider and a low API quota.
+    pub warning: Style,
+    /// Secondary text: counts, hints, empty states.
+    pub muted: Style,
+    /// Key hints, links and bookmarks.
+    pub accent: Style,
+    /// Text being typed, headings and finished steps.
+    pub highlight: Style,
+    /// Marked entries and notes about an account (e.g. muted until …).
+    pub mark: Style,
+}
+
+const fn fg(color: Color) -> Style {
+    Style::new().fg(color)
+}
+
+impl Theme {
+    pub const DARK: Self = Self {
+        header: fg(Color::Cyan),
+        author: fg(Color::Green).add_modifier(Modifier::BOLD),
+        handle: fg(Color::DarkGray),
+        timestamp: fg(Color::DarkGray),
+        boost: fg(Color::Cyan).add_modifier(Modifier::ITALIC),
+        boosted: fg(Color::Green),
+        favourited: fg(Color::Yellow),
+        cw: fg(Color::Yellow).add_modifier(Modifier::ITALIC),
+        selection: fg(Color::Cyan).add_modifier(Modifier::REVERSED),
+        error: fg(Color::Red),
+        warning: fg(Color::Yellow),
+        muted: fg(Color::DarkGray),
+        accent: fg(Color::Cyan),
+        highlight: fg(Color::Green),
+        mark: fg(Color::Magenta),
+    };
+
+    /// For light terminal backgrounds: no yellow or cyan text.
+    pub const LIGHT: Self = Self {
+        header: fg(Color::Blue),
+        author: fg(Color::Green).add_modifier(Modifier::BOLD),
+        handle: fg(Color::DarkGray),
+        timestamp: fg(Color::DarkGray),
+        boost: fg(Color::Blue).add_modifier(Modifier::ITALIC),
+        boosted: fg(Color::Green),
+        favourited: fg(Color::Magenta),
+        cw: fg(Color::Magenta).add_modifier(Modifier::ITALIC),
+        selection: fg(Color::Blue).add_modifier(Modifier::REVERSED),
+        error: fg(Color::Red),
+        warning: fg(Color::Magenta),
+        muted: fg(Color::DarkGray),
+        accent: fg(Color::Blue),
+        highlight: fg(Color::Green),
+        mark: fg(Color::Red),
+    };
+
+    /// Bright colours and bold text; nothing relies on dark grey.
+    pub const HIGH_CONTRAST: Self = Self {
+        header: fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+        author: fg(Color::White).add_modifier(Modifier::BOLD),
+        handle: fg(Color::LightCyan),
+        timestamp: fg(Color::Gray),
+        boost: fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+        boosted: fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        favourited: fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+        cw: fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+        selection: Style::new()
+            .fg(Color::Black)
+            .bg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        error: fg(Color::LightRed).add_modifier(Modifier::BOLD),
+        warning: fg(Color::LightYellow),
+        muted: fg(Color::Gray),
+        accent: fg(Color::LightCyan),
+        highlight: fg(Color::LightGreen),
+        mark: fg(Color::LightMagenta).add_modifier(Modifier::BOLD),
+    };
+
+    /// No colours at all, only bold, italic, dim and reverse video. r[theme.no-color]
+    pub const MONOCHROME: Self = Self {
+        header: Style::new().add_modifier(Modifier::BOLD),
+        author: Style::new().add_modifier(Modifier::BOLD),
+        handle: Style::new(),
+        timestamp: Style::new().add_modifier(Modifier::DIM),
+        boost: Style::new().add_modifier(Modifier::ITALIC),
+        boosted: Style::new().add_modifier(Modifier::BOLD),
+        favourited: Style::new().add_modifier(Modifier::BOLD),
+        cw: Style::new().add_modifier(Modifier::ITALIC),
+        selection: Style::new().add_modifier(Modifier::REVERSED),
+        error: Style::new().add_modifier(Modifier::BOLD),
+        warning: Style::new().add_modifier(Modifier::BOLD),
+        muted: Style::new().add_modifier(Modifier::DIM),
+        accent: Style::new(),
+        highlight: Style::new().add_modifier(Modifier::BOLD),
+        mark: Style::new().add_modifier(Modifier::UNDERLINED),
+    };
+
+    /// A built-in theme by name. r[theme.builtin]
+    #[must_use]
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "high-contrast" => Some(Self::HIGH_CONTRAST),
+            "monochrome" => Some(Self::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    fn slot_mut(&mut self, name: &str) -> Option<&mut Style> {
+        Some(match name {
+            "header" => &mut self.header,
+            "author" => &mut self.author,
+            "handle" => &mut self.handle,
+            "timestamp" => &mut self.timestamp,
+            "boost" => &mut self.boost,
+            "boosted" => &mut self.boosted,
+            "favourited" => &mut self.favourited,
+            "cw" => &mut self.cw,
+            "selection" => &mut self.selection,
+            "error" => &mut self.error,
+            "warning" => &mut self.warning,
+            "muted" => &mut self.muted,
+            "accent" => &mut self.accent,
+            "highlight" => &mut self.highlight,
+            "mark" => &mut self.mark,
+            _ => return None,
+        })
+    }
+
+    fn slots_mut(&mut self) -> [&mut Style; 15] {
+        [
+            &mut self.header,
+            &mut self.author,
+            &mut self.handle,
+            &mut self.timestamp,
+            &mut self.boost,
+            &mut self.boosted,
+            &mut self.favourited,
+            &mut self.cw,
+            &mut self.selection,
+            &mut self.error,
+            &mut self.warning,
+            &mut self.muted,
+            &mut self.accent,
+            &mut self.highlight,
+            &mut self.mark,
+        ]
+    }
+
+    /// A user theme from the TOML of a theme file. r[theme.user]
+    pub fn from_toml(name: &str, toml: &str) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(toml)
+            .map_err(|e| MastotuiError::Config(format!("Invalid theme `{name}`: {e}")))?;
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme = Self::builtin(base).ok_or_else(|| {
+            MastotuiError::Config(format!("Theme `{name}`: unknown base theme `{base}`"))
+        })?;
+        for (slot, spec) in &file.styles {
+            let style = spec
+                .style()
+                .map_err(|e| MastotuiError::Config(format!("Theme `{name}`, {slot}: {e}")))?;
+            *theme.slot_mut(slot).ok_or_else(|| {
+                MastotuiError::Config(format!("Theme `{name}`: unknown style `{slot}`"))
+            })? = style;
+        }
+        Ok(theme)
+    }
+
+    /// Theme `name` (built-in, else `themes/<name>.toml` in the config dir), adjusted to what
+    /// the terminal can show. Without a name the dark theme is used.
+    pub fn load(name: Option<&str>) -> Result<Self> {
+        let name = name.unwrap_or("dark");
+        let theme = Self::builtin(name).map_or_else(
+            || {
+                let path = config_dir()?.join("themes").join(format!("{name}.toml"));
+                let toml = std::fs::read_to_string(&path).map_err(|e| {
+                    MastotuiError::Config(format!(
+                        "Could not read theme `{name}` from {}: {e}",
+                        path.display()
+                    ))
+                })?;
+                Self::from_toml(name, &toml)
+            },
+            Ok,
+        )?;
+        Ok(theme.for_terminal(
+            no_color(),
+            ColorDepth::detect(
+                std::env::var("COLORTERM").ok().as_deref(),
+                std::env::var("TERM").ok().as_deref(),
+            ),
+        ))
+    }
+
+    /// Monochrome when `NO_COLOR` is set, else every colour brought down to `depth`.
+    /// r[theme.no-color] r[theme.depth]
+    #[must_use]
+    pub fn for_terminal(mut self, no_color: bool, depth: ColorDepth) -> Self {
+        if no_color {
+            return Self::MONOCHROME;
+        }
+        for style in self.slots_mut() {
+            style.fg = style.fg.map(|c| depth.adapt(c));
+            style.bg = style.bg.map(|c| depth.adapt(c));
+        }
+        self
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+/// <https://no-color.org>: set and not empty.
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// How many colours the terminal shows. r[theme.depth]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// The 16 ANSI colours.
+    Ansi16,
+    /// The xterm 256-colour palette.
+    Ansi256,
+    /// 24-bit colour.
+    TrueColor,
+}
+
+/// xterm's values for the 16 ANSI colours, to find the closest one.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// RGB of a 256-colour palette entry.
+fn indexed_rgb(i: u8) -> (u8, u8, u8) {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match i {
+        0..=15 => ANSI[usize::from(i)].1,
+        16..=231 => {
+            let i = usize::from(i - 16);
+            (LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+        }
+        _ => {
+            let v = 8 + (i - 232) * 10;
+            (v, v, v)
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+impl ColorDepth {
+    /// From `COLORTERM` and `TERM`, as terminals advertise it.
+    #[must_use]
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            Self::TrueColor
+        } else if term.is_some_and(|t| t.contains("256color")) {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+
+    /// The closest colour this depth can show; named colours are always shown as they are.
+    #[must_use]
+    pub fn adapt(self, color: Color) -> Color {
+        let rgb = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Indexed(i) => indexed_rgb(i),
+            other => return other,
+        };
+        match (self, color) {
+            (Self::TrueColor, _) | (Self::Ansi256, Color::Indexed(_)) => color,
+            (Self::Ansi256, _) => (16..=255)
+                .min_by_key(|&i| distance(rgb, indexed_rgb(i)))
+                .map_or(color, Color::Indexed),
+            (Self::Ansi16, _) => ANSI
+                .iter()
+                .min_by_key(|(_, c)| distance(rgb, *c))
+                .map_or(color, |(c, _)| *c),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    #[serde(default)]
+    styles: BTreeMap<String, StyleSpec>,
+}
+
+/// One slot as written in a theme file.
+// One flag per modifier, as the TOML spells them.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleSpec {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    dim: bool,
+    #[serde(default)]
+    underlined: bool,
+    #[serde(default)]
+    reversed: bool,
+}
+
+impl StyleSpec {
+    fn style(&self) -> std::result::Result<Style, String> {
+        let color = |c: &Option<String>| {
+            c.as_deref()
+                .map(|s| Color::from_str(s).map_err(|_| format!("unknown colour `{s}`")))
+                .transpose()
+        };
+        let mut style = Style::new();
+        style.fg = color(&self.fg)?;
+        style.bg = color(&self.bg)?;
+        for (on, modifier) in [
+            (self.bold, Modifier::BOLD),
+            (self.italic, Modifier::ITALIC),
+            (self.dim, Modifier::DIM),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.reversed, Modifier::REVERSED),
+        ] {
+            if on {
+                style = style.add_modifier(modifier);
+            }
+        }
+        Ok(style)
+    }
+}
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+/// Draw with `theme` from now on.
+pub fn set_theme(theme: Theme) {
+    if let Ok(mut current) = THEME.write() {
+        *current = theme;
+    }
+}
+
+/// The theme views draw with.
+pub fn theme() -> Theme {
+    THEME.read().map_or(Theme::DARK, |t| *t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify theme.builtin]
+    #[test]
+    fn builtin_themes_are_found_by_name() {
+        for name in ["dark", "light", "high-contrast", "monochrome"] {
+            assert!(Theme::builtin(name).is_some(), "{name}");
+        }
+        assert!(Theme::builtin("solarized").is_none());
+        let mut mono = Theme::MONOCHROME;
+        assert!(mono
+            .slots_mut()
+            .iter()
+            .all(|s| s.fg.is_none() && s.bg.is_none()));
+    }
+
+    // r[verify theme.user]
+    #[test]
+    fn user_theme_replaces_named_slots_of_its_base() {
+        let theme = Theme::from_toml(
+            "mine",
+            "base = \"light\"\n[styles]\nauthor = { fg = \"#a6e22e\", bold = true }\n\
+             selection = { fg = \"black\", bg = \"208\" }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            theme.author,
+            Style::new()
+                .fg(Color::Rgb(0xa6, 0xe2, 0x2e))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(theme.selection.bg, Some(Color::Indexed(208)));
+        assert_eq!(theme.header, Theme::LIGHT.header);
+
+        for (toml, error) in [
+            (
+                "[styles]\nauthr = { fg = \"red\" }\n",
+                "unknown style `authr`",
+            ),
+            (
+                "[styles]\nauthor = { fg = \"redish\" }\n",
+                "author: unknown colour `redish`",
+            ),
+            ("base = \"neon\"\n", "unknown base theme `neon`"),
+            (
+                "[styles]\nauthor = { blink = true }\n",
+                "Invalid theme `mine`",
+            ),
+        ] {
+            let err = Theme::from_toml("mine", toml).unwrap_err().to_string();
+            assert!(err.contains(error), "{err}");
+        }
+    }
+
+    // r[verify theme.user]
+    #[test]
+    fn load_reads_user_themes_from_the_config_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("mastotui/themes");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("mine.toml"), "base = \"monochrome\"\n").unwrap();
+        let _env = crate::test_env::isolate(temp.path());
+        let mine = Theme::load(Some("mine"));
+        let missing = Theme::load(Some("missing"));
+        assert_eq!(mine.unwrap(), Theme::MONOCHROME);
+        let err = missing.unwrap_err().to_string();
+        assert!(err.contains("Could not read theme `missing`"), "{err}");
+    }
+
+    // r[verify theme.no-color]
+    #[test]
+    fn no_color_means_monochrome() {
+        let theme = Theme::HIGH_CONTRAST.for_terminal(true, ColorDepth::TrueColor);
+        assert_eq!(theme, Theme::MONOCHROME);
+    }
+
+    // r[verify theme.depth]
+    #[test]
+    fn colours_are_brought_down_to_the_terminal_depth() {
+        assert_eq!(
+            ColorDepth::detect(Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::detect(None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(ColorDepth::detect(None, Some("linux")), ColorDepth::Ansi16);
+
+        let orange = Color::Rgb(255, 135, 0);
+        assert_eq!(ColorDepth::TrueColor.adapt(orange), orange);
+        assert_eq!(ColorDepth::Ansi256.adapt(orange), Color::Indexed(208));
+        assert_eq!(ColorDepth::Ansi16.adapt(orange), Color::Yellow);
+        assert_eq!(
+            ColorDepth::Ansi16.adapt(Color::Indexed(46)),
+            Color::LightGreen
+        );
+        assert_eq!(ColorDepth::Ansi16.adapt(Color::Cyan), Color::Cyan);
+
+        let mut theme = Theme::DARK;
+        theme.author = theme.author.fg(orange);
+        let theme = theme.for_terminal(false, ColorDepth::Ansi256);
+        assert_eq!(theme.author.fg, Some(Color::Indexed(208)));
+        assert!(theme.author.add_modifier.contains(Modifier::BOLD));
+    }
+}
@@ -1,39 +1,114 @@
 //! TUI view rendering. r[timeline.home.empty-state] r[toot.view-detail] r[toot.post.validation]
 
 use hyperrat::Link;
-use ratatui::layout::{Constraint, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, LineGauge, Paragraph, Sparkline, Wrap};
 use ratatui::Frame;
 
-use crate::api::{Account, Status};
+use crate::api::{
+    Account, Conversation, FailedRequest, Filter, FilterAction, List, RateLimit, Relationship,
+    RepliesPolicy, ReportCategory, ScheduledStatus, Status, TrendHistory, Trends, TrendsKind,
+};
+use crate::app::{
+    ExploreBrowser, FilterForm, FollowsBrowser, FollowsTab, ListForm, ModerationBrowser,
+    ModerationTab, ReportForm, ReportStep, FILTER_CONTEXTS,
+};
+use crate::error::MastotuiError;
+use crate::html::strip_html;
+use crate::keymap::{Action, KeyView, Keymap};
+use crate::outbox::OutboxItem;
+use crate::schedule::format_local;
+use crate::tui::mouse::{Hit, HitMap};
+use crate::tui::theme::theme;
 
-/// Strip HTML tags from Mastodon content for plain-text display.
-///
-/// Ensures a space after hyperlinks so "link</a>next" becomes "link next".
-/// Block tags like </p> and <br> become newlines so content keeps paragraph breaks.
+/// Web links in Mastodon content, in order and without repeats; mentions and hashtags
+/// (which Mastodon marks with a `mention` class) are left out. r[mouse.links]
 #[must_use]
-pub fn strip_html(html: &str) -> String {
-    let s = html
-        .replace("</a>", "</a> ")
-        .replace("</p>", "\n")
-        .replace("<br>", "\n")
-        .replace("<br/>", "\n")
-        .replace("<br />", "\n");
-    let fragment = scraper::Html::parse_fragment(&s);
-    fragment
-        .root_element()
-        .text()
-        .collect::<Vec<_>>()
-        .join("")
-        .trim()
-        .to_string()
+pub fn status_links(html: &str) -> Vec<String> {
+    let fragment = scraper::Html::parse_fragment(html);
+    let Ok(anchors) = scraper::Selector::parse("a[href]") else {
+        return Vec::new();
+    };
+    let mut links: Vec<String> = Vec::new();
+    for a in fragment.select(&anchors) {
+        let mention = a.value().classes().any(|c| c == "mention");
+        let href = a.value().attr("href").unwrap_or_default();
+        let web = href.starts_with("https://") || href.starts_with("http://");
+        if web && !mention && !links.iter().any(|l| l == href) {
+            links.push(href.to_string());
+        }
+    }
+    links
+}
+
+/// A dim row of key hints such as `[b] boost`, recording where each clickable one lands in
+/// `area`. r[mouse.hints]
+fn hint_line(hints: &[(String, Option<Action>)], area: Rect, map: &mut HitMap) -> Line<'static> {
+    let mut spans = vec![Span::styled(" ", Style::default().dim())];
+    let mut x = area.x + 1;
+    for (i, (text, action)) in hints.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("  ", Style::default().dim()));
+            x = x.saturating_add(2);
+        }
+        let span = Span::styled(text.clone(), Style::default().dim());
+        let width = u16::try_from(span.width()).unwrap_or(u16::MAX);
+        if let Some(action) = *action {
+            map.push(
+                Rect::new(x, area.y, width, 1).intersection(area),
+                Hit::Action(action),
+            );
+        }
+        x = x.saturating_add(width);
+        spans.push(span);
+    }
+    spans.push(Span::styled(" ", Style::default().dim()));
+    Line::from(spans)
+}
+
+/// Hints for the keys `view` is bound to in `keymap`: `[k]/[j]` when it moves up and down,
+/// then each action's first key before its text, e.g. `[b] boost`. Unbound actions are left
+/// out. r[keys.config]
+fn key_hints(
+    keymap: &Keymap,
+    view: KeyView,
+    hints: &[(Action, &str)],
+) -> Vec<(String, Option<Action>)> {
+    let key = |action| keymap.key(view, action);
+    let moves = key(Action::Up)
+        .zip(key(Action::Down))
+        .map(|(up, down)| (format!("[{up}]/[{down}]"), None));
+    moves
+        .into_iter()
+        .chain(hints.iter().filter_map(|&(action, text)| {
+            key(action).map(|k| (format!("[{k}] {text}"), Some(action)))
+        }))
+        .collect()
+}
+
+/// Footer text of [`key_hints`], for views without clickable hints.
+fn key_hint_text(keymap: &Keymap, view: KeyView, hints: &[(Action, &str)]) -> String {
+    let hints: Vec<String> = key_hints(keymap, view, hints)
+        .into_iter()
+        .map(|(text, _)| text)
+        .collect();
+    format!(" {} ", hints.join("  "))
+}
+
+/// Rows taken by `lines` wrapped to `width`, as drawn in a wrapping paragraph.
+fn wrapped_height(lines: &[Line], width: u16) -> u16 {
+    let count = Paragraph::new(lines.to_vec())
+        .wrap(Wrap { trim: true })
+        .line_count(width);
+    u16::try_from(count).unwrap_or(u16::MAX)
 }
 
 /// r[instance.info.dialog]: instance info (press i): current instance, l log out/in, b browse another.
 pub fn draw_instance_info(
     frame: &mut Frame,
+    keymap: &Keymap,
     instance_url: &str,
     is_logged_in: bool,
     anonymous_instance_url: Option<&str>,
@@ -49,7 +124,7 @@ pub fn draw_instance_info(
     let title = Paragraph::new("Instance").block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title, chunks[0]);
 
@@ -73,25 +148,36 @@ pub fn draw_instance_info(
     } else {
         "Not logged in."
     };
-    lines.push(Line::from(Span::styled(
-        status,
-        Style::default().fg(Color::DarkGray),
-    )));
+    lines.push(Line::from(Span::styled(status, theme().muted)));
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "l: Log out / Log in",
-        Style::default().fg(Color::Cyan),
-    )));
-    lines.push(Line::from(Span::styled(
-        "b: Browse another instance (anonymous)",
-        Style::default().fg(Color::Cyan),
-    )));
+    for (action, text) in [
+        (Action::LogInOut, "Log out / Log in"),
+        (
+            Action::BrowseInstance,
+            "Browse another instance (anonymous)",
+        ),
+    ] {
+        if let Some(key) = keymap.key(KeyView::InstanceInfo, action) {
+            lines.push(Line::from(Span::styled(
+                format!("{key}: {text}"),
+                theme().accent,
+            )));
+        }
+    }
     let block = Block::default().borders(Borders::ALL).title(" Instance ");
     let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
     frame.render_widget(para, chunks[1]);
 
     let help = Line::from(Span::styled(
-        " [l] log out/in  [b] browse another  [Esc] back ",
+        key_hint_text(
+            keymap,
+            KeyView::InstanceInfo,
+            &[
+                (Action::LogInOut, "log out/in"),
+                (Action::BrowseInstance, "browse another"),
+                (Action::Back, "back"),
+            ],
+        ),
         Style::default().dim(),
     ));
     frame.render_widget(Paragraph::new(help), chunks[2]);
@@ -117,13 +203,13 @@ pub fn draw_instance_picker(
     let title = Paragraph::new("Browse instance (anonymous)").block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title, chunks[0]);
 
     let mut lines = vec![Line::from(Span::styled(
         "Instance URL: ".to_string() + input + "▌",
-        Style::default().fg(Color::Green),
+        theme().highlight,
     ))];
     if !known.is_empty() {
         lines.push(Line::from(""));
@@ -133,9 +219,7 @@ pub fn draw_instance_picker(
         )));
         for (i, url) in known.iter().enumerate() {
             let style = if i == selected {
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::REVERSED)
+                theme().selection
             } else {
                 Style::default()
             };
@@ -144,10 +228,7 @@ pub fn draw_instance_picker(
     }
     if !message.is_empty() {
         lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            message,
-            Style::default().fg(Color::Yellow),
-        )));
+        lines.push(Line::from(Span::styled(message, theme().warning)));
     }
     let block = Block::default().borders(Borders::ALL).title(" Instance ");
     let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
@@ -178,7 +259,7 @@ pub fn draw_timeline_picker(
     let title = Paragraph::new("Select timeline").block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title, chunks[0]);
 
@@ -188,9 +269,7 @@ pub fn draw_timeline_picker(
     ))];
     for (i, opt) in options.iter().enumerate() {
         let style = if i == selected {
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::REVERSED)
+            theme().selection
         } else {
             Style::default()
         };
@@ -203,9 +282,7 @@ pub fn draw_timeline_picker(
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             lists_message,
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::DIM),
+            theme().warning.add_modifier(Modifier::DIM),
         )));
     }
     let block = Block::default().borders(Borders::ALL).title(" Timeline ");
@@ -238,7 +315,7 @@ pub fn draw_login(
     let title = Paragraph::new("mastotui — Mastodon TUI").block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title, chunks[0]);
 
@@ -261,7 +338,7 @@ pub fn draw_login(
         }
         lines.push(Line::from(Span::styled(
             "Input: ".to_string() + code_buffer + "▌",
-            Style::default().fg(Color::Green),
+            theme().highlight,
         )));
     } else {
         lines.push(Line::from(Span::styled(
@@ -270,17 +347,13 @@ pub fn draw_login(
         )));
     }
     lines.push(Line::from(""));
-    if !message.is_empty() {
-        lines.push(Line::from(Span::styled(
-            message,
-            Style::default().fg(Color::Yellow),
-        )));
-    }
+    let message = (!message.is_empty()).then(|| Line::from(Span::styled(message, theme().warning)));
     // Top/bottom only so selecting the URL doesn't include side borders (│) and paste gets contiguous text.
     let block = Block::default()
         .borders(Borders::TOP | Borders::BOTTOM)
         .title(" Login ");
     if auth_url.is_empty() {
+        lines.extend(message);
         let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
         frame.render_widget(para, chunks[1]);
     } else {
@@ -304,10 +377,12 @@ pub fn draw_login(
             Line::from("After authorizing, paste the code and press Enter."),
             Line::from(Span::styled(
                 "Code: ".to_string() + code_buffer + "▌",
-                Style::default().fg(Color::Green),
+                theme().highlight,
             )),
         ];
         lines_below.push(Line::from(""));
+        // Below the code prompt: the area above the link only fits its three lines.
+        lines_below.extend(message);
         let para_below = Paragraph::new(lines_below).wrap(Wrap { trim: true });
         frame.render_widget(para_below, below);
     }
@@ -336,9 +411,7 @@ fn media_alt_lines(status: &Status) -> Vec<Line<'static>> {
         .map(|alt| {
             Line::from(Span::styled(
                 format!("[media: {alt}]"),
-                Style::default()
-                    .fg(Color::DarkGray)
-                    .add_modifier(Modifier::DIM),
+                theme().muted.add_modifier(Modifier::DIM),
             ))
         })
         .collect()
@@ -351,34 +424,120 @@ fn display_status(status: &Status) -> (&Status, Option<&Account>) {
     })
 }
 
+/// Reply, boost and favourite counts, with the user's own boost and favourite highlighted
+/// and a flag when bookmarked. r[toot.optimistic]
+fn status_counts(status: &Status) -> Vec<Span<'static>> {
+    let theme = theme();
+    let mine = |on: Option<bool>, style: Style| {
+        if on.unwrap_or(false) {
+            style
+        } else {
+            theme.muted
+        }
+    };
+    let mut spans = vec![
+        Span::styled(format!(" ↩ {}", status.replies_count), theme.muted),
+        Span::styled(
+            format!(" ⟳ {}", status.reblogs_count),
+            mine(status.reblogged, theme.boosted),
+        ),
+        Span::styled(
+            format!(" ★ {}", status.favourites_count),
+            mine(status.favourited, theme.favourited),
+        ),
+    ];
+    if status.bookmarked.unwrap_or(false) {
+        spans.push(Span::styled(" ⚑", theme.accent));
+    }
+    spans
+}
+
+/// One timeline as drawn in a pane: the rows shown (with why each is hidden, if it is), the
+/// selection and scroll position, and the load state.
+#[derive(Debug, Clone, Copy)]
+pub struct TimelinePane<'a> {
+    pub label: &'a str,
+    pub rows: &'a [(&'a Status, Option<&'a str>)],
+    pub selected: usize,
+    pub scroll: usize,
+    /// Rows above the unread divider (0 for none).
+    pub unread: usize,
+    pub loading: bool,
+    /// Load error shown instead of the rows.
+    pub message: &'a str,
+}
+
 /// r[timeline.home.fetch] r[timeline.home.empty-state] r[timeline.select.header]: timeline list and current timeline label in header.
+/// r[markers.divider]: when `unread` > 0, a divider follows the first `unread` statuses.
+/// r[ratelimit.status]: the footer ends with the API quota left, when the server reports it.
+#[allow(clippy::too_many_arguments)]
 pub fn draw_timeline(
     frame: &mut Frame,
+    keymap: &Keymap,
     timeline_label: &str,
-    statuses: &[Status],
+    statuses: &[(&Status, Option<&str>)],
     selected: usize,
     scroll: usize,
+    unread: usize,
+    rate_limit: Option<&RateLimit>,
     loading: bool,
     message: &str,
-) {
-    let area = frame.area();
-    let block_title = format!(" {timeline_label}  [t] timeline  [i] instance ");
+) -> HitMap {
+    let pane = TimelinePane {
+        label: timeline_label,
+        rows: statuses,
+        selected,
+        scroll,
+        unread,
+        loading,
+        message,
+    };
     let chunks = Layout::vertical([
         Constraint::Length(1),
         Constraint::Min(0),
         Constraint::Length(1),
     ])
-    .split(area);
+    .split(frame.area());
+    let mut hits = draw_timeline_pane(frame, chunks[1], &pane, false);
+    if !loading {
+        draw_timeline_footer(frame, keymap, chunks[2], rate_limit, &mut hits);
+    }
+    hits
+}
 
-    let content_area = chunks[1];
+/// The timeline `pane` in its bordered block, border in the accent style when `focused`.
+/// r[layout.columns]
+pub fn draw_timeline_pane(
+    frame: &mut Frame,
+    area: Rect,
+    pane: &TimelinePane,
+    focused: bool,
+) -> HitMap {
+    let TimelinePane {
+        label: timeline_label,
+        rows: statuses,
+        selected,
+        scroll,
+        unread,
+        loading,
+        message,
+    } = *pane;
+    let mut hits = HitMap::default();
+    let border = if focused {
+        theme().accent
+    } else {
+        Style::default()
+    };
+    let block_title = format!(" {timeline_label}  [t] timeline  [i] instance ");
     if loading {
         let para = Paragraph::new("Loading…").block(
             Block::default()
                 .borders(Borders::ALL)
+                .border_style(border)
                 .title(block_title.as_str()),
         );
-        frame.render_widget(para, content_area);
-        return;
+        frame.render_widget(para, area);
+        return hits;
     }
 
     if !message.is_empty() {
@@ -386,31 +545,40 @@ pub fn draw_timeline(
             .block(
                 Block::default()
                     .borders(Borders::ALL)
+                    .border_style(border)
                     .title(block_title.as_str()),
             )
-            .style(Style::default().fg(Color::Red));
-        frame.render_widget(para, content_area);
+            .style(theme().error);
+        frame.render_widget(para, area);
     } else if statuses.is_empty() {
         let para = Paragraph::new(EMPTY_TIMELINE_MESSAGE)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
+                    .border_style(border)
                     .title(block_title.as_str()),
             )
-            .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(para, content_area);
+            .style(theme().muted);
+        frame.render_widget(para, area);
     } else {
         // Each timeline item is 2 lines (header, then content on new line); items that fit = height/2
-        let visible = (content_area.height as usize / 2).max(1);
+        let visible = (area.height as usize / 2).max(1);
         let start = scroll.min(statuses.len().saturating_sub(visible));
         let end = (start + visible).min(statuses.len());
         let mut lines: Vec<Line> = Vec::with_capacity(2 * (end - start));
-        for (i, s) in statuses[start..end].iter().enumerate() {
+        // Where each row (Some) or the unread marker (None) starts in `lines`.
+        let mut marks: Vec<(Option<usize>, usize)> = Vec::with_capacity(end - start);
+        for (i, &(s, hidden)) in statuses[start..end].iter().enumerate() {
             let idx = start + i;
+            if unread > 0 && idx == unread {
+                marks.push((None, lines.len()));
+                lines.push(Line::from(Span::styled(
+                    format!("──── ▲ {unread} unread ────"),
+                    theme().warning,
+                )));
+            }
             let style = if idx == selected {
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::REVERSED)
+                theme().selection
             } else {
                 Style::default()
             };
@@ -422,7 +590,6 @@ pub fn draw_timeline(
             } else {
                 account.acct.as_str()
             };
-            let header = format!("@{} · {}", handle, display_status.created_at);
             let booster_prefix = booster
                 .map(|a| {
                     let h = if a.acct.is_empty() {
@@ -433,22 +600,43 @@ pub fn draw_timeline(
                     format!("@{h} boosted · ")
                 })
                 .unwrap_or_default();
-            let header_line = Line::from(vec![
+            let mut header_spans = vec![
+                Span::styled(format!(" {display} "), theme().author),
+                Span::styled(booster_prefix, theme().boost),
+                Span::styled(format!("@{handle}"), theme().handle),
                 Span::styled(
-                    format!(" {display} "),
-                    Style::default()
-                        .add_modifier(Modifier::BOLD)
-                        .fg(Color::Green),
+                    format!(" · {}", display_status.created_at),
+                    theme().timestamp,
                 ),
-                Span::styled(
-                    booster_prefix,
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::ITALIC),
-                ),
-                Span::styled(header, Style::default().fg(Color::DarkGray)),
-            ]);
+            ];
+            header_spans.extend(status_counts(display_status));
+            let header_line = Line::from(header_spans);
+            marks.push((Some(idx), lines.len()));
             lines.push(header_line);
+            // r[mute.show-hidden]: hidden statuses only appear when toggled on; say why.
+            if let Some(reason) = hidden {
+                let content = strip_html(&display_status.content);
+                let preview: String = content
+                    .lines()
+                    .next()
+                    .unwrap_or("")
+                    .chars()
+                    .take(60)
+                    .collect();
+                lines.push(Line::from(Span::styled(
+                    format!("Hidden ({reason}): {preview}"),
+                    style.patch(theme().muted).add_modifier(Modifier::ITALIC),
+                )));
+                continue;
+            }
+            // r[filter.server.warn]: collapse to the filter title instead of the content.
+            if let Some((_, title)) = s.server_filter() {
+                lines.push(Line::from(Span::styled(
+                    format!("Filtered: {title}"),
+                    style.patch(theme().cw),
+                )));
+                continue;
+            }
             let content = strip_html(&display_status.content);
             let content_preview = content.lines().next().unwrap_or(&content);
             let content_line = Line::from(Span::styled(
@@ -460,31 +648,153 @@ pub fn draw_timeline(
                 lines.push(media_line);
             }
         }
-        let para = Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(block_title.as_str()),
-            )
-            .wrap(Wrap { trim: true });
-        frame.render_widget(para, content_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(border)
+            .title(block_title.as_str());
+        // r[mouse.select]: each row covers the lines it wraps to.
+        let inner = block.inner(area);
+        let mut y = inner.y;
+        for (m, &(row, first)) in marks.iter().enumerate() {
+            let last = marks.get(m + 1).map_or(lines.len(), |&(_, next)| next);
+            let height = wrapped_height(&lines[first..last], inner.width);
+            if let Some(row) = row {
+                let row_area = Rect::new(inner.x, y, inner.width, height).intersection(inner);
+                hits.push(row_area, Hit::Row(row));
+            }
+            y = y.saturating_add(height);
+        }
+        let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+        frame.render_widget(para, area);
     }
 
-    let status_line = Line::from(Span::styled(
-        " [↑]/[↓]  [Enter]: open  [p]: post  [t]: timeline  [q]: quit  [r]: refresh ",
-        Style::default().dim(),
-    ));
-    frame.render_widget(Paragraph::new(status_line), chunks[2]);
+    hits
+}
+
+/// Timeline on the left, the selected toot (or, in the toot view, the opened one) on the
+/// right. Until `detail_focused`, a click on the toot opens it rather than acting on it.
+/// r[layout.split]
+pub fn draw_split(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    pane: &TimelinePane,
+    detail: Option<(&Status, bool, &str)>,
+    detail_focused: bool,
+    rate_limit: Option<&RateLimit>,
+) -> HitMap {
+    let halves = Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(frame.area());
+    let left = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .split(halves[0]);
+    let mut hits = draw_timeline_pane(frame, left[1], pane, !detail_focused);
+    if !detail_focused && !pane.loading {
+        draw_timeline_footer(frame, keymap, left[2], rate_limit, &mut hits);
+    }
+    if let Some((status, revealed, message)) = detail {
+        let detail_hits =
+            draw_toot_detail_pane(frame, keymap, halves[1], status, revealed, message);
+        if detail_focused {
+            hits.extend(detail_hits);
+        } else {
+            hits.push(halves[1], Hit::Action(Action::Open));
+        }
+    }
+    hits
+}
+
+/// Several timelines side by side over one footer; clicking a column out of focus focuses
+/// it. r[layout.columns]
+pub fn draw_timeline_columns(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    panes: &[TimelinePane],
+    focused: usize,
+    rate_limit: Option<&RateLimit>,
+) -> HitMap {
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .split(frame.area());
+    let count = u32::try_from(panes.len()).unwrap_or(u32::MAX).max(1);
+    let areas =
+        Layout::horizontal(panes.iter().map(|_| Constraint::Ratio(1, count))).split(chunks[1]);
+    let mut hits = HitMap::default();
+    for (i, (pane, &area)) in panes.iter().zip(areas.iter()).enumerate() {
+        let pane_hits = draw_timeline_pane(frame, area, pane, i == focused);
+        if i == focused {
+            hits.extend(pane_hits);
+        } else {
+            hits.push(area, Hit::Column(i));
+        }
+    }
+    draw_timeline_footer(frame, keymap, chunks[2], rate_limit, &mut hits);
+    hits
+}
+
+/// Key hints under the timeline, ending with the API quota left. r[ratelimit.status]
+fn draw_timeline_footer(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    area: Rect,
+    rate_limit: Option<&RateLimit>,
+    hits: &mut HitMap,
+) {
+    let footer = key_hints(
+        keymap,
+        KeyView::Timeline,
+        &[
+            (Action::Open, "open"),
+            (Action::Compose, "post"),
+            (Action::Scheduled, "scheduled"),
+            (Action::Filters, "filters"),
+            (Action::PickTimeline, "timeline"),
+            (Action::Quit, "quit"),
+            (Action::Refresh, "refresh"),
+        ],
+    );
+    let mut status_line = hint_line(&footer, area, hits).spans;
+    if let Some(r) = rate_limit {
+        let style = if r.is_low() {
+            theme().warning
+        } else {
+            Style::default().dim()
+        };
+        status_line.push(Span::styled(
+            format!(" API {}/{} ", r.remaining, r.limit),
+            style,
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(status_line)), area);
 }
 
 /// r[toot.view-detail]: single toot with full content and actions.
+/// r[filter.server.warn]: a status matched by a `warn` filter stays hidden until `revealed`.
 pub fn draw_toot_detail(
     frame: &mut Frame,
+    keymap: &Keymap,
     status: &Status,
-    _reply_id: Option<&str>,
+    revealed: bool,
     message: &str,
-) {
-    let area = frame.area();
+) -> HitMap {
+    draw_toot_detail_pane(frame, keymap, frame.area(), status, revealed, message)
+}
+
+/// The toot view drawn in `area`, e.g. the right pane of the split layout. r[layout.split]
+pub fn draw_toot_detail_pane(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    area: Rect,
+    status: &Status,
+    revealed: bool,
+    message: &str,
+) -> HitMap {
+    let mut hits = HitMap::default();
     let chunks = Layout::vertical([
         Constraint::Length(1),
         Constraint::Min(5),
@@ -496,14 +806,17 @@ pub fn draw_toot_detail(
     let title = Paragraph::new(" Toot ").block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title, chunks[0]);
 
     let (display_status, booster) = display_status(status);
     let acc = &display_status.account;
-    let header = format!("@{} · {}", acc.acct, display_status.created_at);
-    let content = strip_html(&display_status.content);
+    let filtered = status.server_filter().filter(|_| !revealed);
+    let content = filtered.map_or_else(
+        || strip_html(&display_status.content),
+        |(_, title)| format!("Filtered: {title}  [v] show anyway"),
+    );
     let mut lines = vec![];
     if let Some(b) = booster {
         let handle = if b.acct.is_empty() {
@@ -513,47 +826,80 @@ pub fn draw_toot_detail(
         };
         lines.push(Line::from(Span::styled(
             format!("Boosted by @{handle}"),
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::ITALIC),
+            theme().boost,
         )));
         lines.push(Line::from(""));
     }
     lines.extend_from_slice(&[
-        Line::from(Span::styled(
-            acc.display_name.as_str(),
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(Span::styled(header, Style::default().fg(Color::DarkGray))),
+        Line::from(Span::styled(acc.display_name.as_str(), theme().author)),
+        Line::from(vec![
+            Span::styled(format!("@{}", acc.acct), theme().handle),
+            Span::styled(
+                format!(" · {}", display_status.created_at),
+                theme().timestamp,
+            ),
+        ]),
+        Line::from(status_counts(display_status)),
         Line::from(""),
         Line::from(content),
     ]);
-    for media_line in media_alt_lines(status) {
-        lines.push(media_line);
+    if filtered.is_none() {
+        for media_line in media_alt_lines(status) {
+            lines.push(media_line);
+        }
     }
     let block = Block::default().borders(Borders::ALL);
+    // r[mouse.links]: links are listed under the post, each clickable where it wraps to.
+    let urls = if filtered.is_none() {
+        status_links(&display_status.content)
+    } else {
+        Vec::new()
+    };
+    let inner = block.inner(chunks[1]);
+    if !urls.is_empty() {
+        lines.push(Line::from(""));
+        let mut y = inner.y.saturating_add(wrapped_height(&lines, inner.width));
+        for link in urls {
+            let line = Line::from(Span::styled(format!("→ {link}"), theme().accent));
+            let height = wrapped_height(std::slice::from_ref(&line), inner.width);
+            let area = Rect::new(inner.x, y, inner.width, height).intersection(inner);
+            hits.push(area, Hit::Link(link));
+            y = y.saturating_add(height);
+            lines.push(line);
+        }
+    }
     let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
     frame.render_widget(para, chunks[1]);
 
     if !message.is_empty() {
-        let msg = Paragraph::new(message).style(Style::default().fg(Color::Yellow));
+        let msg = Paragraph::new(message).style(theme().warning);
         frame.render_widget(msg, chunks[2]);
     }
 
-    let help = Line::from(Span::styled(
-        " [b] boost  [f] favourite  [r] reply  [Esc] back ",
-        Style::default().dim(),
-    ));
+    let footer = key_hints(
+        keymap,
+        KeyView::TootDetail,
+        &[
+            (Action::Boost, "boost"),
+            (Action::Favourite, "favourite"),
+            (Action::Bookmark, "bookmark"),
+            (Action::Reply, "reply"),
+            (Action::AddToList, "lists"),
+            (Action::Back, "back"),
+        ],
+    );
+    let help = hint_line(&footer, chunks[3], &mut hits);
     frame.render_widget(Paragraph::new(help), chunks[3]);
+    hits
 }
 
-/// r[toot.post.submit] r[toot.post.validation]: compose new toot or reply.
+/// r[toot.post.submit] r[toot.post.validation] r[toot.schedule.compose]: compose new toot or reply.
 pub fn draw_compose(
     frame: &mut Frame,
     buffer: &str,
-    in_reply_to: Option<&str>,
+    title: &str,
+    schedule: &str,
+    schedule_focused: bool,
     error_message: &str,
     char_limit: usize,
 ) {
@@ -561,30 +907,23 @@ pub fn draw_compose(
     let chunks = Layout::vertical([
         Constraint::Length(1),
         Constraint::Min(5),
+        Constraint::Length(1),
         Constraint::Length(2),
         Constraint::Length(1),
     ])
     .split(area);
 
-    let title = match in_reply_to {
-        Some(_) => " Reply ",
-        None => " New toot ",
-    };
     let title_w = Paragraph::new(title).block(
         Block::default()
             .borders(Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(theme().header),
     );
     frame.render_widget(title_w, chunks[0]);
 
     let len = buffer.chars().count();
     let over = len > char_limit;
     let count_str = format!("{len}/{char_limit}");
-    let count_style = if over {
-        Style::default().fg(Color::Red)
-    } else {
-        Style::default().fg(Color::DarkGray)
-    };
+    let count_style = if over { theme().error } else { theme().muted };
     let block = Block::default()
         .borders(Borders::ALL)
         .title(Span::styled(count_str, count_style));
@@ -593,25 +932,2071 @@ pub fn draw_compose(
         .wrap(Wrap { trim: false });
     frame.render_widget(para, chunks[1]);
 
+    let (schedule_text, schedule_style) = if schedule_focused {
+        (format!(" Schedule: {schedule}▌"), theme().highlight)
+    } else if schedule.is_empty() {
+        (" Schedule: (now)".to_string(), theme().muted)
+    } else {
+        (format!(" Schedule: {schedule}"), theme().muted)
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(schedule_text, schedule_style)),
+        chunks[2],
+    );
+
     if !error_message.is_empty() {
-        let err = Paragraph::new(error_message).style(Style::default().fg(Color::Red));
-        frame.render_widget(err, chunks[2]);
+        let err = Paragraph::new(error_message).style(theme().error);
+        frame.render_widget(err, chunks[3]);
+    }
+
+    let help = Line::from(Span::styled(
+        " [Enter] post  [Alt+Enter] newline  [Ctrl+t] thread  [Tab] schedule  [Esc] cancel  [Ctrl+i] instance ",
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[4]);
+}
+
+/// Greedy word wrap to `width` columns (counted in chars); long words are broken.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut out = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            while word.chars().count() > width {
+                if !line.is_empty() {
+                    out.push(std::mem::take(&mut line));
+                }
+                let head: String = word.chars().take(width).collect();
+                word = word.chars().skip(width).collect();
+                out.push(head);
+            }
+            let needed =
+                line.chars().count() + usize::from(!line.is_empty()) + word.chars().count();
+            if needed > width && !line.is_empty() {
+                out.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        out.push(line);
+    }
+    out
+}
+
+/// r[dm.list] r[dm.unread]: conversations with participants, unread marker and last message.
+pub fn draw_conversations(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    conversations: &[Conversation],
+    selected: usize,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let unread = conversations.iter().filter(|c| c.unread).count();
+    let title = Paragraph::new(format!(" Direct messages ({unread} unread) ")).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(2 * conversations.len());
+    if conversations.is_empty() {
+        lines.push(Line::from(Span::styled("No conversations.", theme().muted)));
+    }
+    for (i, c) in conversations.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let participants = c
+            .accounts
+            .iter()
+            .map(|a| format!("@{}", a.handle()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let marker = if c.unread { "● " } else { "  " };
+        let when = c
+            .last_status
+            .as_ref()
+            .map(|s| format!(" · {}", s.created_at))
+            .unwrap_or_default();
+        let participant_style = if c.unread {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, theme().warning),
+            Span::styled(participants, participant_style),
+            Span::styled(when, theme().muted),
+        ]));
+        let snippet = c
+            .last_status
+            .as_ref()
+            .map(|s| strip_html(&s.content))
+            .unwrap_or_default();
+        let snippet = snippet.lines().next().unwrap_or_default();
+        lines.push(Line::from(Span::styled(
+            format!("    {}", snippet.chars().take(80).collect::<String>()),
+            theme().muted,
+        )));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
     }
 
     let help = Line::from(Span::styled(
-        " [Enter] post  [Esc] cancel  [Ctrl+i] instance ",
+        key_hint_text(
+            keymap,
+            KeyView::Conversations,
+            &[
+                (Action::Open, "open"),
+                (Action::MarkRead, "mark read"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        ),
         Style::default().dim(),
     ));
     frame.render_widget(Paragraph::new(help), chunks[3]);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// r[dm.thread]: chat-like conversation, oldest at top, newest at the bottom. Own messages
+/// (author id equals `me_id`) are right-aligned.
+pub fn draw_conversation_thread(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    conversation: Option<&Conversation>,
+    statuses: &[Status],
+    me_id: Option<&str>,
+    scroll: usize,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
 
-    // r[verify timeline.home.empty-state]
-    #[test]
-    fn empty_timeline_message_shown_when_no_toots() {
-        assert!(EMPTY_TIMELINE_MESSAGE.contains("No toots"));
+    let participants = conversation
+        .map(|c| {
+            c.accounts
+                .iter()
+                .map(|a| format!("@{}", a.handle()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default();
+    let title = Paragraph::new(format!(" Conversation with {participants} ")).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let block = Block::default().borders(Borders::ALL);
+    let inner = block.inner(chunks[1]);
+    let bubble_width = (inner.width as usize * 3 / 4).max(10);
+    let mut lines: Vec<Line> = Vec::new();
+    for s in statuses {
+        let own = me_id.is_some_and(|id| id == s.account.id);
+        let (alignment, name_style) = if own {
+            (Alignment::Right, theme().accent)
+        } else {
+            (Alignment::Left, theme().highlight)
+        };
+        lines.push(
+            Line::from(vec![
+                Span::styled(
+                    format!("@{}", s.account.handle()),
+                    name_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" · {}", s.created_at), theme().muted),
+            ])
+            .alignment(alignment),
+        );
+        for l in wrap_text(&strip_html(&s.content), bubble_width) {
+            lines.push(Line::from(l).alignment(alignment));
+        }
+        lines.push(Line::from(""));
+    }
+    // Anchor to the newest message; scroll counts lines back from the bottom.
+    let visible = inner.height as usize;
+    let max_offset = lines.len().saturating_sub(visible);
+    let offset = max_offset.saturating_sub(scroll);
+    let para = Paragraph::new(lines)
+        .block(block)
+        .scroll((u16::try_from(offset).unwrap_or(u16::MAX), 0));
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::Conversation,
+            &[(Action::Reply, "reply (direct)"), (Action::Back, "back")],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[toot.thread.preview]: numbered thread parts before posting; posted parts are marked.
+pub fn draw_thread_preview(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    parts: &[String],
+    posted: usize,
+    scroll: usize,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(5),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(format!(" Thread preview ({} posts) ", parts.len())).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, part) in parts.iter().enumerate().skip(scroll) {
+        let (marker, style) = if i < posted {
+            ("posted", theme().highlight)
+        } else {
+            ("pending", theme().muted)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}/{} ", i + 1, parts.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("({} chars, {marker})", part.chars().count()), style),
+        ]));
+        lines.extend(part.lines().map(|l| Line::from(l.to_string())));
+        lines.push(Line::from(""));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message)
+            .style(theme().error)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::ThreadPreview,
+            &[
+                (Action::Send, "post thread"),
+                (Action::Back, "back to edit"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[toot.schedule.list]: scheduled posts with reschedule and cancel actions.
+pub fn draw_scheduled_posts(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    scheduled: &[ScheduledStatus],
+    selected: usize,
+    reschedule_input: Option<&str>,
+    confirm_cancel: bool,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(" Scheduled posts ").block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(2 * scheduled.len());
+    if scheduled.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No scheduled posts.",
+            theme().muted,
+        )));
+    }
+    for (i, s) in scheduled.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let reply = if s.params.in_reply_to_id.is_some() {
+            " (reply)"
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", format_local(&s.scheduled_at)),
+                style.add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(reply, theme().muted),
+        ]));
+        let text = s.params.text.lines().next().unwrap_or_default();
+        lines.push(Line::from(format!(
+            "   {}",
+            text.chars().take(80).collect::<String>()
+        )));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if let Some(input) = reschedule_input {
+        let prompt = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("New time: {input}▌"),
+                theme().highlight,
+            )),
+            Line::from(Span::styled(message, theme().warning)),
+        ]);
+        frame.render_widget(prompt, chunks[2]);
+    } else if confirm_cancel {
+        let prompt = Paragraph::new("Cancel this scheduled post? [y] yes  [any other key] no")
+            .style(theme().warning);
+        frame.render_widget(prompt, chunks[2]);
+    } else if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::ScheduledPosts,
+            &[
+                (Action::Edit, "reschedule"),
+                (Action::Delete, "cancel post"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[filter.manage.list] r[filter.manage.edit]: server-side filters and the create/edit form.
+pub fn draw_filters(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    filters: &[Filter],
+    selected: usize,
+    form: Option<&FilterForm>,
+    confirm_delete: bool,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(" Filters ").block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let lines = form.map_or_else(|| filter_list_lines(filters, selected), filter_form_lines);
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if confirm_delete {
+        let prompt = Paragraph::new("Delete this filter? [y] yes  [any other key] no")
+            .style(theme().warning);
+        frame.render_widget(prompt, chunks[2]);
+    } else if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = if form.is_some() {
+        " [Tab]/[↑]/[↓] field  [Space] toggle  [Enter] save  [Esc] cancel ".to_string()
+    } else {
+        key_hint_text(
+            keymap,
+            KeyView::Filters,
+            &[
+                (Action::Open, "keywords"),
+                (Action::New, "new"),
+                (Action::Edit, "edit"),
+                (Action::Delete, "delete"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[3],
+    );
+}
+
+const fn filter_action_label(action: FilterAction) -> &'static str {
+    match action {
+        FilterAction::Warn => "warn",
+        FilterAction::Hide => "hide",
+        FilterAction::Blur => "blur",
+    }
+}
+
+fn filter_list_lines(filters: &[Filter], selected: usize) -> Vec<Line<'static>> {
+    if filters.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No filters. Press [n] to create one.",
+            theme().muted,
+        ))];
+    }
+    let mut lines = Vec::with_capacity(2 * filters.len());
+    for (i, f) in filters.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let expires = f
+            .expires_at
+            .as_deref()
+            .map(|t| format!(" · expires {}", format_local(t)))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", f.title), style.add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(" {}{expires}", filter_action_label(f.filter_action)),
+                theme().muted,
+            ),
+        ]));
+        let keywords: Vec<&str> = f.keywords.iter().map(|k| k.keyword.as_str()).collect();
+        lines.push(Line::from(format!(
+            "   {} · {}",
+            f.context.join(", "),
+            keywords.join(", ")
+        )));
+    }
+    lines
+}
+
+fn filter_form_lines(form: &FilterForm) -> Vec<Line<'static>> {
+    let focus = |field: usize| {
+        if form.field == field {
+            theme().accent.add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let heading = if form.id.is_some() {
+        "Edit filter"
+    } else {
+        "New filter"
+    };
+    let cursor = if form.field == 0 { "▌" } else { "" };
+    let mut lines = vec![
+        Line::from(Span::styled(heading, theme().highlight)),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Title: {}{cursor}", form.title),
+            focus(0),
+        )),
+        Line::from("Applies to:"),
+    ];
+    for (i, (name, on)) in FILTER_CONTEXTS.iter().zip(form.context).enumerate() {
+        let mark = if on { "x" } else { " " };
+        lines.push(Line::from(Span::styled(
+            format!("  [{mark}] {name}"),
+            focus(i + 1),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        format!("Action: {}", filter_action_label(form.action)),
+        focus(FilterForm::ACTION_FIELD),
+    )));
+    lines
+}
+
+/// r[filter.manage.keywords]: keywords of one filter, with add/edit input.
+pub fn draw_filter_keywords(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    filter: Option<&Filter>,
+    selected: usize,
+    input: Option<&str>,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = format!(" Keywords · {} ", filter.map_or("", |f| f.title.as_str()));
+    let title = Paragraph::new(title).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let keywords = filter.map_or(&[][..], |f| f.keywords.as_slice());
+    let mut lines: Vec<Line> = Vec::with_capacity(keywords.len().max(1));
+    if keywords.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No keywords. Press [a] to add one.",
+            theme().muted,
+        )));
+    }
+    for (i, k) in keywords.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let whole = if k.whole_word { "  (whole word)" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", k.keyword), style),
+            Span::styled(whole, theme().muted),
+        ]));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if let Some(input) = input {
+        let prompt = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("Keyword: {input}▌"),
+                theme().highlight,
+            )),
+            Line::from(Span::styled(message, theme().warning)),
+        ]);
+        frame.render_widget(prompt, chunks[2]);
+    } else if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::FilterKeywords,
+            &[
+                (Action::New, "add"),
+                (Action::Edit, "edit"),
+                (Action::WholeWord, "whole word"),
+                (Action::Delete, "delete"),
+                (Action::Back, "back"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[list.manage.list] r[list.manage.edit]: the user's lists and the create/rename form.
+pub fn draw_lists(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    lists: &[List],
+    selected: usize,
+    form: Option<&ListForm>,
+    confirm_delete: bool,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(" Lists ").block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let lines = form.map_or_else(|| list_lines(lists, selected), list_form_lines);
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if confirm_delete {
+        let prompt =
+            Paragraph::new("Delete this list? [y] yes  [any other key] no").style(theme().warning);
+        frame.render_widget(prompt, chunks[2]);
+    } else if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = if form.is_some() {
+        " [Tab]/[↑]/[↓] field  [Space] change  [Enter] save  [Esc] cancel ".to_string()
+    } else {
+        key_hint_text(
+            keymap,
+            KeyView::Lists,
+            &[
+                (Action::Open, "members"),
+                (Action::New, "new"),
+                (Action::Edit, "edit"),
+                (Action::Delete, "delete"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[3],
+    );
+}
+
+const fn replies_policy_label(policy: RepliesPolicy) -> &'static str {
+    match policy {
+        RepliesPolicy::Followed => "replies to any followed user",
+        RepliesPolicy::List => "replies to list members",
+        RepliesPolicy::None => "no replies",
+    }
+}
+
+fn list_lines(lists: &[List], selected: usize) -> Vec<Line<'static>> {
+    if lists.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No lists. Press [n] to create one.",
+            theme().muted,
+        ))];
+    }
+    lists
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let style = if i == selected {
+                theme().selection
+            } else {
+                Style::default()
+            };
+            let exclusive = if l.exclusive { " · exclusive" } else { "" };
+            Line::from(vec![
+                Span::styled(format!(" {} ", l.title), style.add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" {}{exclusive}", replies_policy_label(l.replies_policy)),
+                    theme().muted,
+                ),
+            ])
+        })
+        .collect()
+}
+
+fn list_form_lines(form: &ListForm) -> Vec<Line<'static>> {
+    let focus = |field: usize| {
+        if form.field == field {
+            theme().accent.add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let heading = if form.id.is_some() {
+        "Edit list"
+    } else {
+        "New list"
+    };
+    let cursor = if form.field == 0 { "▌" } else { "" };
+    let exclusive = if form.exclusive { "x" } else { " " };
+    vec![
+        Line::from(Span::styled(heading, theme().highlight)),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Title: {}{cursor}", form.title),
+            focus(0),
+        )),
+        Line::from(Span::styled(
+            format!("Show: {}", replies_policy_label(form.replies_policy)),
+            focus(1),
+        )),
+        Line::from(Span::styled(
+            format!("[{exclusive}] Exclusive (hide members' posts from Home)"),
+            focus(2),
+        )),
+    ]
+}
+
+/// r[list.manage.members]: accounts in a list, with a prompt to add one by handle.
+pub fn draw_list_members(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    list: Option<&List>,
+    members: &[Account],
+    selected: usize,
+    input: Option<&str>,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = format!(" Members · {} ", list.map_or("", |l| l.title.as_str()));
+    let title = Paragraph::new(title).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(members.len().max(1));
+    if members.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No members. Press [a] to add an account you follow.",
+            theme().muted,
+        )));
+    }
+    for (i, a) in members.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", a.display_name), style),
+            Span::styled(format!(" @{}", a.handle()), theme().muted),
+        ]));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if let Some(input) = input {
+        let prompt = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("Add account: @{input}▌"),
+                theme().highlight,
+            )),
+            Line::from(Span::styled(message, theme().warning)),
+        ]);
+        frame.render_widget(prompt, chunks[2]);
+    } else if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::ListMembers,
+            &[
+                (Action::New, "add"),
+                (Action::Delete, "remove"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[list.add-author]: toggle a toot author's membership of each list.
+pub fn draw_list_picker(
+    frame: &mut Frame,
+    account: Option<&Account>,
+    lists: &[List],
+    member_of: &[String],
+    selected: usize,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = format!(" Add @{} to list… ", account.map_or("", |a| a.handle()));
+    let title = Paragraph::new(title).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(lists.len().max(1));
+    if lists.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No lists yet. Create one with [L] from the timeline.",
+            theme().muted,
+        )));
+    }
+    for (i, l) in lists.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let mark = if member_of.contains(&l.id) { "x" } else { " " };
+        lines.push(Line::from(Span::styled(
+            format!(" [{mark}] {} ", l.title),
+            style,
+        )));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message)
+            .style(theme().warning)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        " [↑]/[↓]  [Space]/[Enter] add or remove  [Esc] back ",
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[follow-requests.list] r[follow-requests.decide]: pending follow requests to approve.
+pub fn draw_follow_requests(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    requests: &[Account],
+    selected: usize,
+    has_more: bool,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let more = if has_more { "+" } else { "" };
+    let title = Paragraph::new(format!(" Follow requests · {}{more} ", requests.len())).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(requests.len().max(1));
+    if requests.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No pending follow requests.",
+            theme().muted,
+        )));
+    }
+    for (i, a) in requests.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", a.display_name), style),
+            Span::styled(format!(" @{}", a.handle()), theme().muted),
+        ]));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::FollowRequests,
+            &[
+                (Action::Authorize, "authorize"),
+                (Action::Reject, "reject"),
+                (Action::LoadMore, "more"),
+                (Action::Refresh, "refresh"),
+                (Action::Back, "back"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// Relationship badges for an account in the follows browser. r[follows.badges]
+fn relationship_badges(tab: FollowsTab, rel: Option<&Relationship>) -> Vec<&'static str> {
+    let Some(rel) = rel else {
+        return Vec::new();
+    };
+    let mut badges = Vec::new();
+    if rel.following && rel.followed_by {
+        badges.push("mutual");
+    } else if tab == FollowsTab::Following && !rel.following && !rel.requested {
+        badges.push("not following");
+    }
+    if rel.requested {
+        badges.push("requested");
+    }
+    if rel.muting {
+        badges.push("muted");
+    }
+    badges
+}
+
+/// r[follows.list] r[follows.badges] r[follows.search] r[follows.bulk]: followers/following
+/// with marks, search, list chooser and bulk progress.
+pub fn draw_follows(frame: &mut Frame, keymap: &Keymap, follows: &FollowsBrowser, lists: &[List]) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let more = if follows.next_max_id.is_some() {
+        ", more with [m]"
+    } else {
+        ""
+    };
+    let title = format!(
+        " {} · {} loaded{more}{} ",
+        match follows.tab {
+            FollowsTab::Following => "Following",
+            FollowsTab::Followers => "Followers",
+        },
+        follows.accounts.len(),
+        if follows.marked.is_empty() {
+            String::new()
+        } else {
+            format!(" · {} marked", follows.marked.len())
+        }
+    );
+    let title = Paragraph::new(title).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let search_style = if follows.searching {
+        theme().highlight
+    } else {
+        theme().muted
+    };
+    let cursor = if follows.searching { "▌" } else { "" };
+    frame.render_widget(
+        Paragraph::new(format!(" Search: {}{cursor}", follows.query)).style(search_style),
+        chunks[1],
+    );
+
+    let list_area = chunks[2];
+    let rows = list_area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(choice) = follows.list_choice {
+        lines.push(Line::from(Span::styled(
+            "Add to which list?",
+            theme().highlight,
+        )));
+        for (i, l) in lists.iter().enumerate() {
+            let style = if i == choice {
+                theme().selection
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(Span::styled(format!(" {} ", l.title), style)));
+        }
+    } else {
+        let filtered = follows.filtered();
+        if filtered.is_empty() {
+            lines.push(Line::from(Span::styled("No accounts.", theme().muted)));
+        }
+        let start = follows.selected.saturating_sub(rows.saturating_sub(1));
+        for (pos, &i) in filtered.iter().enumerate().skip(start).take(rows.max(1)) {
+            let a = &follows.accounts[i];
+            let style = if pos == follows.selected {
+                theme().selection
+            } else {
+                Style::default()
+            };
+            let mark = if follows.marked.contains(&a.id) {
+                "x"
+            } else {
+                " "
+            };
+            let mut spans = vec![
+                Span::styled(format!(" [{mark}] {} ", a.display_name), style),
+                Span::styled(format!(" @{}", a.handle()), theme().muted),
+            ];
+            let badges = relationship_badges(follows.tab, follows.relationships.get(&a.id));
+            if !badges.is_empty() {
+                spans.push(Span::styled(
+                    format!("  {}", badges.join(" · ")),
+                    theme().mark,
+                ));
+            }
+            if let Some(err) = follows.errors.get(&a.id) {
+                spans.push(Span::styled(format!("  ✗ {err}"), theme().error));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    let block = Block::default().borders(Borders::ALL);
+    frame.render_widget(Paragraph::new(lines).block(block), list_area);
+
+    let status = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).split(chunks[3]);
+    if let Some(ref job) = follows.job {
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = job.done() as f64 / job.total.max(1) as f64;
+        let gauge = LineGauge::default()
+            .filled_style(theme().highlight)
+            .label(format!(
+                "{} {}/{}",
+                job.action.label(),
+                job.done(),
+                job.total
+            ))
+            .ratio(ratio.clamp(0.0, 1.0));
+        frame.render_widget(gauge, status[0]);
+    } else if let Some(ref action) = follows.confirm {
+        let n = follows.marked.len().max(1);
+        let prompt = Paragraph::new(format!(
+            "{} {n} account(s)? [y] yes  [any other key] no",
+            action.label()
+        ))
+        .style(theme().warning);
+        frame.render_widget(prompt, status[0]);
+    }
+    if !follows.message.is_empty() {
+        let msg = Paragraph::new(follows.message.as_str()).style(theme().warning);
+        frame.render_widget(msg, status[1]);
+    }
+
+    let help = if follows.list_choice.is_some() {
+        " [↑]/[↓]  [Enter] add marked accounts  [Esc] cancel ".to_string()
+    } else if follows.searching {
+        " type to search  [Enter] done  [Esc] clear ".to_string()
+    } else {
+        key_hint_text(
+            keymap,
+            KeyView::Follows,
+            &[
+                (Action::Mark, "mark"),
+                (Action::ClearMarks, "clear"),
+                (Action::Search, "search"),
+                (Action::ToggleFollow, "follow/unfollow"),
+                (Action::Unfollow, "unfollow"),
+                (Action::Mute, "mute"),
+                (Action::AddToList, "add to list"),
+                (Action::LoadMore, "more"),
+                (Action::NextTab, "followers/following"),
+                (Action::Back, "back"),
+            ],
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[4],
+    );
+}
+
+/// Duration and scope of a mute, e.g. "until 2025-06-01 14:00 · notifications muted".
+/// r[moderation.mutes]
+fn mute_details(account: &Account, notifications: bool) -> String {
+    let until = account.mute_expires_at.as_deref().map_or_else(
+        || "indefinite".to_string(),
+        |t| format!("until {}", format_local(t)),
+    );
+    if notifications {
+        format!("{until} · notifications muted")
+    } else {
+        until
+    }
+}
+
+/// r[moderation.mutes] r[moderation.blocks] r[moderation.domains] r[moderation.domain-add]:
+/// mutes, blocks and domain blocks with undo and a hostname prompt.
+pub fn draw_moderation(frame: &mut Frame, keymap: &Keymap, moderation: &ModerationBrowser) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let more = if moderation.next_max_id.is_some() {
+        ", more with [m]"
+    } else {
+        ""
+    };
+    let title = Paragraph::new(format!(" Moderation · {} loaded{more} ", moderation.len())).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let tabs: Vec<Span> = [
+        (ModerationTab::Mutes, "Muted"),
+        (ModerationTab::Blocks, "Blocked"),
+        (ModerationTab::Domains, "Blocked domains"),
+    ]
+    .into_iter()
+    .map(|(tab, label)| {
+        let style = if tab == moderation.tab {
+            theme().accent.add_modifier(Modifier::BOLD)
+        } else {
+            theme().muted
+        };
+        Span::styled(format!(" {label} "), style)
+    })
+    .collect();
+    frame.render_widget(Paragraph::new(Line::from(tabs)), chunks[1]);
+
+    let list_area = chunks[2];
+    let rows = list_area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    if moderation.is_empty() {
+        let empty = match moderation.tab {
+            ModerationTab::Mutes => "No muted accounts.",
+            ModerationTab::Blocks => "No blocked accounts.",
+            ModerationTab::Domains => "No blocked domains.",
+        };
+        lines.push(Line::from(Span::styled(empty, theme().muted)));
+    }
+    let start = moderation.selected.saturating_sub(rows.saturating_sub(1));
+    for i in (start..moderation.len()).take(rows.max(1)) {
+        let style = if i == moderation.selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let line = match moderation.tab {
+            ModerationTab::Domains => {
+                Line::from(Span::styled(format!(" {} ", moderation.domains[i]), style))
+            }
+            tab => {
+                let a = &moderation.accounts[i];
+                let mut spans = vec![
+                    Span::styled(format!(" {} ", a.display_name), style),
+                    Span::styled(format!(" @{}", a.handle()), theme().muted),
+                ];
+                if tab == ModerationTab::Mutes {
+                    let notifications = moderation.muting_notifications.contains(&a.id);
+                    spans.push(Span::styled(
+                        format!("  {}", mute_details(a, notifications)),
+                        theme().mark,
+                    ));
+                }
+                Line::from(spans)
+            }
+        };
+        lines.push(line);
+    }
+    let block = Block::default().borders(Borders::ALL);
+    frame.render_widget(Paragraph::new(lines).block(block), list_area);
+
+    let status = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).split(chunks[3]);
+    if let Some(ref input) = moderation.domain_input {
+        let prompt = Paragraph::new(format!("Block domain: {input}▌")).style(theme().highlight);
+        frame.render_widget(prompt, status[0]);
+    }
+    if !moderation.message.is_empty() {
+        let msg = Paragraph::new(moderation.message.as_str()).style(theme().warning);
+        frame.render_widget(msg, status[1]);
+    }
+
+    let help = if moderation.domain_input.is_some() {
+        " type a hostname  [Enter] block  [Esc] cancel ".to_string()
+    } else {
+        let undo = match moderation.tab {
+            ModerationTab::Mutes => "unmute",
+            ModerationTab::Blocks => "unblock",
+            ModerationTab::Domains => "unblock domain",
+        };
+        key_hint_text(
+            keymap,
+            KeyView::Moderation,
+            &[
+                (Action::Undo, undo),
+                (Action::BlockDomain, "block domain"),
+                (Action::LoadMore, "more"),
+                (Action::Refresh, "refresh"),
+                (Action::NextTab, "next list"),
+                (Action::Back, "back"),
+            ],
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[4],
+    );
+}
+
+/// Category names and what they mean, as offered in the report flow. r[report.category]
+const fn report_category_label(category: ReportCategory) -> (&'static str, &'static str) {
+    match category {
+        ReportCategory::Spam => (
+            "Spam",
+            "malicious links, fake engagement or repetitive replies",
+        ),
+        ReportCategory::Legal => ("Illegal", "content you believe breaks the law"),
+        ReportCategory::Violation => ("Breaks server rules", "you will pick the rules"),
+        ReportCategory::Other => ("Something else", "none of the above"),
+    }
+}
+
+/// r[report.open] r[report.category] r[report.rules] r[report.statuses] r[report.comment]:
+/// one step of the report flow, with the steps shown as a breadcrumb.
+pub fn draw_report(frame: &mut Frame, keymap: &Keymap, form: &ReportForm) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(format!(" Report @{} ", form.account.handle())).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut crumbs: Vec<Span> = Vec::new();
+    for (i, step) in form.steps().into_iter().enumerate() {
+        if i > 0 {
+            crumbs.push(Span::styled(" › ", theme().muted));
+        }
+        let label = match step {
+            ReportStep::Category => "Category",
+            ReportStep::Rules => "Rules",
+            ReportStep::Statuses => "Posts",
+            ReportStep::Comment => "Comment",
+        };
+        let style = if step == form.step {
+            theme().accent.add_modifier(Modifier::BOLD)
+        } else {
+            theme().muted
+        };
+        crumbs.push(Span::styled(label, style));
+    }
+    frame.render_widget(Paragraph::new(Line::from(crumbs)), chunks[1]);
+
+    let selected_style = theme().selection;
+    let row_style = |i: usize| {
+        if i == form.cursor {
+            selected_style
+        } else {
+            Style::default()
+        }
+    };
+    let check = |on: bool| if on { "[x]" } else { "[ ]" };
+    let list_area = chunks[2];
+    let rows = list_area.height.saturating_sub(2) as usize;
+    let start = form.cursor.saturating_sub(rows.saturating_sub(1));
+    let mut lines: Vec<Line> = Vec::new();
+    match form.step {
+        ReportStep::Category => {
+            lines.push(Line::from("Why are you reporting this account?"));
+            for (i, category) in ReportCategory::ALL.into_iter().enumerate() {
+                let (name, hint) = report_category_label(category);
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {name} "), row_style(i)),
+                    Span::styled(format!("  {hint}"), theme().muted),
+                ]));
+            }
+        }
+        ReportStep::Rules => {
+            for (i, rule) in form.rules.iter().enumerate().skip(start).take(rows.max(1)) {
+                let on = form.rule_ids.contains(&rule.id);
+                lines.push(Line::from(Span::styled(
+                    format!(" {} {}. {} ", check(on), i + 1, rule.text),
+                    row_style(i),
+                )));
+            }
+        }
+        ReportStep::Statuses => {
+            if form.statuses.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No posts to attach.",
+                    theme().muted,
+                )));
+            }
+            for (i, status) in form
+                .statuses
+                .iter()
+                .enumerate()
+                .skip(start)
+                .take(rows.max(1))
+            {
+                let on = form.status_ids.contains(&status.id);
+                let text = strip_html(&status.content).replace('\n', " ");
+                lines.push(Line::from(vec![
+                    Span::styled(format!(" {} {} ", check(on), text), row_style(i)),
+                    Span::styled(
+                        format!("  {}", format_local(&status.created_at)),
+                        theme().muted,
+                    ),
+                ]));
+            }
+        }
+        ReportStep::Comment => {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{} · {} post(s) attached{}",
+                    report_category_label(form.category).0,
+                    form.status_ids.len(),
+                    if form.category == ReportCategory::Violation {
+                        format!(" · {} rule(s)", form.rule_ids.len())
+                    } else {
+                        String::new()
+                    }
+                ),
+                theme().muted,
+            )));
+            lines.push(Line::from(format!(
+                "Comment ({}/{}): {}▌",
+                form.comment.chars().count(),
+                ReportForm::COMMENT_LIMIT,
+                form.comment
+            )));
+            if form.is_remote() {
+                let server = form.account.acct.rsplit('@').next().unwrap_or_default();
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "Forward to {server}: {}",
+                        if form.forward { "yes" } else { "no" }
+                    ),
+                    theme().highlight,
+                )));
+            }
+        }
+    }
+    let block = Block::default().borders(Borders::ALL);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        list_area,
+    );
+
+    if !form.message.is_empty() {
+        let msg = Paragraph::new(form.message.as_str()).style(theme().warning);
+        frame.render_widget(msg, chunks[3]);
+    }
+
+    let help = match form.step {
+        ReportStep::Category => key_hint_text(
+            keymap,
+            KeyView::Report,
+            &[(Action::Next, "choose"), (Action::Back, "cancel")],
+        ),
+        ReportStep::Rules | ReportStep::Statuses => key_hint_text(
+            keymap,
+            KeyView::Report,
+            &[
+                (Action::Mark, "select"),
+                (Action::Next, "next"),
+                (Action::Back, "back"),
+            ],
+        ),
+        ReportStep::Comment if form.is_remote() => {
+            " type a comment  [Tab] forward on/off  [Enter] send report  [Esc] back ".to_string()
+        }
+        ReportStep::Comment => " type a comment  [Enter] send report  [Esc] back ".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[4],
+    );
+}
+
+/// Daily uses from a trend's history, oldest first, for a sparkline. r[explore.sparkline]
+fn history_series(history: &[TrendHistory]) -> Vec<u64> {
+    history
+        .iter()
+        .rev()
+        .map(|h| h.uses.parse().unwrap_or(0))
+        .collect()
+}
+
+/// People talking about a trend over the last two days, as Mastodon's own UI counts them.
+fn recent_accounts(history: &[TrendHistory]) -> u64 {
+    history
+        .iter()
+        .take(2)
+        .map(|h| h.accounts.parse::<u64>().unwrap_or(0))
+        .sum()
+}
+
+/// r[explore.trends] r[explore.sparkline]: trending tags, posts or links, with a week of
+/// usage as a sparkline per row and the selected item's link below.
+pub fn draw_explore(frame: &mut Frame, keymap: &Keymap, explore: &ExploreBrowser) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(" Explore · trending now ").block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let tab_spans: Vec<Span> = [
+        (TrendsKind::Tags, "Hashtags"),
+        (TrendsKind::Statuses, "Posts"),
+        (TrendsKind::Links, "News"),
+    ]
+    .into_iter()
+    .map(|(tab, label)| {
+        let style = if tab == explore.tab {
+            theme().accent.add_modifier(Modifier::BOLD)
+        } else {
+            theme().muted
+        };
+        Span::styled(format!(" {label} "), style)
+    })
+    .collect();
+    frame.render_widget(Paragraph::new(Line::from(tab_spans)), chunks[1]);
+
+    let block = Block::default().borders(Borders::ALL);
+    let inner = block.inner(chunks[2]);
+    frame.render_widget(block, chunks[2]);
+    let len = explore.trends.as_ref().map_or(0, Trends::len);
+    if len == 0 {
+        let empty = if explore.trends.is_some() {
+            "Nothing is trending here right now."
+        } else {
+            "Loading…"
+        };
+        frame.render_widget(Paragraph::new(empty).style(theme().muted), inner);
+    }
+    let rows = inner.height as usize;
+    let start = explore.selected.saturating_sub(rows.saturating_sub(1));
+    for (row, i) in (start..len).take(rows).enumerate() {
+        #[allow(clippy::cast_possible_truncation)] // row < inner.height
+        let y = inner.y + row as u16;
+        let cols = Layout::horizontal([Constraint::Min(10), Constraint::Length(8)]).split(
+            ratatui::layout::Rect {
+                y,
+                height: 1,
+                ..inner
+            },
+        );
+        let style = if i == explore.selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let dim = theme().muted;
+        let (line, history) = match explore.trends.as_ref() {
+            Some(Trends::Tags(tags)) => {
+                let t = &tags[i];
+                let line = Line::from(vec![
+                    Span::styled(format!(" #{} ", t.name), style),
+                    Span::styled(
+                        format!(
+                            "  {} people in the past 2 days",
+                            recent_accounts(&t.history)
+                        ),
+                        dim,
+                    ),
+                ]);
+                (line, t.history.as_slice())
+            }
+            Some(Trends::Statuses(statuses)) => {
+                let (s, _) = display_status(&statuses[i]);
+                let text = strip_html(&s.content).replace('\n', " ");
+                let line = Line::from(vec![
+                    Span::styled(format!(" @{} ", s.account.handle()), style),
+                    Span::raw(format!(" {text}")),
+                ]);
+                (line, &[][..])
+            }
+            Some(Trends::Links(links)) => {
+                let l = &links[i];
+                let line = Line::from(vec![
+                    Span::styled(format!(" {} ", l.title), style),
+                    Span::styled(format!("  {}", l.provider_name), dim),
+                ]);
+                (line, l.history.as_slice())
+            }
+            None => break,
+        };
+        frame.render_widget(Paragraph::new(line), cols[0]);
+        if !history.is_empty() {
+            let sparkline = Sparkline::default()
+                .data(history_series(history))
+                .style(theme().highlight);
+            frame.render_widget(sparkline, cols[1]);
+        }
+    }
+
+    let detail = match explore.trends.as_ref() {
+        Some(Trends::Tags(tags)) => tags
+            .get(explore.selected)
+            .map(|t| (String::new(), t.url.as_str())),
+        Some(Trends::Links(links)) => links
+            .get(explore.selected)
+            .map(|l| (l.description.clone(), l.url.as_str())),
+        Some(Trends::Statuses(_)) | None => None,
+    };
+    if let Some((description, url)) = detail {
+        let rows = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(chunks[3]);
+        frame.render_widget(
+            Paragraph::new(description)
+                .style(theme().muted)
+                .wrap(Wrap { trim: true }),
+            rows[0],
+        );
+        frame.render_widget(Link::new(url, url), rows[1]);
+    }
+
+    if !explore.message.is_empty() {
+        let msg = Paragraph::new(explore.message.as_str()).style(theme().warning);
+        frame.render_widget(msg, chunks[4]);
+    }
+
+    let open = (Action::Open, "open post");
+    let rest = [
+        (Action::NextTab, "hashtags/posts/news"),
+        (Action::Refresh, "refresh"),
+        (Action::Back, "back"),
+    ];
+    let hints: Vec<(Action, &str)> = if explore.tab == TrendsKind::Statuses {
+        std::iter::once(open).chain(rest).collect()
+    } else {
+        rest.to_vec()
+    };
+    let help = key_hint_text(keymap, KeyView::Explore, &hints);
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().dim()))),
+        chunks[5],
+    );
+}
+
+/// State of a queued write: waiting for its first try, backing off, or refused.
+fn outbox_item_state(item: &OutboxItem) -> String {
+    match (&item.error, item.failed) {
+        (Some(e), true) => format!("refused: {e}"),
+        (Some(e), false) => {
+            let at = item
+                .next_attempt_at
+                .as_deref()
+                .map(format_local)
+                .unwrap_or_default();
+            format!("attempt {} failed, retry {at}: {e}", item.attempts)
+        }
+        (None, _) => "sending…".to_string(),
+    }
+}
+
+/// r[outbox.view]: writes waiting to be sent, with their retry state.
+pub fn draw_outbox(
+    frame: &mut Frame,
+    keymap: &Keymap,
+    items: &[OutboxItem],
+    selected: usize,
+    message: &str,
+) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(format!(" Outbox · {} ", items.len())).block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines: Vec<Line> = Vec::with_capacity(items.len().max(1));
+    if items.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nothing waiting to be sent.",
+            theme().muted,
+        )));
+    }
+    for (i, item) in items.iter().enumerate() {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let (mark, mark_style) = if item.failed {
+            ("✗", theme().error)
+        } else {
+            ("…", theme().muted)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {mark} "), mark_style),
+            Span::styled(format!(" {} ", item.action.describe()), style),
+            Span::styled(format!(" {}", outbox_item_state(item)), mark_style),
+        ]));
+    }
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(para, chunks[1]);
+
+    if !message.is_empty() {
+        let msg = Paragraph::new(message).style(theme().warning);
+        frame.render_widget(msg, chunks[2]);
+    }
+
+    let help = Line::from(Span::styled(
+        key_hint_text(
+            keymap,
+            KeyView::Outbox,
+            &[
+                (Action::Send, "send now"),
+                (Action::Discard, "discard"),
+                (Action::Back, "back"),
+            ],
+        ),
+        Style::default().dim(),
+    ));
+    frame.render_widget(Paragraph::new(help), chunks[3]);
+}
+
+/// r[errors.debug]: the last error response: status, URL, time and the raw body
+/// (pretty-printed when it is JSON).
+pub fn draw_debug(frame: &mut Frame, failed: Option<&FailedRequest>) {
+    let area = frame.area();
+    let chunks = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .split(area);
+
+    let title = Paragraph::new(" Last API error ").block(
+        Block::default()
+            .borders(Borders::BOTTOM)
+            .border_style(theme().header),
+    );
+    frame.render_widget(title, chunks[0]);
+
+    let lines = failed.map_or_else(
+        || {
+            vec![Line::from(Span::styled(
+                "No API errors this session.",
+                theme().muted,
+            ))]
+        },
+        |f| {
+            let message = MastotuiError::from_response(f.status, &f.body).to_string();
+            let body = serde_json::from_str::<serde_json::Value>(&f.body)
+                .ok()
+                .and_then(|v| serde_json::to_string_pretty(&v).ok())
+                .unwrap_or_else(|| f.body.clone());
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    format!("{} {}", f.status, f.url),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(format_local(&f.at), theme().muted)),
+                Line::from(Span::styled(message, theme().warning)),
+                Line::from(""),
+            ];
+            lines.extend(body.lines().map(|l| Line::from(l.to_string())));
+            lines
+        },
+    );
+    let block = Block::default().borders(Borders::ALL);
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(para, chunks[1]);
+
+    let help = Line::from(Span::styled(" [Esc] back ", Style::default().dim()));
+    frame.render_widget(Paragraph::new(help), chunks[2]);
+}
+
+/// Overlay listing the bindings of the current view, drawn over it. r[keys.help]
+pub fn draw_help(frame: &mut Frame, title: &str, rows: &[(String, &str)]) {
+    let area = frame.area();
+    let key_width = rows
+        .iter()
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let text_width = rows
+        .iter()
+        .map(|(_, d)| d.chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = u16::try_from(key_width + text_width + 6)
+        .unwrap_or(u16::MAX)
+        .min(area.width);
+    let height = u16::try_from(rows.len() + 2)
+        .unwrap_or(u16::MAX)
+        .min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(format!(" {keys:<key_width$} "), theme().accent),
+                Span::raw(*description),
+            ])
+        })
+        .collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().header)
+        .title(format!(" Keys: {title} "))
+        .title_bottom(Line::styled(" any key to close ", Style::default().dim()));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// `:` command palette over the current view: the typed text, then the matching commands
+/// with their keys, best first. r[palette.open]
+pub fn draw_palette(frame: &mut Frame, input: &str, commands: &[(String, &str)], selected: usize) {
+    let area = frame.area();
+    let shown = commands.len().clamp(1, 12);
+    let width = area.width.min(60);
+    let height = u16::try_from(shown + 4)
+        .unwrap_or(u16::MAX)
+        .min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + area.height.min(2),
+        width,
+        height.min(area.height.saturating_sub(area.height.min(2))),
+    );
+    let rows = usize::from(popup.height.saturating_sub(4)).max(1);
+    let first = (selected + 1).saturating_sub(rows);
+    let mut lines = vec![
+        Line::from(Span::styled(format!(":{input}▌"), theme().highlight)),
+        Line::from(""),
+    ];
+    if commands.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching command",
+            theme().muted,
+        )));
+    }
+    let label_width = usize::from(width.saturating_sub(4));
+    for (i, (label, keys)) in commands.iter().enumerate().skip(first).take(rows) {
+        let style = if i == selected {
+            theme().selection
+        } else {
+            Style::default()
+        };
+        let pad = label_width.saturating_sub(label.chars().count() + keys.chars().count() + 2);
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {label}{} ", " ".repeat(pad)), style),
+            Span::styled((*keys).to_string(), theme().muted),
+        ]));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme().header)
+        .title(" Command ")
+        .title_bottom(Line::styled(
+            " [Enter] run  [Esc] close ",
+            Style::default().dim(),
+        ));
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify timeline.home.empty-state]
+    #[test]
+    fn empty_timeline_message_shown_when_no_toots() {
+        assert!(EMPTY_TIMELINE_MESSAGE.contains("No toots"));
+    }
+
+    // r[verify follows.badges]
+    #[test]
+    fn relationship_badges_show_mutual_requested_and_muted() {
+        let rel = |following, followed_by, requested, muting| Relationship {
+            id: "1".into(),
+            following,
+            followed_by,
+            requested,
+            muting,
+            muting_notifications: false,
+        };
+        assert_eq!(
+            relationship_badges(FollowsTab::Followers, Some(&rel(true, true, false, true))),
+            vec!["mutual", "muted"]
+        );
+        assert_eq!(
+            relationship_badges(FollowsTab::Followers, Some(&rel(false, true, true, false))),
+            vec!["requested"]
+        );
+        assert_eq!(
+            relationship_badges(
+                FollowsTab::Following,
+                Some(&rel(false, false, false, false))
+            ),
+            vec!["not following"]
+        );
+        assert!(relationship_badges(FollowsTab::Following, None).is_empty());
+    }
+
+    // r[verify moderation.mutes]
+    #[test]
+    fn mute_details_show_expiry_and_notifications() {
+        let mut account: Account = serde_json::from_value(serde_json::json!({
+            "id": "1", "display_name": "Ann", "username": "ann", "acct": "ann"
+        }))
+        .unwrap();
+        assert_eq!(mute_details(&account, false), "indefinite");
+        assert_eq!(
+            mute_details(&account, true),
+            "indefinite · notifications muted"
+        );
+        account.mute_expires_at = Some("not a date".into());
+        assert_eq!(mute_details(&account, false), "until not a date");
+    }
+
+    // r[verify explore.sparkline]
+    #[test]
+    fn trend_history_becomes_oldest_first_series() {
+        let history: Vec<TrendHistory> = serde_json::from_value(serde_json::json!([
+            { "day": "1700179200", "uses": "12", "accounts": "9" },
+            { "day": "1700092800", "uses": "4", "accounts": "3" },
+            { "day": "1700006400", "uses": "oops", "accounts": "1" }
+        ]))
+        .unwrap();
+        assert_eq!(history_series(&history), vec![0, 4, 12]);
+        assert_eq!(recent_accounts(&history), 12);
+    }
+
+    // r[verify toot.optimistic]
+    #[test]
+    fn status_counts_show_totals_and_own_actions() {
+        let status: Status = serde_json::from_value(serde_json::json!({
+            "id": "1", "uri": "u", "content": "", "created_at": "",
+            "account": { "id": "2", "display_name": "Ann", "username": "ann" },
+            "replies_count": 2, "reblogs_count": 3, "favourites_count": 4,
+            "favourited": true, "bookmarked": true
+        }))
+        .unwrap();
+        let spans = status_counts(&status);
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, " ↩ 2 ⟳ 3 ★ 4 ⚑");
+        assert_eq!(spans[1].style, theme().muted);
+        assert_eq!(spans[2].style, theme().favourited);
+    }
+
+    // r[verify ratelimit.status]
+    #[test]
+    fn timeline_footer_shows_api_quota() {
+        let backend = ratatui::backend::TestBackend::new(140, 6);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        let quota = RateLimit {
+            limit: 300,
+            remaining: 29,
+            reset: None,
+        };
+        let keymap = Keymap::default();
+        terminal
+            .draw(|f| {
+                draw_timeline(f, &keymap, "Home", &[], 0, 0, 0, Some(&quota), false, "");
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let footer: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, buffer.area.height - 1)].symbol())
+            .collect();
+        assert!(footer.contains("API 29/300"), "{footer}");
+    }
+
+    // r[verify keys.config]
+    #[test]
+    fn footer_hints_follow_the_keymap() {
+        let config = toml::from_str("[timeline]\ncompose = \"n\"\nquit = []\n").unwrap();
+        let keymap = Keymap::from_config(&config).unwrap();
+        let backend = ratatui::backend::TestBackend::new(140, 6);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                draw_timeline(f, &keymap, "Home", &[], 0, 0, 0, None, false, "");
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let footer: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, buffer.area.height - 1)].symbol())
+            .collect();
+        assert!(
+            footer.starts_with(" [k]/[j]  [enter] open  [n] post"),
+            "{footer}"
+        );
+        assert!(!footer.contains("quit"), "{footer}");
+    }
+
+    // r[verify errors.debug]
+    #[test]
+    fn debug_panel_shows_message_and_raw_body() {
+        let backend = ratatui::backend::TestBackend::new(80, 12);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        let failed = FailedRequest {
+            status: 422,
+            url: "https://example.com/api/v1/statuses".into(),
+            at: "2025-06-01T12:00:00Z".into(),
+            body: r#"{"error":"Validation failed: Text can't be blank"}"#.into(),
+        };
+        terminal.draw(|f| draw_debug(f, Some(&failed))).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(text.contains("422 https://example.com/api/v1/statuses"));
+        assert!(text.contains("Validation failed: Text can't be blank"));
+        assert!(text.contains(r#""error": "Validation failed"#), "{text}");
+    }
+
+    #[test]
+    fn wrap_text_respects_width_and_breaks_long_words() {
+        let lines = wrap_text("hello wonderful world\nabcdefghij", 8);
+        assert_eq!(
+            lines,
+            vec!["hello", "wonderfu", "l world", "abcdefgh", "ij"]
+        );
+    }
+    // r[verify mouse.links] r[verify mouse.hints]
+    #[test]
+    fn toot_detail_lists_web_links_where_they_can_be_clicked() {
+        let html = "<p>Hi <a href=\"https://a.example/@ann\" class=\"u-url mention\">@ann</a> \
+                    <a href=\"https://a.example/tags/rust\" class=\"mention hashtag\">#rust</a> \
+                    see <a href=\"https://docs.example/x\">docs</a> and \
+                    <a href=\"https://docs.example/x\">again</a></p>";
+        assert_eq!(status_links(html), vec!["https://docs.example/x"]);
+
+        let status: Status = serde_json::from_value(serde_json::json!({
+            "id": "1", "uri": "u", "content": html, "created_at": "",
+            "account": { "id": "2", "display_name": "Ann", "username": "ann" }
+        }))
+        .unwrap();
+        let backend = ratatui::backend::TestBackend::new(60, 20);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        let mut hits = HitMap::default();
+        terminal
+            .draw(|f| hits = draw_toot_detail(f, &Keymap::default(), &status, false, ""))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = (0..20).find(|&y| buffer[(1, y)].symbol() == "→").unwrap();
+        assert_eq!(
+            hits.at(10, row),
+            Some(&Hit::Link("https://docs.example/x".into()))
+        );
+        assert_eq!(hits.at(10, row + 1), None);
+        assert_eq!(hits.at(2, 19), Some(&Hit::Action(Action::Boost)));
+        assert_eq!(hits.at(11, 19), None, "the gap between two hints");
     }
 }
@@ -0,0 +1,446 @@
+//! In-process fake Mastodon server on 127.0.0.1 for end-to-end tests.
+//!
+//! Responses come from the JSON files in `tests/fixtures`. The server keeps the home
+//! timeline in memory, so posts and boosts show up on later reads, records every request
+//! it receives, and can be told to fail the next request to a path with an error fixture.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use serde_json::{json, Value};
+
+/// Access token the fake `/oauth/token` hands out for the code `GOOD_CODE`.
+pub const ACCESS_TOKEN: &str = "mock-access-token";
+/// Authorization code the fake `/oauth/token` accepts.
+pub const GOOD_CODE: &str = "good-code";
+/// Statuses per page, whatever `limit` asks for (real servers cap it too).
+pub const PAGE_SIZE: usize = 2;
+/// The `X-RateLimit-Limit` the server reports.
+pub const RATE_LIMIT: u32 = 300;
+
+/// A fixture from `tests/fixtures`.
+pub fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{path}: {e}"))
+}
+
+/// One request as the server saw it.
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub body: String,
+}
+
+#[derive(Default)]
+struct Mock {
+    base_url: String,
+    requests: Vec<Recorded>,
+    /// Home timeline statuses, newest first.
+    home: Vec<Value>,
+    /// Error fixtures to answer the next requests to `(method, path)` with, and how many.
+    failures: HashMap<(Method, String), (StatusCode, Value, u32)>,
+    /// Idempotency keys already used, with the status they created.
+    idempotent: HashMap<String, Value>,
+    next_id: u64,
+    /// The logged-in account approves its followers.
+    locked: bool,
+}
+
+type Shared = Arc<Mutex<Mock>>;
+
+fn lock(mock: &Shared) -> MutexGuard<'_, Mock> {
+    mock.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A running fake instance; stops when dropped.
+pub struct MockMastodon {
+    pub url: String,
+    mock: Shared,
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl MockMastodon {
+    pub fn start() -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("addr"));
+        let mock: Shared = Arc::new(Mutex::new(Mock {
+            base_url: url.clone(),
+            home: serde_json::from_value(fixture("home.json")).expect("home.json"),
+            next_id: 9000,
+            ..Mock::default()
+        }));
+        let app = router(mock.clone());
+        runtime.spawn(async move { axum::serve(listener, app).await });
+        Self {
+            url,
+            mock,
+            _runtime: runtime,
+        }
+    }
+
+    /// Every request so far, oldest first.
+    pub fn requests(&self) -> Vec<Recorded> {
+        lock(&self.mock).requests.clone()
+    }
+
+    /// Requests with this method and path.
+    pub fn requests_to(&self, method: &Method, path: &str) -> Vec<Recorded> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == *method && r.path == path)
+            .collect()
+    }
+
+    /// Answer the next `method` request to `path` with `status` and an error fixture; a 429
+    /// comes with `Retry-After: 60`.
+    pub fn fail_next(&self, method: Method, path: &str, status: u16, error_fixture: &str) {
+        self.fail_times(method, path, status, error_fixture, 1);
+    }
+
+    /// Like `fail_next`, for the next `times` requests.
+    pub fn fail_times(
+        &self,
+        method: Method,
+        path: &str,
+        status: u16,
+        error_fixture: &str,
+        times: u32,
+    ) {
+        let status = StatusCode::from_u16(status).expect("status");
+        lock(&self.mock).failures.insert(
+            (method, path.to_string()),
+            (status, fixture(error_fixture), times),
+        );
+    }
+
+    /// Make the logged-in account a locked one, which gets follow requests.
+    pub fn lock_account(&self) {
+        lock(&self.mock).locked = true;
+    }
+
+    /// A status on the home timeline (or boosted inside one) by id.
+    pub fn status(&self, id: &str) -> Option<Value> {
+        find_status(&lock(&self.mock).home, id).cloned()
+    }
+}
+
+fn router(mock: Shared) -> Router {
+    Router::new()
+        .route("/api/v1/apps", post(register_app))
+        .route("/oauth/token", post(token))
+        .route("/api/v1/accounts/verify_credentials", get(account))
+        .route("/api/v1/follow_requests", get(|| async { Json(json!([])) }))
+        .route("/api/v1/timelines/home", get(home_timeline))
+        .route("/api/v1/timelines/public", get(public_timeline))
+        .route("/api/v1/timelines/list/{id}", get(list_timeline))
+        .route(
+            "/api/v1/lists",
+            get(|| async { Json(fixture("lists.json")) }),
+        )
+        .route("/api/v1/markers", get(|| async { Json(json!({})) }))
+        .route("/api/v1/notifications", get(|| async { Json(json!([])) }))
+        .route("/api/v1/statuses", post(create_status))
+        .route("/api/v1/statuses/{id}", get(get_status))
+        .route("/api/v1/statuses/{id}/{action}", post(status_action))
+        .fallback(|| async { (StatusCode::NOT_FOUND, Json(fixture("error_404.json"))) })
+        .layer(middleware::from_fn_with_state(mock.clone(), record))
+        .with_state(mock)
+}
+
+/// Record the request, check the token, apply an injected failure, and add rate limit
+/// headers to the response.
+async fn record(State(mock): State<Shared>, request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let bytes = to_bytes(body, 1 << 20).await.unwrap_or_default();
+    let path = parts.uri.path().to_string();
+    let remaining = {
+        let mut m = lock(&mock);
+        m.requests.push(Recorded {
+            method: parts.method.clone(),
+            path: path.clone(),
+            query: parts.uri.query().map(str::to_string),
+            idempotency_key: parts
+                .headers
+                .get("idempotency-key")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+        });
+        RATE_LIMIT.saturating_sub(u32::try_from(m.requests.len()).unwrap_or(RATE_LIMIT))
+    };
+    let authorized = parts
+        .headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v == format!("Bearer {ACCESS_TOKEN}"));
+    let failure = {
+        let mut mock = lock(&mock);
+        let key = (parts.method.clone(), path.clone());
+        let failure = mock.failures.get_mut(&key).map(|(status, body, times)| {
+            *times -= 1;
+            (*status, body.clone(), *times)
+        });
+        if failure.as_ref().is_some_and(|(_, _, left)| *left == 0) {
+            mock.failures.remove(&key);
+        }
+        failure
+    };
+    let mut response = if let Some((status, body, _)) = failure {
+        let mut response = (status, Json(body)).into_response();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            // Longer than the client is willing to wait on the UI thread.
+            response
+                .headers_mut()
+                .insert("retry-after", HeaderValue::from_static("60"));
+        }
+        response
+    } else if path.starts_with("/api/") && path != "/api/v1/apps" && !authorized {
+        let body = json!({ "error": "The access token is invalid" });
+        (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+    } else {
+        next.run(Request::from_parts(parts, Body::from(bytes)))
+            .await
+    };
+    let headers = response.headers_mut();
+    headers.insert("x-ratelimit-limit", HeaderValue::from(RATE_LIMIT));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+    headers.insert(
+        "x-ratelimit-reset",
+        HeaderValue::from_static("2099-01-01T00:00:00.000Z"),
+    );
+    response
+}
+
+async fn register_app(Json(body): Json<Value>) -> Response {
+    if body["client_name"] != "mastotui" || body["redirect_uris"].as_str().is_none() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(fixture("error_422.json")),
+        )
+            .into_response();
+    }
+    Json(fixture("app.json")).into_response()
+}
+
+async fn token(Form(form): Form<HashMap<String, String>>) -> Response {
+    let app = fixture("app.json");
+    let field = |k: &str| form.get(k).map(String::as_str);
+    let client_ok = field("client_id") == app["client_id"].as_str()
+        && field("client_secret") == app["client_secret"].as_str();
+    let grant_ok = match field("grant_type") {
+        Some("authorization_code") => {
+            field("code") == Some(GOOD_CODE) && field("code_verifier").is_some()
+        }
+        Some("client_credentials") => true,
+        _ => false,
+    };
+    if client_ok && grant_ok {
+        Json(fixture("token.json")).into_response()
+    } else {
+        let body = json!({
+            "error": "invalid_grant",
+            "error_description": "The provided authorization grant is invalid, expired, revoked, does not match the redirection URI used in the authorization request, or was issued to another client."
+        });
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+/// One page of `statuses` older than `max_id`, with a Link header to the next page.
+fn page(
+    base_url: &str,
+    path: &str,
+    statuses: &[Value],
+    query: &HashMap<String, String>,
+) -> Response {
+    let start = query.get("max_id").map_or(0, |max_id| {
+        statuses
+            .iter()
+            .position(|s| s["id"] == max_id.as_str())
+            .map_or(statuses.len(), |i| i + 1)
+    });
+    let limit = query
+        .get("limit")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(20)
+        .min(PAGE_SIZE);
+    let items: Vec<Value> = statuses.iter().skip(start).take(limit).cloned().collect();
+    let mut response = Json(&items).into_response();
+    if let Some(last) = items
+        .last()
+        .filter(|_| start + items.len() < statuses.len())
+    {
+        let id = last["id"].as_str().unwrap_or_default();
+        let link = format!("<{base_url}{path}?max_id={id}>; rel=\"next\"");
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            response.headers_mut().insert("link", value);
+        }
+    }
+    response
+}
+
+async fn account(State(mock): State<Shared>) -> Json<Value> {
+    let mut account = fixture("account.json");
+    account["locked"] = json!(lock(&mock).locked);
+    Json(account)
+}
+
+async fn home_timeline(
+    State(mock): State<Shared>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let m = lock(&mock);
+    page(&m.base_url, "/api/v1/timelines/home", &m.home, &query)
+}
+
+/// The public timeline is the home timeline; like some real instances, it needs a token
+/// (an app token will do).
+async fn public_timeline(
+    State(mock): State<Shared>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    let m = lock(&mock);
+    page(&m.base_url, "/api/v1/timelines/public", &m.home, &query)
+}
+
+async fn list_timeline(
+    State(mock): State<Shared>,
+    Path(id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Response {
+    if id != "42" {
+        return (StatusCode::NOT_FOUND, Json(fixture("error_404.json"))).into_response();
+    }
+    let statuses: Vec<Value> =
+        serde_json::from_value(fixture("list_timeline.json")).unwrap_or_default();
+    let base_url = lock(&mock).base_url.clone();
+    page(
+        &base_url,
+        &format!("/api/v1/timelines/list/{id}"),
+        &statuses,
+        &query,
+    )
+}
+
+async fn create_status(
+    State(mock): State<Shared>,
+    headers: axum::http::HeaderMap,
+    Json(body): Json<Value>,
+) -> Response {
+    let text = body["status"].as_str().unwrap_or_default();
+    if text.trim().is_empty() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(fixture("error_422.json")),
+        )
+            .into_response();
+    }
+    let key = headers
+        .get("idempotency-key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let mut m = lock(&mock);
+    if let Some(status) = key.as_ref().and_then(|k| m.idempotent.get(k)) {
+        return Json(status.clone()).into_response();
+    }
+    m.next_id += 1;
+    let id = m.next_id.to_string();
+    let status = json!({
+        "id": id,
+        "uri": format!("{}/statuses/{id}", m.base_url),
+        "content": format!("<p>{text}</p>"),
+        "account": fixture("account.json"),
+        "created_at": "2025-06-01T13:00:00.000Z",
+        "visibility": body["visibility"].as_str().unwrap_or("public"),
+        "reblog": null,
+        "favourited": false,
+        "reblogged": false,
+        "bookmarked": false,
+        "in_reply_to_id": body["in_reply_to_id"],
+        "in_reply_to_account_id": null,
+        "media_attachments": []
+    });
+    m.home.insert(0, status.clone());
+    if let Some(k) = key {
+        m.idempotent.insert(k, status.clone());
+    }
+    Json(status).into_response()
+}
+
+async fn get_status(State(mock): State<Shared>, Path(id): Path<String>) -> Response {
+    let status = find_status(&lock(&mock).home, &id).cloned();
+    status.map_or_else(
+        || (StatusCode::NOT_FOUND, Json(fixture("error_404.json"))).into_response(),
+        |status| Json(status).into_response(),
+    )
+}
+
+/// reblog/unreblog, favourite/unfavourite and bookmark/unbookmark: set the flag and count
+/// once per change, like Mastodon, and return the status.
+async fn status_action(
+    State(mock): State<Shared>,
+    Path((id, action)): Path<(String, String)>,
+) -> Response {
+    let (flag, count, on) = match action.as_str() {
+        "reblog" => ("reblogged", Some("reblogs_count"), true),
+        "unreblog" => ("reblogged", Some("reblogs_count"), false),
+        "favourite" => ("favourited", Some("favourites_count"), true),
+        "unfavourite" => ("favourited", Some("favourites_count"), false),
+        "bookmark" => ("bookmarked", None, true),
+        "unbookmark" => ("bookmarked", None, false),
+        _ => return (StatusCode::NOT_FOUND, Json(fixture("error_404.json"))).into_response(),
+    };
+    let mut m = lock(&mock);
+    let Some(status) = find_status_mut(&mut m.home, &id) else {
+        return (StatusCode::NOT_FOUND, Json(fixture("error_404.json"))).into_response();
+    };
+    if status[flag] != on {
+        if let Some(count) = count {
+            let n = status[count].as_u64().unwrap_or(0);
+            status[count] = json!(if on { n + 1 } else { n.saturating_sub(1) });
+        }
+    }
+    status[flag] = json!(on);
+    Json(status.clone()).into_response()
+}
+
+fn find_status<'a>(home: &'a [Value], id: &str) -> Option<&'a Value> {
+    home.iter().find_map(|s| {
+        if s["id"] == id {
+            Some(s)
+        } else if s["reblog"]["id"] == id {
+            Some(&s["reblog"])
+        } else {
+            None
+        }
+    })
+}
+
+fn find_status_mut<'a>(home: &'a mut [Value], id: &str) -> Option<&'a mut Value> {
+    home.iter_mut().find_map(|s| {
+        if s["id"] == id {
+            Some(s)
+        } else if s["reblog"]["id"] == id {
+            Some(&mut s["reblog"])
+        } else {
+            None
+        }
+    })
+}
@@ -0,0 +1,114 @@
+//! Shared harness for end-to-end tests: a fake Mastodon server, an in-memory credential
+//! store, throwaway config/cache/data dirs, and an `App` driven through `handle_key` and
+//! drawn on a `TestBackend` like the main loop does.
+
+pub mod mock_server;
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use mastotui::app::App;
+use mastotui::credential::{set_store, MemoryStore};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+pub use mock_server::{fixture, MockMastodon, GOOD_CODE};
+
+/// Tests share the process environment (XDG dirs) and the credential store, so they take
+/// turns.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+pub struct Harness {
+    pub app: App,
+    pub server: MockMastodon,
+    terminal: Terminal<TestBackend>,
+    _dirs: tempfile::TempDir,
+    _serial: MutexGuard<'static, ()>,
+}
+
+impl Harness {
+    /// A fresh app on the login screen, with nothing stored, and a fake instance.
+    pub fn start() -> Self {
+        let serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+        let dirs = tempfile::tempdir().expect("tempdir");
+        for (var, dir) in [
+            ("XDG_CONFIG_HOME", "config"),
+            ("XDG_CACHE_HOME", "cache"),
+            ("XDG_DATA_HOME", "data"),
+        ] {
+            std::env::set_var(var, dirs.path().join(dir));
+        }
+        set_store(Arc::new(MemoryStore::default()));
+        let server = MockMastodon::start();
+        let app = App::new().expect("app");
+        let terminal = Terminal::new(TestBackend::new(160, 30)).expect("terminal");
+        Self {
+            app,
+            server,
+            terminal,
+            _dirs: dirs,
+            _serial: serial,
+        }
+    }
+
+    /// `start`, then log in to the fake instance and load the home timeline.
+    pub fn logged_in() -> Self {
+        let mut h = Self::start();
+        h.type_text(&h.server.url.clone());
+        h.press(KeyCode::Enter);
+        h.type_text(GOOD_CODE);
+        h.press(KeyCode::Enter);
+        h
+    }
+
+    /// One pass of the main loop: background work, draw, then send queued writes.
+    pub fn tick(&mut self) {
+        self.app.ensure_timeline_loaded().expect("load");
+        self.app.step_bulk();
+        self.app.check_follow_requests();
+        self.terminal.draw(|f| self.app.draw(f)).expect("draw");
+        self.app.flush_outbox();
+    }
+
+    pub fn press(&mut self, code: KeyCode) {
+        self.press_with(code, KeyModifiers::NONE);
+    }
+
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        self.app
+            .handle_key(KeyEvent::new(code, modifiers))
+            .expect("key");
+        self.tick();
+    }
+
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c));
+        }
+    }
+
+    /// The screen as drawn on the last tick, one line per row.
+    pub fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        let width = usize::from(buffer.area.width);
+        buffer
+            .content()
+            .chunks(width)
+            .map(|row| {
+                row.iter()
+                    .map(ratatui::buffer::Cell::symbol)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Panic with the screen unless it shows `text`.
+    pub fn assert_shows(&self, text: &str) {
+        let screen = self.screen();
+        assert!(
+            screen.contains(text),
+            "expected {text:?} on screen:\n{screen}"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! End-to-end tests: the app against a fake Mastodon server over real HTTP, driven through
+//! key presses. See `common` for the harness.
+
+// The harness holds the lock that keeps these tests apart for the whole test on purpose.
+#![allow(clippy::significant_drop_tightening)]
+
+mod common;
+
+use axum::http::Method;
+use crossterm::event::KeyCode;
+
+use common::{Harness, GOOD_CODE};
+
+#[test]
+fn login_registers_app_exchanges_code_and_loads_home() {
+    let mut h = Harness::start();
+    h.tick();
+    h.assert_shows("Instance URL");
+    let url = h.server.url.clone();
+    h.type_text(&url);
+    h.press(KeyCode::Enter);
+    assert_eq!(h.server.requests_to(&Method::POST, "/api/v1/apps").len(), 1);
+    h.assert_shows("/oauth/authorize");
+
+    h.type_text("wrong-code");
+    h.press(KeyCode::Enter);
+    h.assert_shows("Login failed");
+
+    h.type_text(GOOD_CODE);
+    h.press(KeyCode::Enter);
+    let tokens = h.server.requests_to(&Method::POST, "/oauth/token");
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens[1].body.contains("grant_type=authorization_code"));
+    assert!(tokens[1].body.contains("code_verifier="));
+    h.assert_shows("Good morning, fediverse!");
+    h.assert_shows("Reading the #rust book today");
+    h.assert_shows("API ");
+
+    let config = std::fs::read_to_string(
+        std::path::Path::new(&std::env::var("XDG_CONFIG_HOME").unwrap())
+            .join("mastotui/config.toml"),
+    )
+    .unwrap();
+    assert!(config.contains("mock-client-id"));
+    assert!(
+        !config.contains("mock-client-secret") && !config.contains("mock-access-token"),
+        "secrets stay out of the config file"
+    );
+}
+
+#[test]
+fn stored_login_is_reused_on_restart() {
+    let mut h = Harness::logged_in();
+    h.app = mastotui::app::App::new().unwrap();
+    h.tick();
+    h.tick();
+    h.assert_shows("Good morning, fediverse!");
+    assert_eq!(
+        h.server.requests_to(&Method::POST, "/oauth/token").len(),
+        1,
+        "the stored token is used, no second login"
+    );
+}
+
+#[test]
+fn load_more_follows_max_id_pages() {
+    let mut h = Harness::logged_in();
+    h.assert_shows("Reading the #rust book today");
+    assert!(!h.screen().contains("Lunch was great"));
+
+    h.press(KeyCode::Char('m'));
+    h.press(KeyCode::Char('m'));
+    let pages = h.server.requests_to(&Method::GET, "/api/v1/timelines/home");
+    let queries: Vec<String> = pages.iter().filter_map(|r| r.query.clone()).collect();
+    assert!(queries.contains(&"limit=20&max_id=1004".to_string()));
+    assert!(queries.contains(&"limit=20&max_id=1002".to_string()));
+    for _ in 0..4 {
+        h.press(KeyCode::Char('j'));
+    }
+    h.assert_shows("First post here");
+}
+
+#[test]
+fn boost_is_sent_with_idempotency_key_and_counted() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Enter);
+    h.assert_shows("Good morning, fediverse!");
+    h.assert_shows("⟳ 3");
+
+    h.press(KeyCode::Char('b'));
+    let boosts = h
+        .server
+        .requests_to(&Method::POST, "/api/v1/statuses/1005/reblog");
+    assert_eq!(boosts.len(), 1);
+    assert!(boosts[0].idempotency_key.is_some());
+    assert_eq!(h.server.status("1005").unwrap()["reblogs_count"], 4);
+    h.assert_shows("⟳ 4");
+
+    h.press(KeyCode::Char('b'));
+    assert_eq!(
+        h.server
+            .requests_to(&Method::POST, "/api/v1/statuses/1005/unreblog")
+            .len(),
+        1
+    );
+    h.assert_shows("⟳ 3");
+}
+
+#[test]
+fn favourite_is_drawn_before_it_is_sent_and_rolled_back_when_refused() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Enter);
+    h.assert_shows("★ 7");
+    h.server.fail_next(
+        Method::POST,
+        "/api/v1/statuses/1005/favourite",
+        422,
+        "error_422.json",
+    );
+
+    // The key press queues the favourite; the frame drawn next shows it done, and only then
+    // does the main loop send it.
+    h.app
+        .handle_key(crossterm::event::KeyEvent::from(KeyCode::Char('f')))
+        .unwrap();
+    assert!(h
+        .server
+        .requests_to(&Method::POST, "/api/v1/statuses/1005/favourite")
+        .is_empty());
+    h.tick();
+    h.assert_shows("★ 8");
+    h.assert_shows("Favourited.");
+    assert_eq!(
+        h.server
+            .requests_to(&Method::POST, "/api/v1/statuses/1005/favourite")
+            .len(),
+        1
+    );
+
+    h.tick();
+    h.assert_shows("★ 7");
+    h.assert_shows("was refused");
+}
+
+#[test]
+fn post_reaches_the_server_and_shows_after_refresh() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Char('p'));
+    h.type_text("Hello from the tests");
+    h.press(KeyCode::Enter);
+
+    let posts = h.server.requests_to(&Method::POST, "/api/v1/statuses");
+    assert_eq!(posts.len(), 1);
+    let body: serde_json::Value = serde_json::from_str(&posts[0].body).unwrap();
+    assert_eq!(body["status"], "Hello from the tests");
+    assert!(posts[0].idempotency_key.is_some());
+
+    h.press(KeyCode::Char('r'));
+    h.assert_shows("Hello from the tests");
+}
+
+#[test]
+fn rejected_post_keeps_compose_open_with_the_server_message() {
+    let mut h = Harness::logged_in();
+    h.server
+        .fail_next(Method::POST, "/api/v1/statuses", 422, "error_422.json");
+    h.press(KeyCode::Char('p'));
+    h.type_text("Too long, says the server");
+    h.press(KeyCode::Enter);
+    h.assert_shows("Text character limit of 500 exceeded");
+    h.assert_shows("Too long, says the server");
+
+    h.press(KeyCode::Enter);
+    assert_eq!(
+        h.server
+            .requests_to(&Method::POST, "/api/v1/statuses")
+            .len(),
+        2
+    );
+    h.press(KeyCode::Char('r'));
+    h.assert_shows("Too long, says the server");
+}
+
+#[test]
+fn list_timeline_is_fetched_by_id() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Char('L'));
+    h.assert_shows("Friends & Family");
+    h.press(KeyCode::Esc);
+
+    h.press(KeyCode::Char('t'));
+    h.assert_shows("Friends & Family");
+    for _ in 0..3 {
+        h.press(KeyCode::Down);
+    }
+    h.press(KeyCode::Enter);
+    h.assert_shows("Only on the list");
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/list/42")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn anonymous_browsing_retries_unavailable_server_and_tracks_the_rate_limit() {
+    let mut h = Harness::start();
+    h.server.fail_times(
+        Method::GET,
+        "/api/v1/timelines/public",
+        503,
+        "error_404.json",
+        3,
+    );
+    h.press(KeyCode::Char('i'));
+    h.press(KeyCode::Char('b'));
+    let url = h.server.url.clone();
+    h.type_text(&url);
+    h.press(KeyCode::Enter);
+
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/public")
+            .len(),
+        5,
+        "503 retried three times, then 401 answered with an app token"
+    );
+    assert_eq!(
+        h.server
+            .requests_to(&Method::POST, "/oauth/token")
+            .iter()
+            .filter(|r| r.body.contains("grant_type=client_credentials"))
+            .count(),
+        1
+    );
+    h.assert_shows("Good morning, fediverse!");
+    h.assert_shows("API ");
+}
+
+#[test]
+fn long_retry_after_is_reported_at_once_instead_of_freezing() {
+    let mut h = Harness::logged_in();
+    let before = h
+        .server
+        .requests_to(&Method::GET, "/api/v1/timelines/home")
+        .len();
+    h.server
+        .fail_next(Method::GET, "/api/v1/timelines/home", 429, "error_422.json");
+    let started = std::time::Instant::now();
+    h.press(KeyCode::Char('r'));
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/home")
+            .len(),
+        before + 1,
+        "not retried"
+    );
+    h.assert_shows("offline");
+}
+
+#[test]
+fn expired_token_returns_to_login() {
+    let mut h = Harness::logged_in();
+    h.server
+        .fail_next(Method::GET, "/api/v1/timelines/home", 401, "error_404.json");
+    h.press(KeyCode::Char('r'));
+    h.assert_shows("Session expired");
+}
+
+#[test]
+fn fixtures_decode_into_api_types() {
+    let home: Vec<mastotui::api::Status> =
+        serde_json::from_value(common::fixture("home.json")).unwrap();
+    assert_eq!(home.len(), 5);
+    assert_eq!(
+        home[2].reblog.as_ref().map(|r| r.account.handle()),
+        Some("cat@other.example")
+    );
+    let lists: Vec<mastotui::api::List> =
+        serde_json::from_value(common::fixture("lists.json")).unwrap();
+    assert_eq!(lists[0].id, "42");
+}
+
+#[test]
+fn palette_switches_to_a_list_timeline() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Char(':'));
+    h.assert_shows("Refresh from the top");
+    h.type_text("friends");
+    h.assert_shows("List: Friends & Family");
+    h.press(KeyCode::Enter);
+    h.assert_shows("Only on the list");
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/list/42")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn palette_offers_the_global_commands_in_every_view() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Char('o'));
+    h.press(KeyCode::Char(':'));
+    h.assert_shows("Discard");
+    h.assert_shows("Switch account");
+    h.type_text("new toot");
+    h.press(KeyCode::Enter);
+    h.assert_shows("[Alt+Enter] newline");
+
+    h.press(KeyCode::Esc);
+    h.press(KeyCode::F(12));
+    h.press(KeyCode::Char(':'));
+    h.type_text("local");
+    h.assert_shows("Timeline: Local");
+    h.press(KeyCode::Enter);
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/public")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn columns_load_on_their_own_and_refresh_only_the_focused_one() {
+    let mut h = Harness::logged_in();
+    h.app.config.as_mut().unwrap().columns = vec!["home".into(), "list:Friends & Family".into()];
+    h.press(KeyCode::Char('|'));
+    h.press(KeyCode::Char('|'));
+    h.tick();
+    h.assert_shows("Friends & Family");
+    h.assert_shows("Only on the list");
+    h.assert_shows("Good morning, fediverse!");
+    let homes = h
+        .server
+        .requests_to(&Method::GET, "/api/v1/timelines/home")
+        .len();
+    let list = |h: &Harness| {
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/list/42")
+            .len()
+    };
+    assert_eq!(list(&h), 1);
+
+    h.press(KeyCode::Tab);
+    assert_eq!(h.app.focused_column, 1);
+    h.press(KeyCode::Char('r'));
+    assert_eq!(list(&h), 2);
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/timelines/home")
+            .len(),
+        homes,
+        "the home column is left alone"
+    );
+    h.press(KeyCode::BackTab);
+    h.press(KeyCode::Char('|'));
+    h.assert_shows("Good morning, fediverse!");
+    assert!(!h.screen().contains("Only on the list"));
+}
+
+// r[verify follow-requests.count]
+#[test]
+fn follow_requests_are_not_checked_for_an_unlocked_account() {
+    let mut h = Harness::logged_in();
+    h.press(KeyCode::Char('r'));
+    assert!(h
+        .server
+        .requests_to(&Method::GET, "/api/v1/follow_requests")
+        .is_empty());
+}
+
+// r[verify follow-requests.count]
+#[test]
+fn follow_requests_of_a_locked_account_are_not_checked_on_every_refresh() {
+    let mut h = Harness::start();
+    h.server.lock_account();
+    h.type_text(&h.server.url.clone());
+    h.press(KeyCode::Enter);
+    h.type_text(GOOD_CODE);
+    h.press(KeyCode::Enter);
+    h.press(KeyCode::Char('r'));
+    h.press(KeyCode::Char('r'));
+    assert_eq!(
+        h.server
+            .requests_to(&Method::GET, "/api/v1/follow_requests")
+            .len(),
+        1
+    );
+}
+
+// r[verify errors.debug]
+#[test]
+fn debug_panel_shows_errors_while_browsing_anonymously() {
+    let mut h = Harness::start();
+    h.server.fail_next(
+        Method::GET,
+        "/api/v1/timelines/public",
+        422,
+        "error_422.json",
+    );
+    h.press(KeyCode::Char('i'));
+    h.press(KeyCode::Char('b'));
+    let url = h.server.url.clone();
+    h.type_text(&url);
+    h.press(KeyCode::Enter);
+    h.press(KeyCode::F(12));
+    h.assert_shows("422 ");
+    h.assert_shows("/api/v1/timelines/public");
+}
@@ -0,0 +1,274 @@
+//! Snapshot tests for the main views: each is drawn on a `TestBackend` at a few terminal
+//! sizes with the posts in `tests/fixtures/views.json` (CJK, emoji, RTL text, a boost, a
+//! filtered post) and compared with the stored buffer in `tests/snapshots`.
+//!
+//! After an intended change to a view, review and accept the new buffers with
+//! `cargo insta review` (or rerun with `INSTA_UPDATE=always` and check the diff).
+
+use mastotui::api::{RateLimit, Status};
+use mastotui::app::TimelineSelection;
+use mastotui::keymap::{KeyView, Keymap};
+use mastotui::tui::{
+    draw_compose, draw_help, draw_instance_info, draw_instance_picker, draw_login, draw_split,
+    draw_timeline, draw_timeline_columns, draw_timeline_picker, draw_toot_detail, TimelinePane,
+};
+use ratatui::backend::TestBackend;
+use ratatui::{Frame, Terminal};
+
+/// Terminal sizes every view is drawn at: narrow, classic and wide.
+const SIZES: [(u16, u16); 3] = [(40, 14), (80, 24), (120, 36)];
+
+fn statuses() -> Vec<Status> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/views.json");
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn status(id: &str) -> Status {
+    statuses().into_iter().find(|s| s.id == id).unwrap()
+}
+
+/// Draw at every size and compare each buffer with its snapshot `<name>_<w>x<h>`.
+fn assert_views(name: &str, draw: impl Fn(&mut Frame)) {
+    for (width, height) in SIZES {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| draw(f)).unwrap();
+        insta::assert_snapshot!(
+            format!("{name}_{width}x{height}"),
+            terminal.backend().to_string()
+        );
+    }
+}
+
+#[test]
+fn timeline() {
+    let keymap = Keymap::default();
+    let statuses = statuses();
+    let rows: Vec<(&Status, Option<&str>)> = statuses.iter().map(|s| (s, None)).collect();
+    let quota = RateLimit {
+        limit: 300,
+        remaining: 280,
+        reset: None,
+    };
+    assert_views("timeline", |f| {
+        draw_timeline(f, &keymap, "Home", &rows, 1, 0, 2, Some(&quota), false, "");
+    });
+}
+
+#[test]
+fn timeline_with_hidden_posts_and_low_quota() {
+    let keymap = Keymap::default();
+    let statuses = statuses();
+    let rows: Vec<(&Status, Option<&str>)> = statuses
+        .iter()
+        .map(|s| (s, (s.id == "104").then_some("boosts from @sara")))
+        .collect();
+    let quota = RateLimit {
+        limit: 300,
+        remaining: 12,
+        reset: None,
+    };
+    assert_views("timeline_hidden", |f| {
+        draw_timeline(
+            f,
+            &keymap,
+            "Home · 1 hidden [h]",
+            &rows,
+            3,
+            2,
+            0,
+            Some(&quota),
+            false,
+            "",
+        );
+    });
+}
+
+#[test]
+fn timeline_empty_and_loading() {
+    let keymap = Keymap::default();
+    assert_views("timeline_empty", |f| {
+        draw_timeline(f, &keymap, "Home", &[], 0, 0, 0, None, false, "");
+    });
+    assert_views("timeline_loading", |f| {
+        draw_timeline(f, &keymap, "Local", &[], 0, 0, 0, None, true, "");
+    });
+}
+
+#[test]
+fn toot_detail() {
+    let keymap = Keymap::default();
+    for id in ["106", "105", "104", "102"] {
+        let status = status(id);
+        assert_views(&format!("toot_detail_{id}"), |f| {
+            draw_toot_detail(f, &keymap, &status, false, "");
+        });
+    }
+}
+
+#[test]
+fn toot_detail_of_a_boost() {
+    let keymap = Keymap::default();
+    let boost = status("103");
+    assert_views("toot_detail_boost", |f| {
+        draw_toot_detail(f, &keymap, &boost, false, "Boosted");
+    });
+}
+
+#[test]
+fn toot_detail_of_a_filtered_post() {
+    let keymap = Keymap::default();
+    let filtered = status("101");
+    assert_views("toot_detail_filtered", |f| {
+        draw_toot_detail(f, &keymap, &filtered, false, "");
+    });
+    assert_views("toot_detail_filtered_revealed", |f| {
+        draw_toot_detail(f, &keymap, &filtered, true, "");
+    });
+}
+
+#[test]
+fn compose() {
+    assert_views("compose", |f| {
+        draw_compose(
+            f,
+            "こんにちは 🌸\nمرحبا\nSecond line of a reply",
+            "Reply to @yamada@example.jp",
+            "",
+            false,
+            "",
+            500,
+        );
+    });
+    assert_views("compose_schedule_error", |f| {
+        draw_compose(
+            f,
+            "Later",
+            "New toot",
+            "tomorrow 9:00",
+            true,
+            "Validation failed: Text character limit of 500 exceeded",
+            500,
+        );
+    });
+}
+
+#[test]
+fn login() {
+    assert_views("login_url", |f| {
+        draw_login(f, "https://mastodon.social", "", "https://例え.jp", "");
+    });
+    assert_views("login_code", |f| {
+        draw_login(
+            f,
+            "https://mastodon.social",
+            "https://mastodon.social/oauth/authorize?client_id=abc&scope=read+write",
+            "abc123",
+            "Login failed: invalid_grant",
+        );
+    });
+}
+
+#[test]
+fn instance_picker() {
+    let known = vec![
+        "https://mastodon.social".to_string(),
+        "https://例え.jp".to_string(),
+    ];
+    assert_views("instance_picker", |f| {
+        draw_instance_picker(f, "fosstodon.org", &known, 1, "");
+    });
+}
+
+#[test]
+fn instance_info() {
+    let keymap = Keymap::default();
+    assert_views("instance_info", |f| {
+        draw_instance_info(f, &keymap, "https://mastodon.social", true, None);
+    });
+    assert_views("instance_info_anonymous", |f| {
+        draw_instance_info(f, &keymap, "", false, Some("https://例え.jp"));
+    });
+}
+
+#[test]
+fn timeline_picker() {
+    let options = vec![
+        TimelineSelection::Home,
+        TimelineSelection::Local,
+        TimelineSelection::Public,
+        TimelineSelection::List {
+            id: "1".into(),
+            title: "友達 🍣".into(),
+        },
+        TimelineSelection::List {
+            id: "2".into(),
+            title: "أصدقاء".into(),
+        },
+    ];
+    assert_views("timeline_picker", |f| {
+        draw_timeline_picker(f, &options, 3, "");
+    });
+}
+
+#[test]
+fn help_overlay() {
+    let statuses = statuses();
+    let rows: Vec<(&Status, Option<&str>)> = statuses.iter().map(|s| (s, None)).collect();
+    let keymap = Keymap::default();
+    assert_views("help_timeline", |f| {
+        draw_timeline(f, &keymap, "Home", &rows, 0, 0, 0, None, false, "");
+        draw_help(f, "Timeline", &keymap.help(KeyView::Timeline));
+    });
+    let status = status("105");
+    assert_views("help_toot_detail", |f| {
+        draw_toot_detail(f, &keymap, &status, false, "");
+        draw_help(f, "Toot", &keymap.help(KeyView::TootDetail));
+    });
+}
+
+#[test]
+fn split_and_columns() {
+    let keymap = Keymap::default();
+    let statuses = statuses();
+    let rows: Vec<(&Status, Option<&str>)> = statuses.iter().map(|s| (s, None)).collect();
+    let pane = |label, selected| TimelinePane {
+        label,
+        rows: &rows,
+        selected,
+        scroll: 0,
+        unread: 0,
+        loading: false,
+        message: "",
+    };
+    assert_views("split_preview", |f| {
+        draw_split(
+            f,
+            &keymap,
+            &pane("Home", 2),
+            Some((rows[2].0, false, "")),
+            false,
+            None,
+        );
+    });
+    assert_views("split_detail", |f| {
+        draw_split(
+            f,
+            &keymap,
+            &pane("Home", 1),
+            Some((rows[1].0, false, "Boosted")),
+            true,
+            None,
+        );
+    });
+    let failed = TimelinePane {
+        message: "Not found",
+        ..pane("Local", 0)
+    };
+    let loading = TimelinePane {
+        loading: true,
+        ..pane("友達 🍣", 0)
+    };
+    assert_views("columns", |f| {
+        draw_timeline_columns(f, &keymap, &[pane("Home", 1), failed, loading], 0, None);
+    });
+}